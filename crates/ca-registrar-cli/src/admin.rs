@@ -0,0 +1,174 @@
+//! Commands for the registrar administrators
+
+use anyhow::{anyhow, Result};
+use ca_registrar_client::constants::*;
+use ca_registrar_client::pda::*;
+use ca_registrar_client::*;
+use clap::Args;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::context::Context;
+use crate::output::TransactionReport;
+
+#[derive(Args)]
+pub struct InitArgs {
+    /// Base price of a one-year registration in USD cents
+    #[arg(long)]
+    base_price: u64,
+
+    /// Grace period after expiry during which the owner can still renew, in seconds
+    #[arg(long, default_value_t = 30 * SECONDS_PER_DAY)]
+    grace_period: i64,
+
+    /// Delay before queued admin changes can be executed, in seconds
+    #[arg(long, default_value_t = 0)]
+    timelock_delay: i64,
+
+    /// Super-admin of the registrar, defaults to the keypair
+    #[arg(long)]
+    authority: Option<Pubkey>,
+}
+
+/// Initialize the registrar, the keypair must be the program upgrade authority
+pub fn init(context: &Context, args: InitArgs) -> Result<()> {
+    let payer = context.payer()?;
+    let authority = args.authority.unwrap_or(payer.pubkey());
+
+    let instruction = instructions::initialize(
+        &payer.pubkey(),
+        args.base_price,
+        args.grace_period,
+        args.timelock_delay,
+        &authority,
+    );
+    let signature = context.send(&payer, &[instruction])?;
+
+    context.output.print(&TransactionReport::new(
+        format!("Initialized the registrar with super-admin {authority}"),
+        signature,
+    ))
+}
+
+/// Queue a parameter change, and execute it in the same transaction when the timelock delay is zero
+fn queue_and_maybe_execute(
+    context: &Context,
+    payer: &Keypair,
+    queue: impl FnOnce(u64) -> Instruction,
+    description: &str,
+) -> Result<()> {
+    let program_state = context.program_state()?;
+    let change_id = program_state.queued_changes_count;
+
+    let mut instructions = vec![queue(change_id)];
+    let executed = program_state.timelock_delay_seconds == 0;
+    if executed {
+        instructions.push(instructions::execute_queued_change(
+            &payer.pubkey(),
+            change_id,
+            &payer.pubkey(),
+            None,
+        ));
+    }
+    let signature = context.send(payer, &instructions)?;
+
+    let message = if executed {
+        format!("Set {description}")
+    } else {
+        format!(
+            "Queued change {change_id} to set {description}, run `ca-registrar execute {change_id}` in {} seconds",
+            program_state.timelock_delay_seconds
+        )
+    };
+    context
+        .output
+        .print(&TransactionReport::new(message, signature))
+}
+
+/// Set the base price of a one-year registration, in USD cents
+pub fn set_price(context: &Context, base_price_usd: u64) -> Result<()> {
+    let payer = context.payer()?;
+
+    queue_and_maybe_execute(
+        context,
+        &payer,
+        |change_id| instructions::update_price(&payer.pubkey(), change_id, base_price_usd),
+        &format!("the base price to {base_price_usd} USD cents"),
+    )
+}
+
+/// Set the grace period, in seconds
+pub fn set_grace(context: &Context, grace_period_seconds: i64) -> Result<()> {
+    let payer = context.payer()?;
+
+    queue_and_maybe_execute(
+        context,
+        &payer,
+        |change_id| {
+            instructions::update_grace_period(&payer.pubkey(), change_id, grace_period_seconds)
+        },
+        &format!("the grace period to {grace_period_seconds} seconds"),
+    )
+}
+
+/// Execute a queued change once its timelock delay has elapsed
+pub fn execute(context: &Context, change_id: u64) -> Result<()> {
+    let payer = context.payer()?;
+    let queued_change = context
+        .fetch::<QueuedChange>(&find_queued_change_address(change_id).0)?
+        .ok_or_else(|| anyhow!("queued change {change_id} does not exist"))?;
+
+    let instruction = instructions::execute_queued_change(
+        &payer.pubkey(),
+        change_id,
+        &queued_change.proposer,
+        queued_change.change.target_domain_record().as_ref(),
+    );
+    let signature = context.send(&payer, &[instruction])?;
+
+    context.output.print(&TransactionReport::new(
+        format!("Executed change {change_id}"),
+        signature,
+    ))
+}
+
+/// Hand the super-admin role over to a new key
+pub fn set_authority(context: &Context, new_authority: &Pubkey) -> Result<()> {
+    let payer = context.payer()?;
+
+    let instruction = instructions::update_authority(&payer.pubkey(), new_authority);
+    let signature = context.send(&payer, &[instruction])?;
+
+    context.output.print(&TransactionReport::new(
+        format!("Transferred the super-admin role to {new_authority}"),
+        signature,
+    ))
+}
+
+/// Withdraw the collected fees to the fee destinations and the treasurer
+pub fn withdraw(context: &Context) -> Result<()> {
+    let payer = context.payer()?;
+    let program_state = context.program_state()?;
+    let admin_roles = context.admin_roles()?;
+    let fee_destinations: Vec<_> = program_state
+        .fee_destinations
+        .iter()
+        .map(|fee_destination| fee_destination.wallet)
+        .collect();
+
+    let balance_before = context.balance(&find_program_state_address().0)?;
+    let instruction =
+        instructions::withdraw_fees(&payer.pubkey(), &admin_roles.treasurer, &fee_destinations);
+    let signature = context.send(&payer, &[instruction])?;
+    let balance_after = context.balance(&find_program_state_address().0)?;
+
+    context.output.print(&TransactionReport::new(
+        format!(
+            "Withdrew {} SOL of collected fees",
+            lamports_to_sol(balance_before.saturating_sub(balance_after))
+        ),
+        signature,
+    ))
+}
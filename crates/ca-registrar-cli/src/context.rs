@@ -0,0 +1,168 @@
+//! RPC connection, signer and account fetching shared by every command
+
+use std::path::PathBuf;
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{anyhow, Context as _, Result};
+use ca_registrar_client::fees::SolUsdPrice;
+use ca_registrar_client::pda::*;
+use ca_registrar_client::*;
+use ca_registrar_resolver::Snapshot;
+use solana_account_decoder::UiAccountEncoding;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+use solana_sdk::account::from_account;
+use solana_sdk::clock::Clock;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+
+use crate::output::OutputFormat;
+
+/// Everything a command needs to talk to the cluster
+pub struct Context {
+    rpc: RpcClient,
+    keypair_path: PathBuf,
+
+    /// Pyth SOL/USD price update account passed to paid instructions
+    pub price_feed: Pubkey,
+
+    /// Format of the command output
+    pub output: OutputFormat,
+}
+
+impl Context {
+    pub fn new(
+        url: String,
+        keypair_path: PathBuf,
+        price_feed: Pubkey,
+        output: OutputFormat,
+    ) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(url, CommitmentConfig::confirmed()),
+            keypair_path,
+            price_feed,
+            output,
+        }
+    }
+
+    /// Load the keypair paying for and signing transactions
+    pub fn payer(&self) -> Result<Keypair> {
+        read_keypair_file(&self.keypair_path).map_err(|error| {
+            anyhow!(
+                "failed to read keypair {}: {error}",
+                self.keypair_path.display()
+            )
+        })
+    }
+
+    /// Sign a transaction with the payer and wait for its confirmation
+    pub fn send(&self, payer: &Keypair, instructions: &[Instruction]) -> Result<Signature> {
+        let blockhash = self
+            .rpc
+            .get_latest_blockhash()
+            .context("failed to fetch a recent blockhash")?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            blockhash,
+        );
+
+        self.rpc
+            .send_and_confirm_transaction(&transaction)
+            .context("transaction failed")
+    }
+
+    /// Fetch and deserialize an account, `None` if it does not exist
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<Option<T>> {
+        let account = self
+            .rpc
+            .get_account_with_commitment(address, self.rpc.commitment())
+            .with_context(|| format!("failed to fetch account {address}"))?
+            .value;
+
+        account
+            .map(|account| T::try_deserialize(&mut account.data.as_slice()))
+            .transpose()
+            .with_context(|| format!("failed to deserialize account {address}"))
+    }
+
+    pub fn program_state(&self) -> Result<ProgramState> {
+        self.fetch(&find_program_state_address().0)?
+            .ok_or_else(|| anyhow!("the registrar is not initialized"))
+    }
+
+    pub fn admin_roles(&self) -> Result<AdminRoles> {
+        self.fetch(&find_admin_roles_address().0)?
+            .ok_or_else(|| anyhow!("the registrar is not initialized"))
+    }
+
+    pub fn price_config(&self) -> Result<PriceConfig> {
+        self.fetch(&find_price_config_address().0)?
+            .ok_or_else(|| anyhow!("the registrar is not initialized"))
+    }
+
+    /// Lamport balance of an account
+    pub fn balance(&self, address: &Pubkey) -> Result<u64> {
+        self.rpc
+            .get_balance(address)
+            .with_context(|| format!("failed to fetch the balance of {address}"))
+    }
+
+    /// Read the SOL/USD rate from the configured price feed
+    pub fn sol_usd_price(&self) -> Result<SolUsdPrice> {
+        let account = self
+            .rpc
+            .get_account(&self.price_feed)
+            .with_context(|| format!("failed to fetch price feed {}", self.price_feed))?;
+
+        Ok(SolUsdPrice::from_price_update(&account.data)?)
+    }
+
+    /// Current cluster time, the one the program compares expiries against
+    pub fn current_timestamp(&self) -> Result<i64> {
+        let account = self
+            .rpc
+            .get_account(&sysvar::clock::ID)
+            .context("failed to fetch the clock")?;
+        let clock: Clock = from_account(&account).ok_or_else(|| anyhow!("invalid clock sysvar"))?;
+
+        Ok(clock.unix_timestamp)
+    }
+
+    /// Capture every account of the program, the clock and the price feed
+    pub fn capture_snapshot(&self) -> Result<Snapshot> {
+        Snapshot::capture(&self.rpc, &[self.price_feed]).context("failed to capture a snapshot")
+    }
+
+    /// Fetch every domain record of the program
+    pub fn domain_records(&self) -> Result<Vec<(Pubkey, DomainRecord)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                0,
+                &DomainRecord::DISCRIMINATOR,
+            ))]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        self.rpc
+            .get_program_accounts_with_config(&ID, config)
+            .context("failed to fetch domain records")?
+            .into_iter()
+            .map(|(address, account)| {
+                let domain_record = DomainRecord::try_deserialize(&mut account.data.as_slice())
+                    .with_context(|| format!("failed to deserialize account {address}"))?;
+                Ok((address, domain_record))
+            })
+            .collect()
+    }
+}
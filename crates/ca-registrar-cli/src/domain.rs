@@ -0,0 +1,396 @@
+//! Commands for domain owners and buyers
+
+use anyhow::{anyhow, bail, Result};
+use ca_registrar_client::fees::quote_registration;
+use ca_registrar_client::pda::*;
+use ca_registrar_client::*;
+use ca_registrar_resolver::{normalize_domain_name, LifecycleState};
+use clap::Args;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+use crate::context::Context;
+use crate::output::*;
+
+/// Parse a `<CHAIN_ID>:<ADDRESS>` pair, e.g. `0:7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU`
+pub fn parse_chain_address(value: &str) -> Result<ChainAddress, String> {
+    let (chain_id, address) = value
+        .split_once(':')
+        .ok_or_else(|| format!("expected <CHAIN_ID>:<ADDRESS>, got {value:?}"))?;
+    let chain_id = chain_id.parse().map_err(|_| {
+        format!("invalid chain id {chain_id:?}, expected a number between 0 and 255")
+    })?;
+
+    Ok(ChainAddress {
+        chain_id,
+        address: address.to_string(),
+    })
+}
+
+#[derive(Args)]
+pub struct RegisterArgs {
+    /// Domain name, with or without the .ca suffix
+    name: String,
+
+    /// Number of years to register the domain for
+    #[arg(long, default_value_t = 1)]
+    years: u32,
+
+    /// Owner of the domain, defaults to the keypair
+    #[arg(long)]
+    owner: Option<Pubkey>,
+
+    /// Address the domain resolves to, as <CHAIN_ID>:<ADDRESS>, can be repeated
+    #[arg(long = "address", value_name = "CHAIN_ID:ADDRESS", value_parser = parse_chain_address)]
+    addresses: Vec<ChainAddress>,
+
+    /// Promo code to apply
+    #[arg(long)]
+    promo_code: Option<String>,
+
+    /// Referrer credited with the registration
+    #[arg(long)]
+    referrer: Option<Pubkey>,
+}
+
+pub fn register(context: &Context, args: RegisterArgs) -> Result<()> {
+    let payer = context.payer()?;
+    let domain_name = normalize_domain_name(&args.name);
+    let owner = args.owner.unwrap_or(payer.pubkey());
+
+    let instruction = instructions::register_domain(
+        &payer.pubkey(),
+        &context.price_feed,
+        &domain_name,
+        RegistrationPeriod::Years { count: args.years },
+        args.addresses,
+        &owner,
+        args.promo_code.as_deref(),
+        args.referrer.as_ref(),
+    );
+    let signature = context.send(&payer, &[instruction])?;
+
+    context.output.print(&TransactionReport::new(
+        format!(
+            "Registered {domain_name}.ca for {} year(s) to {owner}",
+            args.years
+        ),
+        signature,
+    ))
+}
+
+#[derive(Args)]
+pub struct RenewArgs {
+    /// Domain name, with or without the .ca suffix
+    name: String,
+
+    /// Number of years to extend the registration by
+    #[arg(long, default_value_t = 1)]
+    years: u32,
+
+    /// Referrer credited with the renewal
+    #[arg(long)]
+    referrer: Option<Pubkey>,
+}
+
+pub fn renew(context: &Context, args: RenewArgs) -> Result<()> {
+    let payer = context.payer()?;
+    let domain_name = normalize_domain_name(&args.name);
+
+    let instruction = instructions::renew_domain(
+        &payer.pubkey(),
+        &context.price_feed,
+        &domain_name,
+        RegistrationPeriod::Years { count: args.years },
+        args.referrer.as_ref(),
+    );
+    let signature = context.send(&payer, &[instruction])?;
+
+    context.output.print(&TransactionReport::new(
+        format!("Renewed {domain_name}.ca for {} year(s)", args.years),
+        signature,
+    ))
+}
+
+#[derive(Args)]
+pub struct BuyArgs {
+    /// Domain name, with or without the .ca suffix
+    name: String,
+
+    /// Number of years to register the domain for
+    #[arg(long, default_value_t = 1)]
+    years: u32,
+
+    /// New owner of the domain, defaults to the keypair
+    #[arg(long)]
+    owner: Option<Pubkey>,
+
+    /// Address the domain resolves to, as <CHAIN_ID>:<ADDRESS>, can be repeated
+    #[arg(long = "address", value_name = "CHAIN_ID:ADDRESS", value_parser = parse_chain_address)]
+    addresses: Vec<ChainAddress>,
+
+    /// Referrer credited with the purchase
+    #[arg(long)]
+    referrer: Option<Pubkey>,
+}
+
+pub fn buy(context: &Context, args: BuyArgs) -> Result<()> {
+    let payer = context.payer()?;
+    let domain_name = normalize_domain_name(&args.name);
+    let owner = args.owner.unwrap_or(payer.pubkey());
+
+    let instruction = instructions::buy_domain(
+        &payer.pubkey(),
+        &context.price_feed,
+        &domain_name,
+        RegistrationPeriod::Years { count: args.years },
+        args.addresses,
+        &owner,
+        args.referrer.as_ref(),
+    );
+    let signature = context.send(&payer, &[instruction])?;
+
+    context.output.print(&TransactionReport::new(
+        format!(
+            "Bought {domain_name}.ca for {} year(s) for {owner}",
+            args.years
+        ),
+        signature,
+    ))
+}
+
+#[derive(Args)]
+pub struct TransferArgs {
+    /// Domain name, with or without the .ca suffix
+    name: String,
+
+    /// New owner of the domain
+    new_owner: Pubkey,
+}
+
+pub fn transfer(context: &Context, args: TransferArgs) -> Result<()> {
+    let payer = context.payer()?;
+    let domain_name = normalize_domain_name(&args.name);
+
+    let instruction = instructions::transfer_domain(&payer.pubkey(), &domain_name, &args.new_owner);
+    let signature = context.send(&payer, &[instruction])?;
+
+    context.output.print(&TransactionReport::new(
+        format!("Transferred {domain_name}.ca to {}", args.new_owner),
+        signature,
+    ))
+}
+
+#[derive(Args)]
+pub struct SetAddressesArgs {
+    /// Domain name, with or without the .ca suffix
+    name: String,
+
+    /// Address the domain resolves to, as <CHAIN_ID>:<ADDRESS>, can be repeated, replaces all current addresses
+    #[arg(long = "address", value_name = "CHAIN_ID:ADDRESS", value_parser = parse_chain_address, required_unless_present = "clear")]
+    addresses: Vec<ChainAddress>,
+
+    /// Remove every address of the domain
+    #[arg(long, conflicts_with = "addresses")]
+    clear: bool,
+}
+
+pub fn set_addresses(context: &Context, args: SetAddressesArgs) -> Result<()> {
+    let payer = context.payer()?;
+    let domain_name = normalize_domain_name(&args.name);
+    let message = if args.clear {
+        format!("Cleared the addresses of {domain_name}.ca")
+    } else {
+        format!(
+            "Set {} address(es) on {domain_name}.ca",
+            args.addresses.len()
+        )
+    };
+
+    let instruction = instructions::update_addresses(&payer.pubkey(), &domain_name, args.addresses);
+    let signature = context.send(&payer, &[instruction])?;
+
+    context
+        .output
+        .print(&TransactionReport::new(message, signature))
+}
+
+pub fn show(context: &Context, name: &str) -> Result<()> {
+    let domain_name = normalize_domain_name(name);
+    let domain_record_address = find_domain_record_address(&domain_name).0;
+
+    let report = match context.fetch::<DomainRecord>(&domain_record_address)? {
+        Some(domain_record) => {
+            let program_state = context.program_state()?;
+            ShowReport::Registered(DomainReport::new(
+                &domain_record_address,
+                &domain_record,
+                program_state.grace_period_seconds,
+                context.current_timestamp()?,
+            ))
+        }
+        None => ShowReport::Unregistered {
+            domain_name,
+            state: LifecycleState::Unregistered,
+        },
+    };
+
+    context.output.print(&report)
+}
+
+#[derive(Args)]
+pub struct QuoteArgs {
+    /// Domain name, with or without the .ca suffix
+    name: String,
+
+    /// Number of years to quote
+    #[arg(long, default_value_t = 1)]
+    years: u32,
+
+    /// Promo code to apply, registrations only
+    #[arg(long)]
+    promo_code: Option<String>,
+}
+
+pub fn quote(context: &Context, args: QuoteArgs) -> Result<()> {
+    let domain_name = normalize_domain_name(&args.name);
+    let program_state = context.program_state()?;
+    let price_config = context.price_config()?;
+    let sol_usd_price = context.sol_usd_price()?;
+
+    let state = match context.fetch::<DomainRecord>(&find_domain_record_address(&domain_name).0)? {
+        Some(domain_record) => LifecycleState::of(
+            &domain_record,
+            program_state.grace_period_seconds,
+            context.current_timestamp()?,
+        ),
+        None => LifecycleState::Unregistered,
+    };
+
+    let promo_discount = match &args.promo_code {
+        Some(_) if state != LifecycleState::Unregistered => {
+            bail!("promo codes only apply to registrations, {domain_name}.ca is already registered")
+        }
+        Some(code) => {
+            let promo_code = context
+                .fetch::<PromoCode>(&find_promo_code_address(code).0)?
+                .ok_or_else(|| anyhow!("promo code {code:?} does not exist"))?;
+            Some(promo_code.discount)
+        }
+        None => None,
+    };
+
+    let fee_quote = quote_registration(
+        &program_state,
+        &price_config,
+        &RegistrationPeriod::Years { count: args.years },
+        promo_discount.as_ref(),
+        &sol_usd_price,
+    )?;
+
+    context.output.print(&QuoteReport::new(
+        domain_name,
+        state,
+        args.years,
+        fee_quote,
+        &sol_usd_price,
+    ))
+}
+
+pub fn whois(context: &Context, owner: &Pubkey) -> Result<()> {
+    let program_state = context.program_state()?;
+    let current_time = context.current_timestamp()?;
+
+    let mut domains: Vec<_> = context
+        .domain_records()?
+        .iter()
+        .filter(|(_, domain_record)| domain_record.owner == *owner)
+        .map(|(address, domain_record)| {
+            DomainReport::new(
+                address,
+                domain_record,
+                program_state.grace_period_seconds,
+                current_time,
+            )
+        })
+        .collect();
+    domains.sort_by(|a, b| a.domain_name.cmp(&b.domain_name));
+
+    context.output.print(&WhoisReport {
+        owner: owner.to_string(),
+        domains,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Command, FromArgMatches};
+
+    fn parse<T: Args + FromArgMatches>(args: &[&str]) -> Result<T, clap::Error> {
+        let matches = T::augment_args(Command::new("test"))
+            .try_get_matches_from(std::iter::once("test").chain(args.iter().copied()))?;
+        T::from_arg_matches(&matches)
+    }
+
+    #[test]
+    fn chain_address_splits_on_the_first_colon() {
+        assert_eq!(
+            parse_chain_address("1:0x7890123456789012345678901234567890123456").unwrap(),
+            ChainAddress {
+                chain_id: 1,
+                address: "0x7890123456789012345678901234567890123456".to_string(),
+            }
+        );
+        assert_eq!(
+            parse_chain_address("203:v=spf1 include:_spf.example.com").unwrap(),
+            ChainAddress {
+                chain_id: DNS_TXT_CHAIN_ID,
+                address: "v=spf1 include:_spf.example.com".to_string(),
+            }
+        );
+
+        assert!(parse_chain_address("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU").is_err());
+        assert!(parse_chain_address("256:address").is_err());
+        assert!(parse_chain_address("sol:address").is_err());
+    }
+
+    #[test]
+    fn register_defaults_to_one_year_and_repeats_addresses() {
+        let args: RegisterArgs = parse(&["alice"]).unwrap();
+        assert_eq!(args.name, "alice");
+        assert_eq!(args.years, 1);
+        assert!(args.addresses.is_empty());
+        assert_eq!(args.owner, None);
+
+        let args: RegisterArgs = parse(&[
+            "alice.ca",
+            "--years",
+            "3",
+            "--address",
+            "0:7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU",
+            "--address",
+            "200:203.0.113.7",
+            "--promo-code",
+            "LAUNCH",
+        ])
+        .unwrap();
+        assert_eq!(args.years, 3);
+        assert_eq!(args.addresses.len(), 2);
+        assert_eq!(args.addresses[1].chain_id, DNS_A_CHAIN_ID);
+        assert_eq!(args.promo_code.as_deref(), Some("LAUNCH"));
+
+        assert!(parse::<RegisterArgs>(&["alice", "--address", "203.0.113.7"]).is_err());
+        assert!(parse::<RegisterArgs>(&["alice", "--years", "-1"]).is_err());
+    }
+
+    #[test]
+    fn set_addresses_requires_addresses_or_clear() {
+        let args: SetAddressesArgs = parse(&["alice", "--clear"]).unwrap();
+        assert!(args.clear);
+        assert!(args.addresses.is_empty());
+
+        assert!(parse::<SetAddressesArgs>(&["alice"]).is_err());
+        assert!(parse::<SetAddressesArgs>(&["alice", "--clear", "--address", "1:0xab"]).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+//! `ca-registrar` command-line tool for registering and managing .ca domains
+
+mod admin;
+mod context;
+mod domain;
+mod output;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use anyhow::Result;
+use ca_registrar_client::DEFAULT_SOL_USD_PRICE_FEED;
+use clap::{Parser, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::context::Context;
+use crate::output::{OutputFormat, SnapshotReport};
+
+/// RPC endpoint of a local validator
+const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+
+/// Keypair file of the Solana CLI
+const DEFAULT_KEYPAIR_PATH: &str = "~/.config/solana/id.json";
+
+#[derive(Parser)]
+#[command(
+    name = "ca-registrar",
+    version,
+    about = "Register and manage .ca domains"
+)]
+struct Cli {
+    /// RPC endpoint of the cluster
+    #[arg(long, short = 'u', global = true, env = "CA_REGISTRAR_RPC_URL", default_value = DEFAULT_RPC_URL)]
+    url: String,
+
+    /// Keypair file paying for and signing transactions
+    #[arg(long, short = 'k', global = true, env = "CA_REGISTRAR_KEYPAIR", default_value = DEFAULT_KEYPAIR_PATH)]
+    keypair: String,
+
+    /// Pyth SOL/USD price update account used by paid instructions
+    #[arg(long, global = true, default_value_t = DEFAULT_SOL_USD_PRICE_FEED)]
+    price_feed: Pubkey,
+
+    /// Output format
+    #[arg(long, short = 'o', global = true, value_enum, default_value_t = OutputFormat::Human)]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Register an available domain
+    Register(domain::RegisterArgs),
+
+    /// Extend the registration of a domain
+    Renew(domain::RenewArgs),
+
+    /// Buy a domain that expired beyond its grace period
+    Buy(domain::BuyArgs),
+
+    /// Transfer a domain to a new owner
+    Transfer(domain::TransferArgs),
+
+    /// Replace the addresses a domain resolves to
+    SetAddresses(domain::SetAddressesArgs),
+
+    /// Show the owner, lifecycle state and addresses of a domain
+    Show {
+        /// Domain name, with or without the .ca suffix
+        name: String,
+    },
+
+    /// Quote the charge of registering, renewing or buying a domain
+    Quote(domain::QuoteArgs),
+
+    /// List the domains owned by a key
+    Whois {
+        /// Owner to look up
+        owner: Pubkey,
+    },
+
+    /// Admin: initialize the registrar, signed by the program upgrade authority
+    Init(admin::InitArgs),
+
+    /// Admin: set the base price of a one-year registration
+    SetPrice {
+        /// New base price in USD cents
+        base_price: u64,
+    },
+
+    /// Admin: set the grace period after expiry
+    SetGrace {
+        /// New grace period in seconds
+        grace_period: i64,
+    },
+
+    /// Admin: execute a queued change once its timelock delay has elapsed
+    Execute {
+        /// Id of the queued change
+        change_id: u64,
+    },
+
+    /// Admin: hand the super-admin role over to a new key
+    SetAuthority {
+        /// New super-admin
+        new_authority: Pubkey,
+    },
+
+    /// Withdraw the collected fees to the fee destinations and the treasurer
+    Withdraw,
+
+    /// Save the program accounts, the clock and the price feed to a snapshot file, e.g. for the gateway
+    Snapshot {
+        /// File to write the snapshot to
+        path: PathBuf,
+    },
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(relative), Some(home)) => PathBuf::from(home).join(relative),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn snapshot(context: &Context, path: &Path) -> Result<()> {
+    let snapshot = context.capture_snapshot()?;
+    snapshot.save(path)?;
+
+    context.output.print(&SnapshotReport {
+        path: path.display().to_string(),
+        accounts: snapshot.len(),
+    })
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let context = Context::new(
+        cli.url,
+        expand_home(&cli.keypair),
+        cli.price_feed,
+        cli.output,
+    );
+
+    match cli.command {
+        Command::Register(args) => domain::register(&context, args),
+        Command::Renew(args) => domain::renew(&context, args),
+        Command::Buy(args) => domain::buy(&context, args),
+        Command::Transfer(args) => domain::transfer(&context, args),
+        Command::SetAddresses(args) => domain::set_addresses(&context, args),
+        Command::Show { name } => domain::show(&context, &name),
+        Command::Quote(args) => domain::quote(&context, args),
+        Command::Whois { owner } => domain::whois(&context, &owner),
+        Command::Init(args) => admin::init(&context, args),
+        Command::SetPrice { base_price } => admin::set_price(&context, base_price),
+        Command::SetGrace { grace_period } => admin::set_grace(&context, grace_period),
+        Command::Execute { change_id } => admin::execute(&context, change_id),
+        Command::SetAuthority { new_authority } => admin::set_authority(&context, &new_authority),
+        Command::Withdraw => admin::withdraw(&context),
+        Command::Snapshot { path } => snapshot(&context, &path),
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let output = cli.output;
+
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            match output {
+                OutputFormat::Human => eprintln!("Error: {error:#}"),
+                OutputFormat::Json => {
+                    eprintln!("{}", serde_json::json!({ "error": format!("{error:#}") }))
+                }
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("ca-registrar").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn cli_is_well_formed() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn global_options_can_follow_the_subcommand() {
+        let cli = parse(&[
+            "show",
+            "alice.ca",
+            "-o",
+            "json",
+            "-u",
+            "http://localhost:8899",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.output, OutputFormat::Json);
+        assert_eq!(cli.url, "http://localhost:8899");
+        assert_eq!(cli.price_feed, DEFAULT_SOL_USD_PRICE_FEED);
+        assert!(matches!(cli.command, Command::Show { name } if name == "alice.ca"));
+    }
+
+    #[test]
+    fn admin_commands_parse_typed_values() {
+        let new_authority = Pubkey::new_unique();
+
+        assert!(matches!(
+            parse(&["set-price", "600"]).unwrap().command,
+            Command::SetPrice { base_price: 600 }
+        ));
+        assert!(matches!(
+            parse(&["execute", "3"]).unwrap().command,
+            Command::Execute { change_id: 3 }
+        ));
+        assert!(matches!(
+            parse(&["set-authority", &new_authority.to_string()]).unwrap().command,
+            Command::SetAuthority { new_authority: authority } if authority == new_authority
+        ));
+
+        assert!(parse(&["set-price", "$6"]).is_err());
+        assert!(parse(&["whois", "not-a-pubkey"]).is_err());
+        assert!(parse(&["withdraw", "--output", "xml"]).is_err());
+        assert!(parse(&[]).is_err());
+    }
+
+    #[test]
+    fn keypair_path_expands_the_home_directory() {
+        let home = PathBuf::from(std::env::var_os("HOME").unwrap());
+
+        assert_eq!(
+            expand_home("~/.config/solana/id.json"),
+            home.join(".config/solana/id.json")
+        );
+        assert_eq!(expand_home("/tmp/id.json"), PathBuf::from("/tmp/id.json"));
+        assert_eq!(expand_home("~user/id.json"), PathBuf::from("~user/id.json"));
+    }
+}
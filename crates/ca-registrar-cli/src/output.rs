@@ -0,0 +1,382 @@
+//! Human-readable and JSON rendering of command results
+
+use std::fmt;
+
+use anyhow::Result;
+use ca_registrar::instructions::utils::civil_from_days;
+use ca_registrar_client::constants::*;
+use ca_registrar_client::fees::{FeeQuote, SolUsdPrice};
+use ca_registrar_client::*;
+use ca_registrar_resolver::LifecycleState;
+use clap::ValueEnum;
+use serde::Serialize;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+/// Format of the command output
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum OutputFormat {
+    Human,
+    Json,
+}
+
+impl OutputFormat {
+    /// Print a command result in this format
+    pub fn print<T: Serialize + fmt::Display>(self, report: &T) -> Result<()> {
+        match self {
+            OutputFormat::Human => println!("{report}"),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(report)?),
+        }
+
+        Ok(())
+    }
+}
+
+/// Format a Unix timestamp as a UTC date and time
+fn format_timestamp(timestamp: i64) -> String {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(SECONDS_PER_DAY));
+    let seconds = timestamp.rem_euclid(SECONDS_PER_DAY);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Format a price in USD cents as dollars
+fn format_usd(price_usd: u64) -> String {
+    format!("${}.{:02}", price_usd / 100, price_usd % 100)
+}
+
+/// Result of a command that sent a transaction
+#[derive(Serialize)]
+pub struct TransactionReport {
+    pub message: String,
+    pub signature: String,
+}
+
+impl TransactionReport {
+    pub fn new(message: String, signature: Signature) -> Self {
+        Self {
+            message,
+            signature: signature.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TransactionReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.message)?;
+        write!(f, "Signature: {}", self.signature)
+    }
+}
+
+#[derive(Serialize)]
+pub struct AddressReport {
+    pub chain_id: u8,
+    pub address: String,
+}
+
+/// Details of a registered domain
+#[derive(Serialize)]
+pub struct DomainReport {
+    pub domain_name: String,
+    pub account: String,
+    pub state: LifecycleState,
+    pub owner: String,
+    pub registration_timestamp: i64,
+    pub expiry_timestamp: i64,
+    pub grace_period_end_timestamp: i64,
+    pub suspended: bool,
+    pub fuses: u32,
+    pub addresses: Vec<AddressReport>,
+}
+
+impl DomainReport {
+    pub fn new(
+        account: &Pubkey,
+        domain_record: &DomainRecord,
+        grace_period_seconds: i64,
+        current_time: i64,
+    ) -> Self {
+        Self {
+            domain_name: domain_record.domain_name.clone(),
+            account: account.to_string(),
+            state: LifecycleState::of(domain_record, grace_period_seconds, current_time),
+            owner: domain_record.owner.to_string(),
+            registration_timestamp: domain_record.registration_timestamp,
+            expiry_timestamp: domain_record.expiry_timestamp,
+            grace_period_end_timestamp: domain_record
+                .expiry_timestamp
+                .saturating_add(grace_period_seconds),
+            suspended: domain_record.suspended,
+            fuses: domain_record.fuses,
+            addresses: domain_record
+                .addresses
+                .iter()
+                .map(|address| AddressReport {
+                    chain_id: address.chain_id,
+                    address: address.address.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for DomainReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}.ca", self.domain_name)?;
+        writeln!(f, "  Account:      {}", self.account)?;
+        writeln!(
+            f,
+            "  State:        {}{}",
+            self.state,
+            if self.suspended { ", suspended" } else { "" }
+        )?;
+        writeln!(f, "  Owner:        {}", self.owner)?;
+        writeln!(
+            f,
+            "  Registered:   {}",
+            format_timestamp(self.registration_timestamp)
+        )?;
+        writeln!(
+            f,
+            "  Expires:      {}",
+            format_timestamp(self.expiry_timestamp)
+        )?;
+        writeln!(
+            f,
+            "  Grace ends:   {}",
+            format_timestamp(self.grace_period_end_timestamp)
+        )?;
+        if self.fuses != 0 {
+            writeln!(f, "  Fuses:        {:#x}", self.fuses)?;
+        }
+        write!(f, "  Addresses:")?;
+        if self.addresses.is_empty() {
+            write!(f, "    none")?;
+        }
+        for address in &self.addresses {
+            write!(f, "\n    [{}] {}", address.chain_id, address.address)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Lookup of a name that may not be registered
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum ShowReport {
+    Registered(DomainReport),
+    Unregistered {
+        domain_name: String,
+        state: LifecycleState,
+    },
+}
+
+impl fmt::Display for ShowReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShowReport::Registered(domain) => domain.fmt(f),
+            ShowReport::Unregistered { domain_name, .. } => {
+                write!(f, "{domain_name}.ca is not registered")
+            }
+        }
+    }
+}
+
+/// Instruction a quote applies to, depending on the current state of the name
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteAction {
+    Register,
+    Renew,
+    Buy,
+}
+
+/// Expected charge for a name
+#[derive(Serialize)]
+pub struct QuoteReport {
+    pub domain_name: String,
+    pub state: LifecycleState,
+    pub action: QuoteAction,
+    pub years: u32,
+    pub price_usd_cents: u64,
+    pub lamports: u64,
+    pub sol_usd_price: i64,
+    pub sol_usd_exponent: i32,
+    pub price_publish_time: i64,
+}
+
+impl QuoteReport {
+    pub fn new(
+        domain_name: String,
+        state: LifecycleState,
+        years: u32,
+        quote: FeeQuote,
+        sol_usd_price: &SolUsdPrice,
+    ) -> Self {
+        let action = match state {
+            LifecycleState::Unregistered => QuoteAction::Register,
+            LifecycleState::Active | LifecycleState::Grace => QuoteAction::Renew,
+            LifecycleState::Expired => QuoteAction::Buy,
+        };
+
+        Self {
+            domain_name,
+            state,
+            action,
+            years,
+            price_usd_cents: quote.price_usd,
+            lamports: quote.lamports,
+            sol_usd_price: sol_usd_price.price,
+            sol_usd_exponent: sol_usd_price.exponent,
+            price_publish_time: sol_usd_price.publish_time,
+        }
+    }
+}
+
+impl fmt::Display for QuoteReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let action = match self.action {
+            QuoteAction::Register => "Registering",
+            QuoteAction::Renew => "Renewing",
+            QuoteAction::Buy => "Buying",
+        };
+
+        writeln!(
+            f,
+            "{action} {}.ca ({}) for {} year(s)",
+            self.domain_name, self.state, self.years
+        )?;
+        writeln!(f, "  Price:  {}", format_usd(self.price_usd_cents))?;
+        write!(
+            f,
+            "  Charge: {} SOL ({} lamports) at the SOL/USD price of {}",
+            lamports_to_sol(self.lamports),
+            self.lamports,
+            format_timestamp(self.price_publish_time)
+        )
+    }
+}
+
+/// Domains owned by a key
+#[derive(Serialize)]
+pub struct WhoisReport {
+    pub owner: String,
+    pub domains: Vec<DomainReport>,
+}
+
+impl fmt::Display for WhoisReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.domains.is_empty() {
+            return write!(f, "{} owns no domains", self.owner);
+        }
+
+        write!(f, "{} owns {} domain(s)", self.owner, self.domains.len())?;
+        for domain in &self.domains {
+            write!(
+                f,
+                "\n  {}.ca  {}, expires {}",
+                domain.domain_name,
+                domain.state,
+                format_timestamp(domain.expiry_timestamp)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Snapshot written to a file
+#[derive(Serialize)]
+pub struct SnapshotReport {
+    pub path: String,
+    pub accounts: usize,
+}
+
+impl fmt::Display for SnapshotReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Saved {} accounts to {}", self.accounts, self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote_report(state: LifecycleState) -> QuoteReport {
+        QuoteReport::new(
+            "alice".to_string(),
+            state,
+            2,
+            FeeQuote {
+                price_usd: 950,
+                lamports: 63_333_333,
+            },
+            &SolUsdPrice {
+                price: 15_000_000_000,
+                exponent: -8,
+                publish_time: 1_700_000_000,
+            },
+        )
+    }
+
+    #[test]
+    fn timestamps_are_formatted_in_utc() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_timestamp(1_700_000_000), "2023-11-14 22:13:20 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00:00 UTC");
+        assert_eq!(format_timestamp(-1), "1969-12-31 23:59:59 UTC");
+    }
+
+    #[test]
+    fn usd_cents_are_formatted_as_dollars() {
+        assert_eq!(format_usd(0), "$0.00");
+        assert_eq!(format_usd(5), "$0.05");
+        assert_eq!(format_usd(500), "$5.00");
+        assert_eq!(format_usd(123_456), "$1234.56");
+    }
+
+    #[test]
+    fn quote_action_follows_the_lifecycle_state() {
+        assert_eq!(
+            quote_report(LifecycleState::Unregistered).action,
+            QuoteAction::Register
+        );
+        assert_eq!(
+            quote_report(LifecycleState::Active).action,
+            QuoteAction::Renew
+        );
+        assert_eq!(
+            quote_report(LifecycleState::Grace).action,
+            QuoteAction::Renew
+        );
+        assert_eq!(
+            quote_report(LifecycleState::Expired).action,
+            QuoteAction::Buy
+        );
+    }
+
+    #[test]
+    fn quote_is_rendered_for_humans_and_json() {
+        let report = quote_report(LifecycleState::Grace);
+
+        assert_eq!(
+            report.to_string(),
+            "Renewing alice.ca (in grace period) for 2 year(s)\n  \
+             Price:  $9.50\n  \
+             Charge: 0.063333333 SOL (63333333 lamports) at the SOL/USD price of 2023-11-14 22:13:20 UTC"
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["state"], "grace");
+        assert_eq!(json["action"], "renew");
+        assert_eq!(json["price_usd_cents"], 950);
+        assert_eq!(json["sol_usd_exponent"], -8);
+    }
+}
@@ -0,0 +1,451 @@
+//! Fee quotes computed with the program's own pricing code
+//!
+//! Quotes match the program charges as long as the program state, price config and SOL/USD price are the ones
+//! the transaction will see. The program rejects SOL/USD prices older than `PYTH_PRICE_FEED_MAX_AGE` seconds.
+//!
+//! The whole period is priced in USD cents before a single conversion to lamports. Registrations used to convert
+//! the yearly price and multiply it by the number of years, so multi-year quotes can be up to one lamport per year
+//! above what that rounding gave.
+
+use anchor_lang::prelude::*;
+use ca_registrar::constants::*;
+use ca_registrar::error::CaRegistrarError;
+use ca_registrar::instructions::utils::{
+    calculate_price_usd, calculate_share, convert_usd_to_lamports,
+};
+use ca_registrar::state::*;
+use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
+
+/// SOL/USD rate read from a Pyth price update account
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SolUsdPrice {
+    /// Price, scaled by 10^exponent
+    pub price: i64,
+
+    /// Power of ten applied to the price
+    pub exponent: i32,
+
+    /// Timestamp of the price
+    pub publish_time: i64,
+}
+
+impl SolUsdPrice {
+    /// Read the SOL/USD rate from the data of a Pyth `PriceUpdateV2` account
+    ///
+    /// # Errors
+    /// * `InvalidPriceFeed` - Account is not a price update of the SOL/USD feed
+    pub fn from_price_update(mut data: &[u8]) -> Result<Self> {
+        let price_update = PriceUpdateV2::try_deserialize(&mut data)?;
+        require!(
+            price_update.price_message.feed_id == get_feed_id_from_hex(SOL_USD_PRICE_FEED_ID)?,
+            CaRegistrarError::InvalidPriceFeed
+        );
+
+        Ok(Self {
+            price: price_update.price_message.price,
+            exponent: price_update.price_message.exponent,
+            publish_time: price_update.price_message.publish_time,
+        })
+    }
+}
+
+/// Charge of a registration, renewal or purchase
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeeQuote {
+    /// Price after discounts in USD cents
+    pub price_usd: u64,
+
+    /// Lamports charged to the payer, referral share included
+    pub lamports: u64,
+}
+
+/// Price in USD cents of a registration, renewal or purchase, as calculated by the program
+///
+/// Voucher registrations apply their discount as `PromoDiscount::Percentage`.
+pub fn quote_price_usd(
+    program_state: &ProgramState,
+    price_config: &PriceConfig,
+    period: &RegistrationPeriod,
+    promo_discount: Option<&PromoDiscount>,
+) -> Result<u64> {
+    calculate_price_usd(
+        program_state.base_price_usd,
+        period,
+        price_config.multi_year_discount_bps(period.whole_years()),
+        promo_discount,
+    )
+}
+
+/// Lamports charged for a price in USD cents at a SOL/USD rate
+pub fn quote_fee_in_lamports(price_usd: u64, sol_usd_price: &SolUsdPrice) -> Result<u64> {
+    convert_usd_to_lamports(price_usd, sol_usd_price.price, sol_usd_price.exponent)
+}
+
+/// Charge of a registration, renewal or purchase
+pub fn quote_registration(
+    program_state: &ProgramState,
+    price_config: &PriceConfig,
+    period: &RegistrationPeriod,
+    promo_discount: Option<&PromoDiscount>,
+    sol_usd_price: &SolUsdPrice,
+) -> Result<FeeQuote> {
+    let price_usd = quote_price_usd(program_state, price_config, period, promo_discount)?;
+
+    Ok(FeeQuote {
+        price_usd,
+        lamports: quote_fee_in_lamports(price_usd, sol_usd_price)?,
+    })
+}
+
+/// Share of a fee paid to a referrer
+pub fn quote_referral_share(
+    program_state: &ProgramState,
+    referrer_config: &ReferrerConfig,
+    lamports: u64,
+) -> Result<u64> {
+    calculate_share(
+        lamports,
+        referrer_config.share_bps(program_state.default_referral_share_bps),
+    )
+}
+
+/// Price of a subdomain in lamports or token base units, and its protocol fee
+pub fn quote_subdomain(
+    program_state: &ProgramState,
+    subdomain_policy: &SubdomainPolicy,
+    period: &RegistrationPeriod,
+) -> Result<(u64, u64)> {
+    let price = calculate_price_usd(subdomain_policy.price_per_year, period, 0, None)?;
+    let protocol_fee = calculate_share(price, program_state.subdomain_protocol_fee_bps)?;
+
+    Ok((price, protocol_fee))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyth_solana_receiver_sdk::price_update::{PriceFeedMessage, VerificationLevel};
+
+    // $150.00 per SOL, with the exponent Pyth publishes SOL/USD at
+    const SOL_USD: SolUsdPrice = SolUsdPrice {
+        price: 15_000_000_000,
+        exponent: -8,
+        publish_time: 1_700_000_000,
+    };
+
+    fn program_state() -> ProgramState {
+        ProgramState {
+            base_price_usd: 500,
+            domains_registered: 0,
+            grace_period_seconds: 30 * 86_400,
+            paused: false,
+            timelock_delay_seconds: 0,
+            queued_changes_count: 0,
+            config_bounds: ConfigBounds {
+                min_base_price_usd: 0,
+                max_base_price_usd: u64::MAX,
+                min_grace_period_seconds: 0,
+                max_grace_period_seconds: SECONDS_PER_YEAR,
+            },
+            fee_destinations: Vec::new(),
+            default_referral_share_bps: 1_000,
+            sunrise_merkle_root: [0; 32],
+            general_availability_timestamp: 0,
+            airdrop_merkle_root: [0; 32],
+            dispute_timelock_enabled: false,
+            release_refund_rate_bps: 0,
+            max_registration_horizon_seconds: DEFAULT_MAX_REGISTRATION_HORIZON_SECONDS,
+            min_registration_seconds: 0,
+            subdomain_protocol_fee_bps: 500,
+            registration_generations: 0,
+            bump: 255,
+        }
+    }
+
+    fn price_config() -> PriceConfig {
+        PriceConfig {
+            multi_year_discounts: vec![
+                MultiYearDiscount {
+                    min_years: 2,
+                    discount_bps: 500,
+                },
+                MultiYearDiscount {
+                    min_years: 5,
+                    discount_bps: 1_500,
+                },
+            ],
+            bump: 255,
+        }
+    }
+
+    fn quote(period: RegistrationPeriod, promo_discount: Option<&PromoDiscount>) -> FeeQuote {
+        quote_registration(
+            &program_state(),
+            &price_config(),
+            &period,
+            promo_discount,
+            &SOL_USD,
+        )
+        .unwrap()
+    }
+
+    fn charge(price_usd: u64, lamports: u64) -> FeeQuote {
+        FeeQuote {
+            price_usd,
+            lamports,
+        }
+    }
+
+    // Lamports at $150/SOL are cents * 10^9 * 10^8 / (15_000_000_000 * 100), i.e. cents * 200_000 / 3 rounded down
+
+    #[test]
+    fn yearly_quotes_apply_the_multi_year_discount() {
+        // $5.00 a year, 5% off from two years, 15% off from five
+        assert_eq!(
+            quote(RegistrationPeriod::Years { count: 1 }, None),
+            charge(500, 33_333_333)
+        );
+        assert_eq!(
+            quote(RegistrationPeriod::Years { count: 2 }, None),
+            charge(950, 63_333_333)
+        );
+        assert_eq!(
+            quote(RegistrationPeriod::Years { count: 3 }, None),
+            charge(1_425, 95_000_000)
+        );
+        assert_eq!(
+            quote(RegistrationPeriod::Years { count: 5 }, None),
+            charge(2_125, 141_666_666)
+        );
+        assert_eq!(
+            quote(RegistrationPeriod::Years { count: 10 }, None),
+            charge(4_250, 283_333_333)
+        );
+    }
+
+    #[test]
+    fn multi_year_quotes_convert_the_total_price_once() {
+        let no_discounts = PriceConfig {
+            multi_year_discounts: Vec::new(),
+            bump: 255,
+        };
+        let quote = |count| {
+            quote_registration(
+                &program_state(),
+                &no_discounts,
+                &RegistrationPeriod::Years { count },
+                None,
+                &SOL_USD,
+            )
+            .unwrap()
+        };
+
+        // $15.00 converts to 100_000_000 lamports, where three times the yearly 33_333_333 would be 99_999_999
+        assert_eq!(quote(1), charge(500, 33_333_333));
+        assert_eq!(quote(3), charge(1_500, 100_000_000));
+        assert_eq!(quote(2), charge(1_000, 66_666_666));
+    }
+
+    #[test]
+    fn monthly_quotes_are_prorated_and_discounted_by_whole_years() {
+        // Cents are rounded down before the discount
+        assert_eq!(
+            quote(RegistrationPeriod::Months { count: 1 }, None),
+            charge(41, 2_733_333)
+        );
+        assert_eq!(
+            quote(RegistrationPeriod::Months { count: 6 }, None),
+            charge(250, 16_666_666)
+        );
+        assert_eq!(
+            quote(RegistrationPeriod::Months { count: 13 }, None),
+            charge(541, 36_066_666)
+        );
+
+        // 18 months is one whole year, so no discount
+        assert_eq!(
+            quote(RegistrationPeriod::Months { count: 18 }, None),
+            charge(750, 50_000_000)
+        );
+
+        // 24 months gets the two-year discount, 61 months the five-year one on 2_541 cents
+        assert_eq!(
+            quote(RegistrationPeriod::Months { count: 24 }, None),
+            charge(950, 63_333_333)
+        );
+        assert_eq!(
+            quote(RegistrationPeriod::Months { count: 61 }, None),
+            charge(2_160, 144_000_000)
+        );
+    }
+
+    #[test]
+    fn second_quotes_are_prorated_over_a_year() {
+        // Less than a cent is free
+        assert_eq!(
+            quote(RegistrationPeriod::Seconds { seconds: 1 }, None),
+            charge(0, 0)
+        );
+        assert_eq!(
+            quote(RegistrationPeriod::Seconds { seconds: 86_400 }, None),
+            charge(1, 66_666)
+        );
+        assert_eq!(
+            quote(
+                RegistrationPeriod::Seconds {
+                    seconds: SECONDS_PER_YEAR / 2
+                },
+                None
+            ),
+            charge(250, 16_666_666)
+        );
+        assert_eq!(
+            quote(
+                RegistrationPeriod::Seconds {
+                    seconds: SECONDS_PER_YEAR
+                },
+                None
+            ),
+            charge(500, 33_333_333)
+        );
+
+        // Three whole years and 17 seconds get the 5% discount on 1_500 cents
+        assert_eq!(
+            quote(
+                RegistrationPeriod::Seconds {
+                    seconds: 3 * SECONDS_PER_YEAR + 17
+                },
+                None
+            ),
+            charge(1_425, 95_000_000)
+        );
+    }
+
+    #[test]
+    fn promo_discounts_apply_after_the_multi_year_discount() {
+        let percentage = PromoDiscount::Percentage {
+            discount_bps: 2_000,
+        };
+        let fixed = PromoDiscount::FixedUsd { amount_usd: 100 };
+        let larger_than_price = PromoDiscount::FixedUsd { amount_usd: 10_000 };
+        let three_years = RegistrationPeriod::Years { count: 3 };
+        let seven_months = RegistrationPeriod::Months { count: 7 };
+        let million_seconds = RegistrationPeriod::Seconds { seconds: 1_000_000 };
+
+        // 20% off 500, 950, 1_425, 291 and 15 cents
+        assert_eq!(
+            quote(RegistrationPeriod::Years { count: 1 }, Some(&percentage)),
+            charge(400, 26_666_666)
+        );
+        assert_eq!(
+            quote(RegistrationPeriod::Years { count: 2 }, Some(&percentage)),
+            charge(760, 50_666_666)
+        );
+        assert_eq!(
+            quote(three_years, Some(&percentage)),
+            charge(1_140, 76_000_000)
+        );
+        assert_eq!(
+            quote(seven_months, Some(&percentage)),
+            charge(233, 15_533_333)
+        );
+        assert_eq!(
+            quote(million_seconds, Some(&percentage)),
+            charge(12, 800_000)
+        );
+
+        // $1.00 off, never below zero
+        assert_eq!(
+            quote(RegistrationPeriod::Years { count: 2 }, Some(&fixed)),
+            charge(850, 56_666_666)
+        );
+        assert_eq!(quote(three_years, Some(&fixed)), charge(1_325, 88_333_333));
+        assert_eq!(quote(seven_months, Some(&fixed)), charge(191, 12_733_333));
+        assert_eq!(quote(million_seconds, Some(&fixed)), charge(0, 0));
+
+        for period in [
+            RegistrationPeriod::Years { count: 1 },
+            three_years,
+            seven_months,
+            million_seconds,
+        ] {
+            assert_eq!(quote(period, Some(&larger_than_price)), charge(0, 0));
+        }
+    }
+
+    #[test]
+    fn referral_share_uses_the_override_or_the_default() {
+        let mut referrer_config = ReferrerConfig {
+            referrer: Pubkey::new_unique(),
+            share_bps_override: None,
+            active: true,
+            referral_count: 0,
+            total_paid_lamports: 0,
+            bump: 255,
+        };
+        assert_eq!(
+            quote_referral_share(&program_state(), &referrer_config, 33_333_333).unwrap(),
+            3_333_333
+        );
+
+        referrer_config.share_bps_override = Some(2_500);
+        assert_eq!(
+            quote_referral_share(&program_state(), &referrer_config, 33_333_333).unwrap(),
+            8_333_333
+        );
+    }
+
+    #[test]
+    fn subdomain_quotes_are_prorated_with_the_protocol_fee() {
+        let subdomain_policy = SubdomainPolicy {
+            parent: Pubkey::new_unique(),
+            parent_registration_generation: 0,
+            mode: SubdomainIssuanceMode::Open,
+            payment_mint: None,
+            price_per_year: 1_000_000,
+            bump: 255,
+        };
+
+        assert_eq!(
+            quote_subdomain(
+                &program_state(),
+                &subdomain_policy,
+                &RegistrationPeriod::Months { count: 6 }
+            )
+            .unwrap(),
+            (500_000, 25_000)
+        );
+    }
+
+    #[test]
+    fn price_update_must_be_the_sol_usd_feed() {
+        let price_update = |feed_id: [u8; 32]| {
+            let mut data = Vec::new();
+            PriceUpdateV2 {
+                write_authority: Pubkey::new_unique(),
+                verification_level: VerificationLevel::Full,
+                price_message: PriceFeedMessage {
+                    feed_id,
+                    price: SOL_USD.price,
+                    conf: 0,
+                    exponent: SOL_USD.exponent,
+                    publish_time: SOL_USD.publish_time,
+                    prev_publish_time: SOL_USD.publish_time - 1,
+                    ema_price: SOL_USD.price,
+                    ema_conf: 0,
+                },
+                posted_slot: 0,
+            }
+            .try_serialize(&mut data)
+            .unwrap();
+            data
+        };
+
+        let sol_usd = get_feed_id_from_hex(SOL_USD_PRICE_FEED_ID).unwrap();
+        assert_eq!(
+            SolUsdPrice::from_price_update(&price_update(sol_usd)).unwrap(),
+            SOL_USD
+        );
+        assert!(SolUsdPrice::from_price_update(&price_update([1; 32])).is_err());
+    }
+}
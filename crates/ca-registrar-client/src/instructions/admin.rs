@@ -0,0 +1,547 @@
+use crate::instructions::build_instruction;
+use crate::pda::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::system_program;
+use ca_registrar::state::*;
+use ca_registrar::{accounts, instruction};
+
+/// Initialize the program, signed by its upgrade authority
+pub fn initialize(
+    initializer: &Pubkey,
+    base_price_usd: u64,
+    grace_period_seconds: i64,
+    timelock_delay_seconds: i64,
+    initial_authority: &Pubkey,
+) -> Instruction {
+    build_instruction(
+        accounts::InitializeAccountConstraints {
+            initializer: *initializer,
+            program_data: Some(find_program_data_address().0),
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            price_config: find_price_config_address().0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::Initialize {
+            base_price_usd,
+            grace_period_seconds,
+            timelock_delay_seconds,
+            initial_authority: *initial_authority,
+        },
+    )
+}
+
+/// Accounts shared by the queued admin setters
+fn queued_setter_accounts(
+    authority: &Pubkey,
+    change_id: u64,
+) -> accounts::UpdatePriceAccountConstraints {
+    accounts::UpdatePriceAccountConstraints {
+        authority: *authority,
+        program_state: find_program_state_address().0,
+        admin_roles: find_admin_roles_address().0,
+        queued_change: find_queued_change_address(change_id).0,
+        system_program: system_program::ID,
+        event_authority: find_event_authority_address().0,
+        program: ca_registrar::ID,
+    }
+}
+
+/// Queue a new base price in USD cents
+pub fn update_price(authority: &Pubkey, change_id: u64, new_price: u64) -> Instruction {
+    build_instruction(
+        queued_setter_accounts(authority, change_id),
+        instruction::UpdatePrice { new_price },
+    )
+}
+
+/// Queue a new grace period
+pub fn update_grace_period(
+    authority: &Pubkey,
+    change_id: u64,
+    grace_period_seconds: i64,
+) -> Instruction {
+    build_instruction(
+        queued_setter_accounts(authority, change_id),
+        instruction::UpdateGracePeriod {
+            grace_period_seconds,
+        },
+    )
+}
+
+/// Queue a new timelock delay
+pub fn update_timelock_delay(
+    authority: &Pubkey,
+    change_id: u64,
+    timelock_delay_seconds: i64,
+) -> Instruction {
+    build_instruction(
+        queued_setter_accounts(authority, change_id),
+        instruction::UpdateTimelockDelay {
+            timelock_delay_seconds,
+        },
+    )
+}
+
+/// Queue a new maximum registration horizon
+pub fn update_registration_horizon(
+    authority: &Pubkey,
+    change_id: u64,
+    max_registration_horizon_seconds: i64,
+) -> Instruction {
+    build_instruction(
+        queued_setter_accounts(authority, change_id),
+        instruction::UpdateRegistrationHorizon {
+            max_registration_horizon_seconds,
+        },
+    )
+}
+
+/// Queue a new minimum registration duration
+pub fn update_min_registration_duration(
+    authority: &Pubkey,
+    change_id: u64,
+    min_registration_seconds: i64,
+) -> Instruction {
+    build_instruction(
+        queued_setter_accounts(authority, change_id),
+        instruction::UpdateMinRegistrationDuration {
+            min_registration_seconds,
+        },
+    )
+}
+
+/// Queue enabling or disabling the timelock on dispute actions
+pub fn update_dispute_timelock(authority: &Pubkey, change_id: u64, enabled: bool) -> Instruction {
+    build_instruction(
+        queued_setter_accounts(authority, change_id),
+        instruction::UpdateDisputeTimelock { enabled },
+    )
+}
+
+/// Set the allowed ranges for base price and grace period
+pub fn update_config_bounds(authority: &Pubkey, config_bounds: ConfigBounds) -> Instruction {
+    build_instruction(
+        accounts::UpdateConfigBoundsAccountConstraints {
+            authority: *authority,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::UpdateConfigBounds { config_bounds },
+    )
+}
+
+/// Cancel a queued change, returning its rent to the proposer
+pub fn cancel_queued_change(authority: &Pubkey, change_id: u64, proposer: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::CancelQueuedChangeAccountConstraints {
+            authority: *authority,
+            admin_roles: find_admin_roles_address().0,
+            queued_change: find_queued_change_address(change_id).0,
+            proposer: *proposer,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::CancelQueuedChange { change_id },
+    )
+}
+
+/// Execute a queued change once its delay has elapsed, dispute actions need the domain record they target
+pub fn execute_queued_change(
+    signer: &Pubkey,
+    change_id: u64,
+    proposer: &Pubkey,
+    domain_record: Option<&Pubkey>,
+) -> Instruction {
+    build_instruction(
+        accounts::ExecuteQueuedChangeAccountConstraints {
+            signer: *signer,
+            program_state: find_program_state_address().0,
+            queued_change: find_queued_change_address(change_id).0,
+            proposer: *proposer,
+            domain_record: domain_record.copied(),
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::ExecuteQueuedChange { change_id },
+    )
+}
+
+/// Assign an admin role to a new key
+pub fn update_role(authority: &Pubkey, role: AdminRole, new_holder: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::UpdateRoleAccountConstraints {
+            authority: *authority,
+            admin_roles: find_admin_roles_address().0,
+            new_holder: *new_holder,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::UpdateRole { role },
+    )
+}
+
+/// Transfer the super-admin role
+pub fn update_authority(authority: &Pubkey, new_authority: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::UpdateAuthorityAccountConstraints {
+            authority: *authority,
+            admin_roles: find_admin_roles_address().0,
+            new_authority: *new_authority,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::UpdateAuthority {},
+    )
+}
+
+/// Pause or resume registrations, renewals and purchases
+pub fn set_paused(authority: &Pubkey, paused: bool) -> Instruction {
+    build_instruction(
+        accounts::SetPausedAccountConstraints {
+            authority: *authority,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::SetPaused { paused },
+    )
+}
+
+/// Configure the fee destinations
+pub fn update_fee_split(authority: &Pubkey, fee_destinations: Vec<FeeDestination>) -> Instruction {
+    build_instruction(
+        accounts::UpdateFeeSplitAccountConstraints {
+            authority: *authority,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::UpdateFeeSplit { fee_destinations },
+    )
+}
+
+/// Replace the multi-year discount schedule
+pub fn update_multi_year_discounts(
+    authority: &Pubkey,
+    multi_year_discounts: Vec<MultiYearDiscount>,
+) -> Instruction {
+    build_instruction(
+        accounts::UpdateMultiYearDiscountsAccountConstraints {
+            authority: *authority,
+            admin_roles: find_admin_roles_address().0,
+            price_config: find_price_config_address().0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::UpdateMultiYearDiscounts {
+            multi_year_discounts,
+        },
+    )
+}
+
+/// Create a promo code from its plain-text code
+pub fn create_promo_code(
+    authority: &Pubkey,
+    code: &str,
+    discount: PromoDiscount,
+    max_uses: u32,
+    expiry_timestamp: i64,
+) -> Instruction {
+    build_instruction(
+        accounts::CreatePromoCodeAccountConstraints {
+            authority: *authority,
+            admin_roles: find_admin_roles_address().0,
+            promo_code: find_promo_code_address(code).0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::CreatePromoCode {
+            code_hash: hash(code.as_bytes()).to_bytes(),
+            discount,
+            max_uses,
+            expiry_timestamp,
+        },
+    )
+}
+
+/// Revoke a promo code
+pub fn close_promo_code(authority: &Pubkey, code: &str) -> Instruction {
+    build_instruction(
+        accounts::ClosePromoCodeAccountConstraints {
+            authority: *authority,
+            admin_roles: find_admin_roles_address().0,
+            promo_code: find_promo_code_address(code).0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::ClosePromoCode {},
+    )
+}
+
+/// Reserve a domain name
+pub fn add_reserved_name(authority: &Pubkey, domain_name: &str) -> Instruction {
+    build_instruction(
+        accounts::AddReservedNameAccountConstraints {
+            authority: *authority,
+            admin_roles: find_admin_roles_address().0,
+            reserved_name: find_reserved_name_address(domain_name).0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::AddReservedName {
+            name_hash: hash(domain_name.as_bytes()).to_bytes(),
+        },
+    )
+}
+
+/// Remove a domain name from the reserved names
+pub fn remove_reserved_name(authority: &Pubkey, domain_name: &str) -> Instruction {
+    build_instruction(
+        accounts::RemoveReservedNameAccountConstraints {
+            authority: *authority,
+            admin_roles: find_admin_roles_address().0,
+            reserved_name: find_reserved_name_address(domain_name).0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::RemoveReservedName {},
+    )
+}
+
+/// Assign a reserved name to its rightful owner
+pub fn admin_register_reserved(
+    authority: &Pubkey,
+    domain_name: &str,
+    period: RegistrationPeriod,
+    addresses: Vec<ChainAddress>,
+    owner: &Pubkey,
+) -> Instruction {
+    build_instruction(
+        accounts::AdminRegisterReservedAccountConstraints {
+            authority: *authority,
+            admin_roles: find_admin_roles_address().0,
+            reserved_name: find_reserved_name_address(domain_name).0,
+            domain_record: find_domain_record_address(domain_name).0,
+            program_state: find_program_state_address().0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::AdminRegisterReserved {
+            domain_name: domain_name.to_string(),
+            period,
+            addresses,
+            owner: *owner,
+        },
+    )
+}
+
+/// Set the sunrise allowlist root and the general availability timestamp
+pub fn configure_sunrise(
+    authority: &Pubkey,
+    sunrise_merkle_root: [u8; 32],
+    general_availability_timestamp: i64,
+) -> Instruction {
+    build_instruction(
+        accounts::ConfigureSunriseAccountConstraints {
+            authority: *authority,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::ConfigureSunrise {
+            sunrise_merkle_root,
+            general_availability_timestamp,
+        },
+    )
+}
+
+/// Publish the Merkle root of pre-allocated domains
+pub fn update_airdrop_root(authority: &Pubkey, airdrop_merkle_root: [u8; 32]) -> Instruction {
+    build_instruction(
+        accounts::UpdateAirdropRootAccountConstraints {
+            authority: *authority,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::UpdateAirdropRoot {
+            airdrop_merkle_root,
+        },
+    )
+}
+
+/// Suspend or reinstate a domain, queued as change `queue_change_id` when given
+pub fn admin_suspend_domain(
+    authority: &Pubkey,
+    domain_name: &str,
+    suspended: bool,
+    reason_code: u16,
+    queue_change_id: Option<u64>,
+) -> Instruction {
+    build_instruction(
+        accounts::AdminSuspendDomainAccountConstraints {
+            authority: *authority,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            domain_record: find_domain_record_address(domain_name).0,
+            queued_change: queue_change_id.map(|change_id| find_queued_change_address(change_id).0),
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::AdminSuspendDomain {
+            domain_name: domain_name.to_string(),
+            suspended,
+            reason_code,
+        },
+    )
+}
+
+/// Reassign a domain to a new owner, queued as change `queue_change_id` when given
+pub fn admin_force_transfer(
+    authority: &Pubkey,
+    domain_name: &str,
+    new_owner: &Pubkey,
+    reason_code: u16,
+    queue_change_id: Option<u64>,
+) -> Instruction {
+    build_instruction(
+        accounts::AdminForceTransferAccountConstraints {
+            authority: *authority,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            domain_record: find_domain_record_address(domain_name).0,
+            queued_change: queue_change_id.map(|change_id| find_queued_change_address(change_id).0),
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::AdminForceTransfer {
+            domain_name: domain_name.to_string(),
+            new_owner: *new_owner,
+            reason_code,
+        },
+    )
+}
+
+/// Set the share of the unused paid amount refunded on release
+pub fn update_release_refund_rate(authority: &Pubkey, refund_rate_bps: u16) -> Instruction {
+    build_instruction(
+        accounts::UpdateReleaseRefundRateAccountConstraints {
+            authority: *authority,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::UpdateReleaseRefundRate { refund_rate_bps },
+    )
+}
+
+/// Queue a new protocol share of paid subdomain registrations
+pub fn update_subdomain_protocol_fee(
+    authority: &Pubkey,
+    change_id: u64,
+    fee_bps: u16,
+) -> Instruction {
+    build_instruction(
+        queued_setter_accounts(authority, change_id),
+        instruction::UpdateSubdomainProtocolFee { fee_bps },
+    )
+}
+
+/// Enroll a referral partner
+pub fn create_referrer(
+    authority: &Pubkey,
+    referrer: &Pubkey,
+    share_bps_override: Option<u16>,
+) -> Instruction {
+    build_instruction(
+        accounts::CreateReferrerAccountConstraints {
+            authority: *authority,
+            admin_roles: find_admin_roles_address().0,
+            referrer: *referrer,
+            referrer_config: find_referrer_config_address(referrer).0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::CreateReferrer { share_bps_override },
+    )
+}
+
+/// Update the share override or active flag of a referral partner
+pub fn update_referrer(
+    authority: &Pubkey,
+    referrer: &Pubkey,
+    share_bps_override: Option<u16>,
+    active: bool,
+) -> Instruction {
+    build_instruction(
+        accounts::UpdateReferrerAccountConstraints {
+            authority: *authority,
+            admin_roles: find_admin_roles_address().0,
+            referrer_config: find_referrer_config_address(referrer).0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::UpdateReferrer {
+            share_bps_override,
+            active,
+        },
+    )
+}
+
+/// Set the referral share used by referrers without an override
+pub fn update_default_referral_share(authority: &Pubkey, share_bps: u16) -> Instruction {
+    build_instruction(
+        accounts::UpdateDefaultReferralShareAccountConstraints {
+            authority: *authority,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::UpdateDefaultReferralShare { share_bps },
+    )
+}
+
+/// Withdraw collected fees, `fee_destinations` are the wallets of `ProgramState.fee_destinations` in order
+pub fn withdraw_fees(
+    signer: &Pubkey,
+    treasurer: &Pubkey,
+    fee_destinations: &[Pubkey],
+) -> Instruction {
+    let mut instruction = build_instruction(
+        accounts::WithdrawFeesAccountConstraints {
+            signer: *signer,
+            treasurer: *treasurer,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::WithdrawFees {},
+    );
+    instruction.accounts.extend(
+        fee_destinations
+            .iter()
+            .map(|wallet| AccountMeta::new(*wallet, false)),
+    );
+    instruction
+}
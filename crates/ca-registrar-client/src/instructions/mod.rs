@@ -0,0 +1,36 @@
+//! Instruction builders for every program instruction
+//!
+//! Builders derive the program accounts from their seeds and take the remaining accounts as arguments. Queued admin
+//! setters take the `change_id` of the change they create, which must be the current
+//! `ProgramState.queued_changes_count`.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+pub use admin::*;
+mod admin;
+
+pub use register::*;
+mod register;
+
+pub use update::*;
+mod update;
+
+pub use transfer::*;
+mod transfer;
+
+pub use release::*;
+mod release;
+
+pub use subdomain::*;
+mod subdomain;
+
+/// Build a program instruction from its Anchor accounts and arguments
+fn build_instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ca_registrar::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
@@ -0,0 +1,210 @@
+use crate::instructions::build_instruction;
+use crate::pda::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_program, sysvar};
+use ca_registrar::constants::VOUCHER_MESSAGE_PREFIX;
+use ca_registrar::state::*;
+use ca_registrar::{accounts, instruction};
+
+/// Register a new domain, optionally with a promo code and a referrer
+#[allow(clippy::too_many_arguments)]
+pub fn register_domain(
+    buyer: &Pubkey,
+    pyth_price_update: &Pubkey,
+    domain_name: &str,
+    period: RegistrationPeriod,
+    addresses: Vec<ChainAddress>,
+    owner: &Pubkey,
+    promo_code: Option<&str>,
+    referrer: Option<&Pubkey>,
+) -> Instruction {
+    build_instruction(
+        accounts::RegisterDomainAccountConstraints {
+            buyer: *buyer,
+            domain_record: find_domain_record_address(domain_name).0,
+            reserved_name: find_reserved_name_address(domain_name).0,
+            program_state: find_program_state_address().0,
+            price_config: find_price_config_address().0,
+            pyth_price_update: *pyth_price_update,
+            promo_code_account: promo_code.map(|code| find_promo_code_address(code).0),
+            referrer_config: referrer.map(|referrer| find_referrer_config_address(referrer).0),
+            referrer: referrer.copied(),
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::RegisterDomain {
+            domain_name: domain_name.to_string(),
+            period,
+            addresses,
+            owner: *owner,
+            promo_code: promo_code.map(str::to_string),
+        },
+    )
+}
+
+/// Message the voucher signer signs for a voucher, verified by a preceding Ed25519 program instruction
+pub fn voucher_message(voucher: &Voucher) -> Vec<u8> {
+    [
+        VOUCHER_MESSAGE_PREFIX,
+        ca_registrar::ID.as_ref(),
+        &voucher.try_to_vec().expect("vouchers always serialize"),
+    ]
+    .concat()
+}
+
+/// Register a new domain with a voucher, the transaction must include the Ed25519 verification of
+/// `voucher_message(&voucher)` right before this instruction
+pub fn register_domain_with_voucher(
+    buyer: &Pubkey,
+    pyth_price_update: &Pubkey,
+    domain_name: &str,
+    period: RegistrationPeriod,
+    addresses: Vec<ChainAddress>,
+    owner: &Pubkey,
+    voucher: Voucher,
+) -> Instruction {
+    build_instruction(
+        accounts::RegisterDomainWithVoucherAccountConstraints {
+            buyer: *buyer,
+            domain_record: find_domain_record_address(domain_name).0,
+            reserved_name: find_reserved_name_address(domain_name).0,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            price_config: find_price_config_address().0,
+            pyth_price_update: *pyth_price_update,
+            voucher_nonce: find_voucher_nonce_address(voucher.nonce).0,
+            instructions_sysvar: sysvar::instructions::ID,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::RegisterDomainWithVoucher {
+            domain_name: domain_name.to_string(),
+            period,
+            addresses,
+            owner: *owner,
+            voucher,
+        },
+    )
+}
+
+/// Claim an allowlisted domain during sunrise
+pub fn claim_sunrise_domain(
+    claimant: &Pubkey,
+    pyth_price_update: &Pubkey,
+    domain_name: &str,
+    period: RegistrationPeriod,
+    addresses: Vec<ChainAddress>,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build_instruction(
+        accounts::ClaimSunriseDomainAccountConstraints {
+            claimant: *claimant,
+            domain_record: find_domain_record_address(domain_name).0,
+            reserved_name: find_reserved_name_address(domain_name).0,
+            program_state: find_program_state_address().0,
+            price_config: find_price_config_address().0,
+            pyth_price_update: *pyth_price_update,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::ClaimSunriseDomain {
+            domain_name: domain_name.to_string(),
+            period,
+            addresses,
+            proof,
+        },
+    )
+}
+
+/// Create a pre-allocated domain from the airdrop Merkle tree
+pub fn claim_domain(
+    payer: &Pubkey,
+    domain_name: &str,
+    owner: &Pubkey,
+    expiry_timestamp: i64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build_instruction(
+        accounts::ClaimDomainAccountConstraints {
+            payer: *payer,
+            domain_record: find_domain_record_address(domain_name).0,
+            reserved_name: find_reserved_name_address(domain_name).0,
+            claimed_leaf: find_claimed_leaf_address(domain_name, owner, expiry_timestamp).0,
+            program_state: find_program_state_address().0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::ClaimDomain {
+            domain_name: domain_name.to_string(),
+            owner: *owner,
+            expiry_timestamp,
+            proof,
+        },
+    )
+}
+
+/// Renew a domain, optionally with a referrer
+pub fn renew_domain(
+    payer: &Pubkey,
+    pyth_price_update: &Pubkey,
+    domain_name: &str,
+    period: RegistrationPeriod,
+    referrer: Option<&Pubkey>,
+) -> Instruction {
+    build_instruction(
+        accounts::RenewDomainAccountConstraints {
+            payer: *payer,
+            domain_record: find_domain_record_address(domain_name).0,
+            program_state: find_program_state_address().0,
+            price_config: find_price_config_address().0,
+            pyth_price_update: *pyth_price_update,
+            referrer_config: referrer.map(|referrer| find_referrer_config_address(referrer).0),
+            referrer: referrer.copied(),
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::RenewDomain {
+            _domain_name: domain_name.to_string(),
+            period,
+        },
+    )
+}
+
+/// Buy a domain that expired beyond its grace period, optionally with a referrer
+pub fn buy_domain(
+    buyer: &Pubkey,
+    pyth_price_update: &Pubkey,
+    domain_name: &str,
+    period: RegistrationPeriod,
+    addresses: Vec<ChainAddress>,
+    owner: &Pubkey,
+    referrer: Option<&Pubkey>,
+) -> Instruction {
+    build_instruction(
+        accounts::BuyDomainAccountConstraints {
+            buyer: *buyer,
+            domain_record: find_domain_record_address(domain_name).0,
+            reserved_name: find_reserved_name_address(domain_name).0,
+            program_state: find_program_state_address().0,
+            price_config: find_price_config_address().0,
+            pyth_price_update: *pyth_price_update,
+            referrer_config: referrer.map(|referrer| find_referrer_config_address(referrer).0),
+            referrer: referrer.copied(),
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::BuyDomain {
+            _domain_name: domain_name.to_string(),
+            period,
+            addresses,
+            owner: *owner,
+        },
+    )
+}
@@ -0,0 +1,21 @@
+use crate::instructions::build_instruction;
+use crate::pda::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use ca_registrar::{accounts, instruction};
+
+/// Release a domain, refunding the unused whole years of what was paid at the configured refund rate
+pub fn release_domain(owner: &Pubkey, domain_name: &str) -> Instruction {
+    build_instruction(
+        accounts::ReleaseDomainAccountConstraints {
+            owner: *owner,
+            domain_record: find_domain_record_address(domain_name).0,
+            program_state: find_program_state_address().0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::ReleaseDomain {
+            _domain_name: domain_name.to_string(),
+        },
+    )
+}
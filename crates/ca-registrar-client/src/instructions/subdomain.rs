@@ -0,0 +1,256 @@
+use crate::instructions::build_instruction;
+use crate::pda::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use ca_registrar::state::*;
+use ca_registrar::{accounts, instruction};
+
+/// Token accounts used to pay for a subdomain sold for an SPL token
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SubdomainTokenPayment {
+    /// Mint set in the subdomain policy
+    pub mint: Pubkey,
+
+    /// Token program owning the mint
+    pub token_program: Pubkey,
+
+    /// Token account of the buyer, paying the price
+    pub buyer_token_account: Pubkey,
+
+    /// Token account of the parent owner, receiving the price minus the protocol fee
+    pub parent_owner_token_account: Pubkey,
+
+    /// Token account of the treasurer, receiving the protocol fee
+    pub treasurer_token_account: Pubkey,
+}
+
+/// Issue a subdomain under a domain
+pub fn create_subdomain(
+    owner: &Pubkey,
+    domain_name: &str,
+    label: &str,
+    subdomain_owner: &Pubkey,
+    addresses: Vec<ChainAddress>,
+) -> Instruction {
+    let parent_domain_record = find_domain_record_address(domain_name).0;
+    build_instruction(
+        accounts::CreateSubdomainAccountConstraints {
+            owner: *owner,
+            parent_domain_record,
+            subdomain_record: find_subdomain_record_address(&parent_domain_record, label).0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::CreateSubdomain {
+            _domain_name: domain_name.to_string(),
+            label: label.to_string(),
+            subdomain_owner: *subdomain_owner,
+            addresses,
+        },
+    )
+}
+
+/// Replace the addresses of a subdomain
+pub fn update_subdomain_addresses(
+    owner: &Pubkey,
+    domain_name: &str,
+    label: &str,
+    addresses: Vec<ChainAddress>,
+) -> Instruction {
+    let parent_domain_record = find_domain_record_address(domain_name).0;
+    build_instruction(
+        accounts::UpdateSubdomainAddressesAccountConstraints {
+            owner: *owner,
+            parent_domain_record,
+            subdomain_record: find_subdomain_record_address(&parent_domain_record, label).0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::UpdateSubdomainAddresses { addresses },
+    )
+}
+
+/// Transfer a subdomain to a new owner
+pub fn transfer_subdomain(
+    owner: &Pubkey,
+    domain_name: &str,
+    label: &str,
+    new_owner: &Pubkey,
+) -> Instruction {
+    let parent_domain_record = find_domain_record_address(domain_name).0;
+    build_instruction(
+        accounts::TransferSubdomainAccountConstraints {
+            owner: *owner,
+            parent_domain_record,
+            subdomain_record: find_subdomain_record_address(&parent_domain_record, label).0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::TransferSubdomain {
+            new_owner: *new_owner,
+        },
+    )
+}
+
+/// Delete a subdomain, signed by the parent domain owner
+pub fn delete_subdomain(owner: &Pubkey, domain_name: &str, label: &str) -> Instruction {
+    let parent_domain_record = find_domain_record_address(domain_name).0;
+    build_instruction(
+        accounts::DeleteSubdomainAccountConstraints {
+            owner: *owner,
+            parent_domain_record,
+            subdomain_record: find_subdomain_record_address(&parent_domain_record, label).0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::DeleteSubdomain {},
+    )
+}
+
+/// Set the subdomain issuance policy of a domain
+pub fn set_subdomain_policy(
+    owner: &Pubkey,
+    domain_name: &str,
+    mode: SubdomainIssuanceMode,
+    payment_mint: Option<Pubkey>,
+    price_per_year: u64,
+) -> Instruction {
+    let parent_domain_record = find_domain_record_address(domain_name).0;
+    build_instruction(
+        accounts::SetSubdomainPolicyAccountConstraints {
+            owner: *owner,
+            parent_domain_record,
+            subdomain_policy: find_subdomain_policy_address(&parent_domain_record).0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::SetSubdomainPolicy {
+            _domain_name: domain_name.to_string(),
+            mode,
+            payment_mint,
+            price_per_year,
+        },
+    )
+}
+
+/// Buy a subdomain according to the parent's subdomain policy, `token_payment` is required for token prices
+#[allow(clippy::too_many_arguments)]
+pub fn register_subdomain(
+    buyer: &Pubkey,
+    domain_name: &str,
+    parent_owner: &Pubkey,
+    label: &str,
+    period: RegistrationPeriod,
+    subdomain_owner: &Pubkey,
+    addresses: Vec<ChainAddress>,
+    proof: Vec<[u8; 32]>,
+    token_payment: Option<&SubdomainTokenPayment>,
+) -> Instruction {
+    let parent_domain_record = find_domain_record_address(domain_name).0;
+    build_instruction(
+        accounts::RegisterSubdomainAccountConstraints {
+            buyer: *buyer,
+            parent_domain_record,
+            parent_owner: *parent_owner,
+            subdomain_policy: find_subdomain_policy_address(&parent_domain_record).0,
+            subdomain_record: find_subdomain_record_address(&parent_domain_record, label).0,
+            program_state: find_program_state_address().0,
+            admin_roles: find_admin_roles_address().0,
+            payment_mint: token_payment.map(|payment| payment.mint),
+            buyer_token_account: token_payment.map(|payment| payment.buyer_token_account),
+            parent_owner_token_account: token_payment
+                .map(|payment| payment.parent_owner_token_account),
+            treasurer_token_account: token_payment.map(|payment| payment.treasurer_token_account),
+            token_program: token_payment.map(|payment| payment.token_program),
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::RegisterSubdomain {
+            _domain_name: domain_name.to_string(),
+            label: label.to_string(),
+            period,
+            subdomain_owner: *subdomain_owner,
+            addresses,
+            proof,
+        },
+    )
+}
+
+/// Burn permission fuses on a subdomain, signed by the subdomain owner or, for PARENT_CANNOT_CONTROL, the parent owner
+/// once CANNOT_RELEASE is burned on the parent
+pub fn burn_subdomain_fuses(
+    authority: &Pubkey,
+    domain_name: &str,
+    label: &str,
+    fuses: u32,
+) -> Instruction {
+    let parent_domain_record = find_domain_record_address(domain_name).0;
+    build_instruction(
+        accounts::BurnSubdomainFusesAccountConstraints {
+            authority: *authority,
+            parent_domain_record,
+            subdomain_record: find_subdomain_record_address(&parent_domain_record, label).0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::BurnSubdomainFuses { fuses },
+    )
+}
+
+/// Set the wildcard record of a domain
+pub fn set_wildcard_record(
+    owner: &Pubkey,
+    domain_name: &str,
+    addresses: Vec<ChainAddress>,
+) -> Instruction {
+    let parent_domain_record = find_domain_record_address(domain_name).0;
+    build_instruction(
+        accounts::SetWildcardRecordAccountConstraints {
+            owner: *owner,
+            parent_domain_record,
+            wildcard_record: find_wildcard_record_address(&parent_domain_record).0,
+            system_program: system_program::ID,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::SetWildcardRecord {
+            _domain_name: domain_name.to_string(),
+            addresses,
+        },
+    )
+}
+
+/// Delete the wildcard record of a domain
+pub fn delete_wildcard_record(owner: &Pubkey, domain_name: &str) -> Instruction {
+    let parent_domain_record = find_domain_record_address(domain_name).0;
+    build_instruction(
+        accounts::DeleteWildcardRecordAccountConstraints {
+            owner: *owner,
+            parent_domain_record,
+            wildcard_record: find_wildcard_record_address(&parent_domain_record).0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::DeleteWildcardRecord {},
+    )
+}
+
+/// Resolve the addresses of a subdomain, returned as Borsh-encoded return data when simulated
+pub fn resolve_subdomain(domain_name: &str, label: &str) -> Instruction {
+    let parent_domain_record = find_domain_record_address(domain_name).0;
+    build_instruction(
+        accounts::ResolveSubdomainAccountConstraints {
+            parent_domain_record,
+            subdomain_record: find_subdomain_record_address(&parent_domain_record, label).0,
+            wildcard_record: find_wildcard_record_address(&parent_domain_record).0,
+        },
+        instruction::ResolveSubdomain {
+            _domain_name: domain_name.to_string(),
+            _label: label.to_string(),
+        },
+    )
+}
@@ -0,0 +1,21 @@
+use crate::instructions::build_instruction;
+use crate::pda::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use ca_registrar::{accounts, instruction};
+
+/// Transfer a domain to a new owner
+pub fn transfer_domain(owner: &Pubkey, domain_name: &str, new_owner: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::TransferDomainAccountConstraints {
+            owner: *owner,
+            domain_record: find_domain_record_address(domain_name).0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::TransferDomain {
+            _domain_name: domain_name.to_string(),
+            new_owner: *new_owner,
+        },
+    )
+}
@@ -0,0 +1,42 @@
+use crate::instructions::build_instruction;
+use crate::pda::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use ca_registrar::state::*;
+use ca_registrar::{accounts, instruction};
+
+/// Replace the addresses of a domain
+pub fn update_addresses(
+    owner: &Pubkey,
+    domain_name: &str,
+    addresses: Vec<ChainAddress>,
+) -> Instruction {
+    build_instruction(
+        accounts::UpdateAddressesAccountConstraints {
+            owner: *owner,
+            domain_record: find_domain_record_address(domain_name).0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::UpdateAddresses {
+            _domain_name: domain_name.to_string(),
+            addresses,
+        },
+    )
+}
+
+/// Burn permission fuses (`FUSE_*` constants) on a domain
+pub fn burn_fuses(owner: &Pubkey, domain_name: &str, fuses: u32) -> Instruction {
+    build_instruction(
+        accounts::BurnFusesAccountConstraints {
+            owner: *owner,
+            domain_record: find_domain_record_address(domain_name).0,
+            event_authority: find_event_authority_address().0,
+            program: ca_registrar::ID,
+        },
+        instruction::BurnFuses {
+            _domain_name: domain_name.to_string(),
+            fuses,
+        },
+    )
+}
@@ -0,0 +1,30 @@
+//! Rust client for the CA Domain Registrar program
+//!
+//! Re-exports the program's account and argument types, and provides PDA derivation helpers,
+//! instruction builders for every program instruction and fee quotes computed with the same
+//! pricing code as the program.
+
+pub mod fees;
+pub mod instructions;
+pub mod pda;
+
+pub use ca_registrar::constants;
+pub use ca_registrar::error::CaRegistrarError;
+pub use ca_registrar::events;
+pub use ca_registrar::state::*;
+pub use ca_registrar::ID;
+
+use anchor_lang::prelude::*;
+
+/// Pyth sponsored SOL/USD price update account, shard 0
+pub const DEFAULT_SOL_USD_PRICE_FEED: Pubkey =
+    pubkey!("7UVimffxr9ow1uXYxsr4LHAcV58mLzhmwaeKvJ1pjLiE");
+
+/// Chain id of Solana addresses
+pub const SOLANA_CHAIN_ID: u8 = 0;
+
+/// Chain ids of addresses holding DNS records rather than chain addresses, served by the DNS bridge
+pub const DNS_A_CHAIN_ID: u8 = 200;
+pub const DNS_AAAA_CHAIN_ID: u8 = 201;
+pub const DNS_CNAME_CHAIN_ID: u8 = 202;
+pub const DNS_TXT_CHAIN_ID: u8 = 203;
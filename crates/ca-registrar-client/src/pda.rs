@@ -0,0 +1,212 @@
+//! Program derived addresses of the registrar accounts
+//!
+//! Each helper returns the address and its bump seed, like `Pubkey::find_program_address`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::hash::hash;
+use ca_registrar::constants::*;
+use ca_registrar::instructions::utils::hash_merkle_leaf;
+use ca_registrar::ID;
+
+/// Global program state singleton
+pub fn find_program_state_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_STATE_SEED], &ID)
+}
+
+/// Admin role assignments singleton
+pub fn find_admin_roles_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ADMIN_ROLES_SEED], &ID)
+}
+
+/// Multi-year discount schedule singleton
+pub fn find_price_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRICE_CONFIG_SEED], &ID)
+}
+
+/// Program data account of the program, holding its upgrade authority
+pub fn find_program_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+/// Event authority signing the self-CPIs that carry program events, seeded like Anchor's `#[event_cpi]`
+pub fn find_event_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"__event_authority"], &ID)
+}
+
+/// Domain record of a name, without the .ca suffix
+pub fn find_domain_record_address(domain_name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DOMAIN_RECORD_SEED, domain_name.as_bytes()], &ID)
+}
+
+/// Queued admin parameter change, `change_id` is `ProgramState.queued_changes_count` when queued
+pub fn find_queued_change_address(change_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[QUEUED_CHANGE_SEED, &change_id.to_le_bytes()], &ID)
+}
+
+/// Referral configuration of a referrer wallet
+pub fn find_referrer_config_address(referrer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REFERRER_CONFIG_SEED, referrer.as_ref()], &ID)
+}
+
+/// Promo code account of a plain-text code
+pub fn find_promo_code_address(code: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROMO_CODE_SEED, &hash(code.as_bytes()).to_bytes()], &ID)
+}
+
+/// Marker of a redeemed voucher nonce
+pub fn find_voucher_nonce_address(nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOUCHER_NONCE_SEED, &nonce.to_le_bytes()], &ID)
+}
+
+/// Reserved name entry of a name, without the .ca suffix
+pub fn find_reserved_name_address(domain_name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[RESERVED_NAME_SEED, &hash(domain_name.as_bytes()).to_bytes()],
+        &ID,
+    )
+}
+
+/// Marker of a claimed airdrop allocation
+pub fn find_claimed_leaf_address(
+    domain_name: &str,
+    owner: &Pubkey,
+    expiry_timestamp: i64,
+) -> (Pubkey, u8) {
+    let leaf = hash_merkle_leaf(&[
+        domain_name.as_bytes(),
+        owner.as_ref(),
+        &expiry_timestamp.to_le_bytes(),
+    ]);
+    Pubkey::find_program_address(&[CLAIMED_LEAF_SEED, &leaf], &ID)
+}
+
+/// Subdomain record of a label under a parent domain record
+pub fn find_subdomain_record_address(parent_domain_record: &Pubkey, label: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SUBDOMAIN_RECORD_SEED,
+            parent_domain_record.as_ref(),
+            &hash(label.as_bytes()).to_bytes(),
+        ],
+        &ID,
+    )
+}
+
+/// Subdomain issuance policy of a parent domain record
+pub fn find_subdomain_policy_address(parent_domain_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SUBDOMAIN_POLICY_SEED, parent_domain_record.as_ref()], &ID)
+}
+
+/// Wildcard record of a parent domain record
+pub fn find_wildcard_record_address(parent_domain_record: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WILDCARD_RECORD_SEED, parent_domain_record.as_ref()], &ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hashv;
+
+    // Seeds spelled out as in the program's account constraints, so a renamed constant is caught
+    fn program_address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(seeds, &ID)
+    }
+
+    #[test]
+    fn singletons_match_the_program_seeds() {
+        assert_eq!(find_program_state_address(), program_address(&[b"state"]));
+        assert_eq!(
+            find_admin_roles_address(),
+            program_address(&[b"admin_roles"])
+        );
+        assert_eq!(
+            find_price_config_address(),
+            program_address(&[b"price_config"])
+        );
+        assert_eq!(
+            find_event_authority_address(),
+            program_address(&[b"__event_authority"])
+        );
+        assert_eq!(
+            find_program_data_address(),
+            Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+        );
+    }
+
+    #[test]
+    fn domain_accounts_match_the_program_seeds() {
+        assert_eq!(
+            find_domain_record_address("alice"),
+            program_address(&[b"domain", b"alice"])
+        );
+        assert_eq!(
+            find_reserved_name_address("alice"),
+            program_address(&[b"reserved", &hash(b"alice").to_bytes()])
+        );
+        assert_ne!(
+            find_domain_record_address("alice"),
+            find_domain_record_address("bob")
+        );
+    }
+
+    #[test]
+    fn admin_accounts_match_the_program_seeds() {
+        let referrer = Pubkey::new_unique();
+
+        assert_eq!(
+            find_queued_change_address(7),
+            program_address(&[b"queued_change", &7u64.to_le_bytes()])
+        );
+        assert_eq!(
+            find_referrer_config_address(&referrer),
+            program_address(&[b"referrer", referrer.as_ref()])
+        );
+        assert_eq!(
+            find_promo_code_address("LAUNCH"),
+            program_address(&[b"promo", &hash(b"LAUNCH").to_bytes()])
+        );
+        assert_eq!(
+            find_voucher_nonce_address(42),
+            program_address(&[b"voucher_nonce", &42u64.to_le_bytes()])
+        );
+    }
+
+    #[test]
+    fn claimed_leaf_matches_the_airdrop_leaf() {
+        let owner = Pubkey::new_unique();
+        let expiry_timestamp = 1_700_000_000i64;
+
+        // Leaves are prefixed with 0x00 to tell them apart from internal nodes
+        let leaf = hashv(&[
+            &[0],
+            b"alice",
+            owner.as_ref(),
+            &expiry_timestamp.to_le_bytes(),
+        ])
+        .to_bytes();
+
+        assert_eq!(
+            find_claimed_leaf_address("alice", &owner, expiry_timestamp),
+            program_address(&[b"claimed_leaf", &leaf])
+        );
+    }
+
+    #[test]
+    fn subdomain_accounts_match_the_program_seeds() {
+        let parent = find_domain_record_address("alice").0;
+
+        assert_eq!(
+            find_subdomain_record_address(&parent, "pay"),
+            program_address(&[b"subdomain", parent.as_ref(), &hash(b"pay").to_bytes()])
+        );
+        assert_eq!(
+            find_subdomain_policy_address(&parent),
+            program_address(&[b"subdomain_policy", parent.as_ref()])
+        );
+        assert_eq!(
+            find_wildcard_record_address(&parent),
+            program_address(&[b"wildcard", parent.as_ref()])
+        );
+    }
+}
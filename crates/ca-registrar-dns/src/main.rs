@@ -0,0 +1,335 @@
+//! DNS server answering queries for .ca domains
+//!
+//! Serves the active domains as a zone built from an RPC endpoint, or from a snapshot file
+//! captured with `ca-registrar snapshot`, optionally signed with a local DNSSEC key.
+
+mod server;
+mod zone;
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use ca_registrar_resolver::Snapshot;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use hickory_server::proto::rr::dnssec::rdata::DNSSECRData;
+use hickory_server::proto::rr::dnssec::Algorithm;
+use hickory_server::proto::rr::{Name, RData, Record};
+use hickory_server::ServerFuture;
+use solana_rpc_client::rpc_client::RpcClient;
+
+use crate::server::ZoneHandler;
+use crate::zone::{Source, Zone, ZoneBuilder, ZoneConfig, ZoneKey};
+
+/// Time a TCP connection may stay idle
+const TCP_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Parser)]
+#[command(
+    name = "ca-registrar-dns",
+    version,
+    about = "DNS server answering queries for .ca domains"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Answer queries over UDP and TCP
+    Serve {
+        /// Address to listen on
+        #[arg(
+            long,
+            env = "CA_REGISTRAR_DNS_LISTEN",
+            default_value = "127.0.0.1:5353"
+        )]
+        listen: SocketAddr,
+
+        /// Time between zone rebuilds, in seconds, the zone is also rebuilt when a domain expires
+        #[arg(long, default_value_t = 60)]
+        refresh: u64,
+
+        #[command(flatten)]
+        source: SourceArgs,
+
+        #[command(flatten)]
+        zone: ZoneArgs,
+    },
+    /// Print the zone in zone file format
+    Zone {
+        #[command(flatten)]
+        source: SourceArgs,
+
+        #[command(flatten)]
+        zone: ZoneArgs,
+    },
+    /// Generate a DNSSEC key file and print the DNSKEY and DS records to publish
+    GenerateKey {
+        /// Key file to create
+        path: PathBuf,
+
+        /// Zone the key signs
+        #[arg(long, default_value = "ca.")]
+        zone: String,
+
+        /// Signing algorithm
+        #[arg(long, value_enum, default_value_t = KeyAlgorithm::Ed25519)]
+        dnssec_algorithm: KeyAlgorithm,
+    },
+}
+
+#[derive(Args)]
+struct SourceArgs {
+    /// RPC endpoint of the cluster
+    #[arg(
+        long,
+        short = 'u',
+        env = "CA_REGISTRAR_RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+
+    /// Snapshot file to serve instead of the RPC endpoint
+    #[arg(long, conflicts_with = "url")]
+    snapshot: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ZoneArgs {
+    /// Zone the domains are served under
+    #[arg(long, default_value = "ca.")]
+    zone: String,
+
+    /// Maximum TTL of the records, in seconds, capped at the time left before a domain expires
+    #[arg(long, default_value_t = 300)]
+    ttl: u32,
+
+    /// Name server of the zone, repeat for several [default: ns.<zone>]
+    #[arg(long = "nameserver")]
+    nameservers: Vec<String>,
+
+    /// Mailbox of the zone administrator, in SOA form [default: hostmaster.<zone>]
+    #[arg(long)]
+    hostmaster: Option<String>,
+
+    /// PKCS#8 key file signing the zone, DNSSEC is disabled without it
+    #[arg(long)]
+    dnssec_key: Option<PathBuf>,
+
+    /// Algorithm of the DNSSEC key
+    #[arg(long, value_enum, default_value_t = KeyAlgorithm::Ed25519)]
+    dnssec_algorithm: KeyAlgorithm,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum KeyAlgorithm {
+    Ed25519,
+    EcdsaP256,
+    EcdsaP384,
+}
+
+impl From<KeyAlgorithm> for Algorithm {
+    fn from(algorithm: KeyAlgorithm) -> Self {
+        match algorithm {
+            KeyAlgorithm::Ed25519 => Algorithm::ED25519,
+            KeyAlgorithm::EcdsaP256 => Algorithm::ECDSAP256SHA256,
+            KeyAlgorithm::EcdsaP384 => Algorithm::ECDSAP384SHA384,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Serve {
+            listen,
+            refresh,
+            source,
+            zone,
+        } => serve(listen, Duration::from_secs(refresh), source, zone).await,
+        Command::Zone { source, zone } => print_zone(source, zone).await,
+        Command::GenerateKey {
+            path,
+            zone,
+            dnssec_algorithm,
+        } => generate_key(&path, &zone, dnssec_algorithm.into()),
+    }
+}
+
+async fn serve(
+    listen: SocketAddr,
+    refresh: Duration,
+    source_args: SourceArgs,
+    zone_args: ZoneArgs,
+) -> Result<()> {
+    let builder = Arc::new(zone_builder(&source_args, zone_args)?);
+    let zone = build(&builder).await?;
+    println!(
+        "Serving {} domains under {}",
+        zone.domain_count,
+        builder.config().origin
+    );
+    let mut next_rebuild = rebuild_delay(&zone, refresh);
+    let handler = ZoneHandler::new(zone);
+
+    let mut server = ServerFuture::new(handler.clone());
+    let udp_socket = tokio::net::UdpSocket::bind(listen)
+        .await
+        .with_context(|| format!("failed to listen on udp {listen}"))?;
+    let tcp_listener = tokio::net::TcpListener::bind(listen)
+        .await
+        .with_context(|| format!("failed to listen on tcp {listen}"))?;
+    server.register_socket(udp_socket);
+    server.register_listener(tcp_listener, TCP_TIMEOUT);
+    match &source_args.snapshot {
+        Some(path) => println!("Serving {} on {listen} (udp and tcp)", path.display()),
+        None => println!("Serving {} on {listen} (udp and tcp)", source_args.url),
+    }
+
+    let rebuilds = async {
+        loop {
+            tokio::time::sleep(next_rebuild).await;
+            match build(&builder).await {
+                Ok(zone) => {
+                    next_rebuild = rebuild_delay(&zone, refresh);
+                    handler.replace(zone);
+                }
+                Err(error) => {
+                    // Keep serving the previous zone until the source answers again
+                    eprintln!("Error: failed to rebuild the zone: {error:#}");
+                    next_rebuild = refresh;
+                }
+            }
+        }
+    };
+
+    tokio::select! {
+        result = server.block_until_done() => result.context("server failed"),
+        _ = rebuilds => Ok(()),
+        _ = tokio::signal::ctrl_c() => Ok(()),
+    }
+}
+
+async fn print_zone(source_args: SourceArgs, zone_args: ZoneArgs) -> Result<()> {
+    let builder = Arc::new(zone_builder(&source_args, zone_args)?);
+    let zone = build(&builder).await?;
+
+    println!(
+        "; {} domains at cluster time {}",
+        zone.domain_count, zone.cluster_time
+    );
+    for record_set in zone.authority.records().await.values() {
+        for record in record_set
+            .records_without_rrsigs()
+            .chain(record_set.rrsigs())
+        {
+            println!("{record}");
+        }
+    }
+
+    Ok(())
+}
+
+fn generate_key(path: &Path, zone: &str, algorithm: Algorithm) -> Result<()> {
+    let origin = parse_name(zone)?;
+    let key = ZoneKey::generate(algorithm)?;
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(key.pkcs8()))
+        .with_context(|| format!("failed to write {}", path.display()))?;
+
+    let ds = key.ds(&origin)?;
+    println!("Wrote {}", path.display());
+    println!(
+        "{}",
+        Record::from_rdata(
+            origin.clone(),
+            3_600,
+            RData::DNSSEC(DNSSECRData::DNSKEY(key.dnskey()?))
+        )
+    );
+    println!(
+        "{}",
+        Record::from_rdata(origin, 3_600, RData::DNSSEC(DNSSECRData::DS(ds)))
+    );
+
+    Ok(())
+}
+
+fn zone_builder(source_args: &SourceArgs, zone_args: ZoneArgs) -> Result<ZoneBuilder> {
+    let source: Source = match &source_args.snapshot {
+        Some(path) => Box::new(Snapshot::load(path)?),
+        None => Box::new(RpcClient::new(source_args.url.clone())),
+    };
+
+    let origin = parse_name(&zone_args.zone)?;
+    if zone_args.ttl == 0 {
+        bail!("--ttl must be at least 1");
+    }
+    let nameservers = if zone_args.nameservers.is_empty() {
+        vec![Name::from_ascii("ns")?.append_domain(&origin)?]
+    } else {
+        zone_args
+            .nameservers
+            .iter()
+            .map(|nameserver| parse_name(nameserver))
+            .collect::<Result<_>>()?
+    };
+    let hostmaster = match &zone_args.hostmaster {
+        Some(hostmaster) => parse_name(hostmaster)?,
+        None => Name::from_ascii("hostmaster")?.append_domain(&origin)?,
+    };
+    let key = zone_args
+        .dnssec_key
+        .as_deref()
+        .map(|path| ZoneKey::load(path, zone_args.dnssec_algorithm.into()))
+        .transpose()?;
+
+    Ok(ZoneBuilder::new(
+        source,
+        ZoneConfig {
+            origin,
+            ttl: zone_args.ttl,
+            nameservers,
+            hostmaster,
+        },
+        key,
+    ))
+}
+
+/// Build the zone off the async runtime, the account source blocks
+async fn build(builder: &Arc<ZoneBuilder>) -> Result<Zone> {
+    let builder = builder.clone();
+    tokio::task::spawn_blocking(move || builder.build())
+        .await
+        .context("zone build panicked")?
+}
+
+/// Time until the zone must be rebuilt, at the latest when a served domain leaves the active state
+fn rebuild_delay(zone: &Zone, refresh: Duration) -> Duration {
+    zone.next_change
+        .map(|change| change.saturating_sub(zone.cluster_time).max(1) as u64)
+        .map_or(refresh, |seconds| refresh.min(Duration::from_secs(seconds)))
+}
+
+/// Parse an absolute domain name, the trailing dot being optional
+fn parse_name(name: &str) -> Result<Name> {
+    let mut parsed =
+        Name::from_ascii(name).with_context(|| format!("invalid domain name {name:?}"))?;
+    parsed.set_fqdn(true);
+    Ok(parsed)
+}
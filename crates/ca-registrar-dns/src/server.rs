@@ -0,0 +1,55 @@
+//! Request handler answering from the latest zone
+
+use std::sync::{Arc, RwLock};
+
+use hickory_server::authority::{Authority, Catalog};
+use hickory_server::server::{Request, RequestHandler, ResponseHandler, ResponseInfo};
+
+use crate::zone::Zone;
+
+/// Answers queries from the latest zone, cheap to clone and shared with the rebuild task
+#[derive(Clone)]
+pub struct ZoneHandler {
+    catalog: Arc<RwLock<Arc<Catalog>>>,
+}
+
+impl ZoneHandler {
+    pub fn new(zone: Zone) -> Self {
+        Self {
+            catalog: Arc::new(RwLock::new(Arc::new(catalog(zone)))),
+        }
+    }
+
+    /// Serve `zone` from now on, queries being answered keep the previous zone
+    pub fn replace(&self, zone: Zone) {
+        *self
+            .catalog
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(catalog(zone));
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler for ZoneHandler {
+    async fn handle_request<R: ResponseHandler>(
+        &self,
+        request: &Request,
+        response_handle: R,
+    ) -> ResponseInfo {
+        let catalog = self
+            .catalog
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        catalog.handle_request(request, response_handle).await
+    }
+}
+
+fn catalog(zone: Zone) -> Catalog {
+    let mut catalog = Catalog::new();
+    catalog.upsert(
+        zone.authority.origin().clone(),
+        Box::new(Arc::new(zone.authority)),
+    );
+    catalog
+}
@@ -0,0 +1,634 @@
+//! Zone built from the domain records, signed with the zone key when DNSSEC is enabled
+
+use std::fs;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::Path;
+use std::time::Duration;
+
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::Discriminator;
+use anyhow::{anyhow, Context, Result};
+use ca_registrar_client::pda::find_program_state_address;
+use ca_registrar_client::*;
+use ca_registrar_resolver::*;
+use hickory_server::authority::ZoneType;
+use hickory_server::proto::rr::dnssec::rdata::{DNSKEY, DS};
+use hickory_server::proto::rr::dnssec::{
+    Algorithm, DigestType, KeyFormat, KeyPair, Private, SigSigner,
+};
+use hickory_server::proto::rr::rdata::{A, AAAA, CNAME, NS, SOA, TXT};
+use hickory_server::proto::rr::{Name, RData, Record};
+use hickory_server::store::in_memory::InMemoryAuthority;
+
+/// Validity of the signatures, the zone is signed again on every rebuild
+const SIGNATURE_VALIDITY: Duration = Duration::from_secs(7 * 86_400);
+
+/// SOA timers, in seconds
+const SOA_REFRESH: i32 = 3_600;
+const SOA_RETRY: i32 = 600;
+const SOA_EXPIRE: i32 = 86_400;
+
+/// Source of the accounts served by the DNS bridge
+pub type Source = Box<dyn AccountSource + Send + Sync>;
+
+/// Names and timers of the served zone
+pub struct ZoneConfig {
+    /// Zone the domains are served under, e.g. `ca.`
+    pub origin: Name,
+    /// Maximum TTL of the records, in seconds
+    pub ttl: u32,
+    /// Name servers listed in the NS records, the first one is the SOA primary
+    pub nameservers: Vec<Name>,
+    /// Mailbox of the zone administrator, in SOA form
+    pub hostmaster: Name,
+}
+
+/// Private key signing the zone, in PKCS#8 form
+pub struct ZoneKey {
+    algorithm: Algorithm,
+    pkcs8: Vec<u8>,
+}
+
+impl ZoneKey {
+    /// Generate a new key
+    pub fn generate(algorithm: Algorithm) -> Result<Self> {
+        let pkcs8 = KeyPair::generate_pkcs8(algorithm)
+            .map_err(|error| anyhow!("failed to generate a {algorithm} key: {error}"))?;
+        Self::from_pkcs8(algorithm, pkcs8)
+    }
+
+    /// Load a key file, checking that it holds a key of `algorithm`
+    pub fn load(path: &Path, algorithm: Algorithm) -> Result<Self> {
+        let pkcs8 = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        Self::from_pkcs8(algorithm, pkcs8)
+            .with_context(|| format!("invalid key file {}", path.display()))
+    }
+
+    fn from_pkcs8(algorithm: Algorithm, pkcs8: Vec<u8>) -> Result<Self> {
+        let zone_key = Self { algorithm, pkcs8 };
+        zone_key.key_pair()?;
+        Ok(zone_key)
+    }
+
+    pub fn pkcs8(&self) -> &[u8] {
+        &self.pkcs8
+    }
+
+    pub fn key_pair(&self) -> Result<KeyPair<Private>> {
+        KeyFormat::Pkcs8
+            .decode_key(&self.pkcs8, None, self.algorithm)
+            .map_err(|error| anyhow!("not a PKCS#8 {} key: {error}", self.algorithm))
+    }
+
+    pub fn dnskey(&self) -> Result<DNSKEY> {
+        self.key_pair()?
+            .to_dnskey(self.algorithm)
+            .map_err(|error| anyhow!("failed to derive the DNSKEY: {error}"))
+    }
+
+    /// DS record to publish in the parent zone, with the key tag of the DNSKEY record as served
+    pub fn ds(&self, origin: &Name) -> Result<DS> {
+        let dnskey = self.dnskey()?;
+        let key_tag = dnskey
+            .calculate_key_tag()
+            .context("failed to compute the key tag")?;
+        let digest = dnskey
+            .to_digest(origin, DigestType::SHA256)
+            .context("failed to digest the DNSKEY")?;
+        Ok(DS::new(
+            key_tag,
+            self.algorithm,
+            DigestType::SHA256,
+            digest.as_ref().to_vec(),
+        ))
+    }
+
+    fn signer(&self, origin: &Name) -> Result<SigSigner> {
+        Ok(SigSigner::dnssec(
+            self.dnskey()?,
+            self.key_pair()?,
+            origin.clone(),
+            SIGNATURE_VALIDITY,
+        ))
+    }
+}
+
+/// Zone served at one point in time
+pub struct Zone {
+    pub authority: InMemoryAuthority,
+    /// Cluster time the zone was built at
+    pub cluster_time: i64,
+    /// Number of domains served
+    pub domain_count: usize,
+    /// Cluster time of the next lifecycle change of a served domain, after which the zone is stale
+    pub next_change: Option<i64>,
+}
+
+/// Builds the zone from an account source
+pub struct ZoneBuilder {
+    source: Source,
+    config: ZoneConfig,
+    key: Option<ZoneKey>,
+}
+
+impl ZoneBuilder {
+    /// Create a builder, signing the zone with `key` when given
+    pub fn new(source: Source, config: ZoneConfig, key: Option<ZoneKey>) -> Self {
+        Self {
+            source,
+            config,
+            key,
+        }
+    }
+
+    pub fn config(&self) -> &ZoneConfig {
+        &self.config
+    }
+
+    /// Scan every domain record and build the zone of the active, non-suspended domains
+    pub fn build(&self) -> Result<Zone> {
+        let program_state_address = find_program_state_address().0;
+        let accounts = self
+            .source
+            .get_multiple_accounts(&[program_state_address, sysvar::clock::ID])?;
+        let program_state: ProgramState = accounts[0]
+            .as_deref()
+            .map(|data| decode_account(&program_state_address, data, "program state"))
+            .transpose()?
+            .ok_or(ResolveError::NotInitialized)?;
+        let cluster_time = accounts[1]
+            .as_deref()
+            .map(decode_clock_timestamp)
+            .ok_or(ResolveError::MissingClock)??;
+
+        let origin = &self.config.origin;
+        let serial = u32::try_from(cluster_time).unwrap_or(0);
+        let mut authority = InMemoryAuthority::empty(origin.clone(), ZoneType::Primary, false);
+        for record in self.apex_records(serial) {
+            authority.upsert_mut(record, serial);
+        }
+
+        let mut domain_count = 0;
+        let mut next_change: Option<i64> = None;
+        for (address, data) in self
+            .source
+            .get_program_accounts(&DomainRecord::DISCRIMINATOR)?
+        {
+            let domain_record: DomainRecord = decode_account(&address, &data, "domain record")?;
+            let resolution = Resolution::new(
+                &domain_record.domain_name,
+                Some(&domain_record),
+                program_state.grace_period_seconds,
+                cluster_time,
+            );
+            // Names that don't resolve are left out of the zone and answered with NXDOMAIN
+            if !resolution.is_resolving() {
+                continue;
+            }
+            let Some(name) = zone_name(&resolution.domain_name, origin) else {
+                continue;
+            };
+
+            let change = resolution.next_state_change();
+            let ttl = change
+                .map(|change| {
+                    change
+                        .saturating_sub(cluster_time)
+                        .clamp(1, i64::from(self.config.ttl))
+                })
+                .map_or(self.config.ttl, |ttl| ttl as u32);
+            for record in domain_records(&name, &resolution.addresses, ttl) {
+                authority.upsert_mut(record, serial);
+            }
+            domain_count += 1;
+            next_change = match (next_change, change) {
+                (Some(next_change), Some(change)) => Some(next_change.min(change)),
+                (next_change, change) => next_change.or(change),
+            };
+        }
+
+        if let Some(key) = &self.key {
+            authority
+                .add_zone_signing_key_mut(key.signer(origin)?)
+                .map_err(|error| anyhow!("failed to add the zone signing key: {error}"))?;
+            authority
+                .secure_zone_mut()
+                .map_err(|error| anyhow!("failed to sign the zone: {error}"))?;
+        }
+
+        Ok(Zone {
+            authority,
+            cluster_time,
+            domain_count,
+            next_change,
+        })
+    }
+
+    fn apex_records(&self, serial: u32) -> Vec<Record> {
+        let ZoneConfig {
+            origin,
+            ttl,
+            nameservers,
+            hostmaster,
+        } = &self.config;
+
+        let soa = SOA::new(
+            nameservers[0].clone(),
+            hostmaster.clone(),
+            serial,
+            SOA_REFRESH,
+            SOA_RETRY,
+            SOA_EXPIRE,
+            *ttl,
+        );
+        std::iter::once(RData::SOA(soa))
+            .chain(
+                nameservers
+                    .iter()
+                    .map(|nameserver| RData::NS(NS(nameserver.clone()))),
+            )
+            .map(|rdata| Record::from_rdata(origin.clone(), *ttl, rdata))
+            .collect()
+    }
+}
+
+/// Name of a domain in the zone, `None` when its name is not a valid DNS label
+///
+/// Only lowercase LDH labels are served: 1 to 63 lowercase ASCII letters, digits and hyphens, neither starting nor
+/// ending with a hyphen. Other names the program accepts, e.g. containing dots or non-ASCII characters, have no
+/// single-label DNS form and are left out of the zone. Internationalized names are served when registered in their
+/// `xn--` form.
+fn zone_name(domain_name: &str, origin: &Name) -> Option<Name> {
+    let is_label = (1..=63).contains(&domain_name.len())
+        && !domain_name.starts_with('-')
+        && !domain_name.ends_with('-')
+        && domain_name
+            .bytes()
+            .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-');
+    if !is_label {
+        return None;
+    }
+
+    Name::from_ascii(domain_name)
+        .and_then(|label| label.append_domain(origin))
+        .ok()
+}
+
+/// Records of one domain: its stored DNS records, and TXT records of its chain addresses when
+/// it has no stored TXT record
+fn domain_records(name: &Name, addresses: &[ChainAddress], ttl: u32) -> Vec<Record> {
+    let stored = |chain_id: u8| {
+        addresses
+            .iter()
+            .filter(move |chain_address| chain_address.chain_id == chain_id)
+            .map(|chain_address| chain_address.address.trim())
+    };
+
+    // A CNAME excludes every other record at the name
+    let cname = stored(DNS_CNAME_CHAIN_ID).find_map(|target| {
+        Name::from_ascii(target).ok().map(|mut target| {
+            target.set_fqdn(true);
+            target
+        })
+    });
+    let rdatas: Vec<RData> = match cname {
+        Some(target) => vec![RData::CNAME(CNAME(target))],
+        None => {
+            let mut rdatas: Vec<RData> = stored(DNS_A_CHAIN_ID)
+                .filter_map(|address| address.parse::<Ipv4Addr>().ok())
+                .map(|address| RData::A(A(address)))
+                .chain(
+                    stored(DNS_AAAA_CHAIN_ID)
+                        .filter_map(|address| address.parse::<Ipv6Addr>().ok())
+                        .map(|address| RData::AAAA(AAAA(address))),
+                )
+                .collect();
+
+            let txt: Vec<RData> = stored(DNS_TXT_CHAIN_ID)
+                .map(|text| RData::TXT(TXT::new(vec![text.to_string()])))
+                .collect();
+            if txt.is_empty() {
+                rdatas.extend(
+                    addresses
+                        .iter()
+                        .filter(|chain_address| !is_dns_chain_id(chain_address.chain_id))
+                        .map(|chain_address| {
+                            RData::TXT(TXT::new(vec![format!(
+                                "{}:{}",
+                                chain_address.chain_id, chain_address.address
+                            )]))
+                        }),
+                );
+            } else {
+                rdatas.extend(txt);
+            }
+            rdatas
+        }
+    };
+
+    rdatas
+        .into_iter()
+        .map(|rdata| Record::from_rdata(name.clone(), ttl, rdata))
+        .collect()
+}
+
+fn is_dns_chain_id(chain_id: u8) -> bool {
+    matches!(
+        chain_id,
+        DNS_A_CHAIN_ID | DNS_AAAA_CHAIN_ID | DNS_CNAME_CHAIN_ID | DNS_TXT_CHAIN_ID
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::AccountSerialize;
+    use ca_registrar_client::constants::*;
+    use ca_registrar_client::pda::find_domain_record_address;
+    use hickory_server::authority::{Authority, LookupOptions};
+    use hickory_server::proto::rr::dnssec::rdata::DNSSECRData;
+    use hickory_server::proto::rr::{LowerName, RecordSet, RecordType, RrKey};
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    const NOW: i64 = 1_700_000_000;
+    const GRACE_PERIOD: i64 = 30 * SECONDS_PER_DAY;
+    const TTL: u32 = 300;
+    const WALLET: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+
+    fn data<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn address(chain_id: u8, address: &str) -> ChainAddress {
+        ChainAddress {
+            chain_id,
+            address: address.to_string(),
+        }
+    }
+
+    fn insert_domain(
+        snapshot: &mut Snapshot,
+        domain_name: &str,
+        expiry_timestamp: i64,
+        suspended: bool,
+        addresses: Vec<ChainAddress>,
+    ) {
+        let domain_record = DomainRecord {
+            domain_name: domain_name.to_string(),
+            owner: Pubkey::new_unique(),
+            expiry_timestamp,
+            registration_timestamp: NOW - 365 * SECONDS_PER_DAY,
+            registration_generation: 0,
+            addresses,
+            bump: 255,
+            suspended,
+            fuses: 0,
+            paid_lamports: 0,
+            paid_timestamp: 0,
+        };
+        snapshot.insert(
+            find_domain_record_address(domain_name).0,
+            ID,
+            data(&domain_record),
+        );
+    }
+
+    /// Registrar at `NOW` holding one name in each lifecycle state, and names with no DNS form
+    fn snapshot() -> Snapshot {
+        let mut snapshot = Snapshot::new();
+        let program_state = ProgramState {
+            base_price_usd: 500,
+            domains_registered: 0,
+            grace_period_seconds: GRACE_PERIOD,
+            paused: false,
+            timelock_delay_seconds: 0,
+            queued_changes_count: 0,
+            config_bounds: ConfigBounds {
+                min_base_price_usd: 0,
+                max_base_price_usd: u64::MAX,
+                min_grace_period_seconds: 0,
+                max_grace_period_seconds: MAX_GRACE_PERIOD_SECONDS_LIMIT,
+            },
+            fee_destinations: Vec::new(),
+            default_referral_share_bps: 0,
+            sunrise_merkle_root: [0; 32],
+            general_availability_timestamp: 0,
+            airdrop_merkle_root: [0; 32],
+            dispute_timelock_enabled: false,
+            release_refund_rate_bps: 0,
+            max_registration_horizon_seconds: DEFAULT_MAX_REGISTRATION_HORIZON_SECONDS,
+            min_registration_seconds: DEFAULT_MIN_REGISTRATION_SECONDS,
+            subdomain_protocol_fee_bps: 0,
+            registration_generations: 0,
+            bump: 255,
+        };
+        snapshot.insert(find_program_state_address().0, ID, data(&program_state));
+
+        let mut clock = vec![0; 32];
+        clock.extend_from_slice(&NOW.to_le_bytes());
+        snapshot.insert(sysvar::clock::ID, sysvar::ID, clock);
+
+        let far_expiry = NOW + 365 * SECONDS_PER_DAY;
+        // Expires in 100 seconds, within the zone TTL
+        insert_domain(
+            &mut snapshot,
+            "alice",
+            NOW + 100,
+            false,
+            vec![
+                address(DNS_A_CHAIN_ID, "203.0.113.7"),
+                address(SOLANA_CHAIN_ID, WALLET),
+            ],
+        );
+        insert_domain(
+            &mut snapshot,
+            "bob",
+            far_expiry,
+            false,
+            vec![
+                address(DNS_CNAME_CHAIN_ID, "alice.ca"),
+                address(DNS_A_CHAIN_ID, "203.0.113.8"),
+                address(DNS_TXT_CHAIN_ID, "hello"),
+            ],
+        );
+        insert_domain(
+            &mut snapshot,
+            "xn--caf-dma",
+            far_expiry,
+            false,
+            vec![address(DNS_AAAA_CHAIN_ID, "2001:db8::1")],
+        );
+        insert_domain(
+            &mut snapshot,
+            "carol",
+            NOW - GRACE_PERIOD - 1,
+            false,
+            Vec::new(),
+        );
+        insert_domain(&mut snapshot, "dave", NOW - 1, false, Vec::new());
+        insert_domain(&mut snapshot, "erin", far_expiry, true, Vec::new());
+        insert_domain(&mut snapshot, "pay.alice", far_expiry, false, Vec::new());
+        insert_domain(&mut snapshot, "-dash", far_expiry, false, Vec::new());
+        insert_domain(&mut snapshot, "café", far_expiry, false, Vec::new());
+
+        snapshot
+    }
+
+    fn build(key: Option<ZoneKey>) -> Zone {
+        let config = ZoneConfig {
+            origin: Name::from_ascii("ca.").unwrap(),
+            ttl: TTL,
+            nameservers: vec![Name::from_ascii("ns1.example.com.").unwrap()],
+            hostmaster: Name::from_ascii("hostmaster.example.com.").unwrap(),
+        };
+        ZoneBuilder::new(Box::new(snapshot()), config, key)
+            .build()
+            .unwrap()
+    }
+
+    fn lower_name(name: &str) -> LowerName {
+        LowerName::from(Name::from_ascii(name).unwrap())
+    }
+
+    async fn record_set(
+        zone: &Zone,
+        name: &str,
+        record_type: RecordType,
+    ) -> Option<Arc<RecordSet>> {
+        zone.authority
+            .records()
+            .await
+            .get(&RrKey::new(lower_name(name), record_type))
+            .cloned()
+    }
+
+    async fn is_nx_domain(zone: &Zone, name: &str) -> bool {
+        match zone
+            .authority
+            .lookup(&lower_name(name), RecordType::A, LookupOptions::default())
+            .await
+        {
+            Err(error) => error.is_nx_domain(),
+            Ok(_) => false,
+        }
+    }
+
+    #[test]
+    fn only_lowercase_single_labels_are_served() {
+        let origin = Name::from_ascii("ca.").unwrap();
+
+        assert_eq!(
+            zone_name("alice-2", &origin),
+            Some(Name::from_ascii("alice-2.ca.").unwrap())
+        );
+        assert!(zone_name("xn--caf-dma", &origin).is_some());
+        assert!(zone_name(&"a".repeat(63), &origin).is_some());
+
+        for domain_name in [
+            "",
+            "pay.alice",
+            "Alice",
+            "-alice",
+            "alice-",
+            "café",
+            "a_b",
+            &"a".repeat(64),
+        ] {
+            assert_eq!(zone_name(domain_name, &origin), None, "{domain_name:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn names_that_do_not_resolve_are_nxdomain() {
+        let zone = build(None);
+        assert_eq!(zone.cluster_time, NOW);
+        assert_eq!(zone.domain_count, 3);
+
+        assert!(!is_nx_domain(&zone, "alice.ca.").await);
+        for name in [
+            "carol.ca.",
+            "dave.ca.",
+            "erin.ca.",
+            "zed.ca.",
+            "pay.alice.ca.",
+        ] {
+            assert!(is_nx_domain(&zone, name).await, "{name}");
+        }
+    }
+
+    #[tokio::test]
+    async fn ttl_is_clamped_to_the_next_state_change() {
+        let zone = build(None);
+
+        // alice stops resolving 101 seconds from now, bob and the IDN name in a year
+        assert_eq!(zone.next_change, Some(NOW + 101));
+        let alice = record_set(&zone, "alice.ca.", RecordType::A).await.unwrap();
+        assert_eq!(alice.ttl(), 101);
+        let idn = record_set(&zone, "xn--caf-dma.ca.", RecordType::AAAA)
+            .await
+            .unwrap();
+        assert_eq!(idn.ttl(), TTL);
+
+        // Chain addresses are published as TXT records when no TXT record is stored
+        let txt = record_set(&zone, "alice.ca.", RecordType::TXT)
+            .await
+            .unwrap();
+        assert_eq!(
+            txt.records_without_rrsigs().next().unwrap().data(),
+            Some(&RData::TXT(TXT::new(vec![format!("0:{WALLET}")])))
+        );
+    }
+
+    #[tokio::test]
+    async fn cname_excludes_other_records() {
+        let zone = build(None);
+
+        let cname = record_set(&zone, "bob.ca.", RecordType::CNAME)
+            .await
+            .unwrap();
+        assert_eq!(
+            cname.records_without_rrsigs().next().unwrap().data(),
+            Some(&RData::CNAME(CNAME(Name::from_str("alice.ca.").unwrap())))
+        );
+        assert!(record_set(&zone, "bob.ca.", RecordType::A).await.is_none());
+        assert!(record_set(&zone, "bob.ca.", RecordType::TXT)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn signed_zone_publishes_the_dnskey_and_signatures() {
+        let unsigned = build(None);
+        assert!(record_set(&unsigned, "ca.", RecordType::DNSKEY)
+            .await
+            .is_none());
+        assert!(record_set(&unsigned, "alice.ca.", RecordType::A)
+            .await
+            .unwrap()
+            .rrsigs()
+            .is_empty());
+
+        let key = ZoneKey::generate(Algorithm::ED25519).unwrap();
+        let dnskey = key.dnskey().unwrap();
+        let zone = build(Some(key));
+
+        let dnskeys = record_set(&zone, "ca.", RecordType::DNSKEY).await.unwrap();
+        assert_eq!(
+            dnskeys.records_without_rrsigs().next().unwrap().data(),
+            Some(&RData::DNSSEC(DNSSECRData::DNSKEY(dnskey)))
+        );
+        assert!(!dnskeys.rrsigs().is_empty());
+        for (name, record_type) in [
+            ("ca.", RecordType::SOA),
+            ("alice.ca.", RecordType::A),
+            ("bob.ca.", RecordType::CNAME),
+        ] {
+            let record_set = record_set(&zone, name, record_type).await.unwrap();
+            assert!(!record_set.rrsigs().is_empty(), "{name} {record_type}");
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! HTTP routes of the gateway
+
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::header::CACHE_CONTROL;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::gateway::{Gateway, GatewayError};
+
+pub fn router(gateway: Arc<Gateway>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/v1/resolve/{name}", get(resolve))
+        .route("/v1/reverse/{address}", get(reverse))
+        .route("/v1/quote/{name}", get(quote))
+        .route("/v1/names", get(names))
+        .with_state(gateway)
+}
+
+#[derive(Deserialize)]
+struct ReverseQuery {
+    chain_id: Option<u8>,
+}
+
+#[derive(Deserialize)]
+struct QuoteQuery {
+    years: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct NamesQuery {
+    owner: Option<String>,
+}
+
+async fn health(State(gateway): State<Arc<Gateway>>) -> Response {
+    respond(gateway, false, |gateway| gateway.health()).await
+}
+
+async fn resolve(State(gateway): State<Arc<Gateway>>, Path(name): Path<String>) -> Response {
+    respond(gateway, true, move |gateway| gateway.resolve(&name)).await
+}
+
+async fn reverse(
+    State(gateway): State<Arc<Gateway>>,
+    Path(address): Path<String>,
+    Query(query): Query<ReverseQuery>,
+) -> Response {
+    respond(gateway, true, move |gateway| {
+        gateway.reverse(&address, query.chain_id)
+    })
+    .await
+}
+
+async fn quote(
+    State(gateway): State<Arc<Gateway>>,
+    Path(name): Path<String>,
+    Query(query): Query<QuoteQuery>,
+) -> Response {
+    respond(gateway, true, move |gateway| {
+        gateway.quote(&name, query.years.unwrap_or(1))
+    })
+    .await
+}
+
+async fn names(State(gateway): State<Arc<Gateway>>, Query(query): Query<NamesQuery>) -> Response {
+    respond(gateway, true, move |gateway| match query.owner {
+        Some(owner) => gateway.names(&owner),
+        None => Err(GatewayError::InvalidRequest(
+            "missing owner query parameter".to_string(),
+        )),
+    })
+    .await
+}
+
+/// Run a blocking gateway query and render its result as JSON
+async fn respond<T, F>(gateway: Arc<Gateway>, cacheable: bool, query: F) -> Response
+where
+    T: Serialize + Send + 'static,
+    F: FnOnce(&Gateway) -> Result<T, GatewayError> + Send + 'static,
+{
+    let max_age = gateway.ttl().as_secs();
+
+    match tokio::task::spawn_blocking(move || query(&gateway)).await {
+        Ok(Ok(body)) => {
+            let mut response = Json(body).into_response();
+            let cache_control = if cacheable {
+                HeaderValue::from_str(&format!("public, max-age={max_age}"))
+                    .expect("valid header value")
+            } else {
+                HeaderValue::from_static("no-store")
+            };
+            response.headers_mut().insert(CACHE_CONTROL, cache_control);
+            response
+        }
+        Ok(Err(error @ GatewayError::InvalidRequest(_))) => {
+            error_response(StatusCode::BAD_REQUEST, error)
+        }
+        Ok(Err(error @ GatewayError::Unavailable(_))) => {
+            error_response(StatusCode::SERVICE_UNAVAILABLE, error)
+        }
+        Err(error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, error),
+    }
+}
+
+fn error_response(status: StatusCode, error: impl std::fmt::Display) -> Response {
+    (status, Json(json!({ "error": error.to_string() }))).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use ca_registrar_resolver::Snapshot;
+    use serde_json::Value;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    use crate::gateway::tests::*;
+
+    async fn get(gateway: Gateway, uri: &str) -> (StatusCode, Option<HeaderValue>, Value) {
+        let response = router(Arc::new(gateway))
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let cache_control = response.headers().get(CACHE_CONTROL).cloned();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (
+            status,
+            cache_control,
+            serde_json::from_slice(&body).unwrap(),
+        )
+    }
+
+    fn gateway(snapshot: Snapshot) -> Gateway {
+        Gateway::new(Box::new(snapshot), price_feed(), Duration::from_secs(30))
+    }
+
+    #[tokio::test]
+    async fn responses_are_cached_for_the_ttl() {
+        let mut snapshot = registrar_snapshot();
+        insert_domain(
+            &mut snapshot,
+            "alice",
+            owner(),
+            NOW + 86_400,
+            false,
+            Vec::new(),
+        );
+
+        let (status, cache_control, body) = get(gateway(snapshot), "/v1/resolve/alice.ca").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(cache_control.unwrap(), "public, max-age=30");
+        assert_eq!(body["state"], "active");
+        assert_eq!(body["owner"], owner().to_string());
+
+        let (status, _, body) = get(gateway(registrar_snapshot()), "/v1/quote/alice?years=2").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["action"], "register");
+        assert_eq!(body["price_usd_cents"], 950);
+    }
+
+    #[tokio::test]
+    async fn health_is_never_cached() {
+        let (status, cache_control, body) = get(gateway(registrar_snapshot()), "/health").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(cache_control.unwrap(), "no-store");
+        assert_eq!(body["initialized"], true);
+        assert_eq!(body["cluster_time"], NOW);
+    }
+
+    #[tokio::test]
+    async fn malformed_requests_are_rejected() {
+        let (status, _, body) = get(gateway(registrar_snapshot()), "/v1/names").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "missing owner query parameter");
+
+        let (status, _, _) = get(
+            gateway(registrar_snapshot()),
+            "/v1/reverse/0xabc?chain_id=0",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _, body) = get(
+            gateway(registrar_snapshot()),
+            "/v1/reverse/0xabc?chain_id=1",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["names"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn source_failures_are_unavailable() {
+        let (status, _, body) = get(gateway(Snapshot::new()), "/v1/resolve/alice").await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["error"], "the registrar is not initialized");
+    }
+}
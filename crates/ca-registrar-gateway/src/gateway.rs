@@ -0,0 +1,703 @@
+//! Queries served by the gateway and their caches
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::Discriminator;
+use ca_registrar_client::fees::{quote_registration, SolUsdPrice};
+use ca_registrar_client::pda::*;
+use ca_registrar_client::*;
+use ca_registrar_resolver::*;
+use serde::Serialize;
+
+/// Source of the accounts served by the gateway
+pub type Source = Box<dyn AccountSource + Send + Sync>;
+
+/// Errors returned by the gateway queries
+#[derive(Debug)]
+pub enum GatewayError {
+    /// The request is malformed
+    InvalidRequest(String),
+    /// The account source failed or returned unusable data
+    Unavailable(String),
+}
+
+impl fmt::Display for GatewayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GatewayError::InvalidRequest(message) | GatewayError::Unavailable(message) => {
+                f.write_str(message)
+            }
+        }
+    }
+}
+
+impl From<ResolveError> for GatewayError {
+    fn from(error: ResolveError) -> Self {
+        GatewayError::Unavailable(error.to_string())
+    }
+}
+
+/// Every domain record, fetched in one scan and shared by owner and reverse lookups
+struct DomainIndex {
+    domain_records: Vec<DomainRecord>,
+    grace_period_seconds: i64,
+    cluster_time: i64,
+    fetched_at: Instant,
+}
+
+impl DomainIndex {
+    /// Resolutions of every domain at the current cluster time, extrapolated from the time of the scan
+    fn resolutions(&self) -> impl Iterator<Item = Resolution> + '_ {
+        let elapsed = i64::try_from(self.fetched_at.elapsed().as_secs()).unwrap_or(i64::MAX);
+        let current_time = self.cluster_time.saturating_add(elapsed);
+
+        self.domain_records.iter().map(move |domain_record| {
+            Resolution::new(
+                &domain_record.domain_name,
+                Some(domain_record),
+                self.grace_period_seconds,
+                current_time,
+            )
+        })
+    }
+}
+
+/// Accounts needed to price a registration
+struct Pricing {
+    program_state: ProgramState,
+    price_config: PriceConfig,
+    sol_usd_price: SolUsdPrice,
+    fetched_at: Instant,
+}
+
+/// Instruction a quote applies to, depending on the current state of the name
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteAction {
+    Register,
+    Renew,
+    Buy,
+}
+
+#[derive(Serialize)]
+pub struct QuoteResponse {
+    pub domain_name: String,
+    pub state: LifecycleState,
+    pub action: QuoteAction,
+    pub years: u32,
+    pub price_usd_cents: u64,
+    pub lamports: u64,
+    pub sol_usd_price: i64,
+    pub sol_usd_exponent: i32,
+    pub price_publish_time: i64,
+}
+
+#[derive(Serialize)]
+pub struct ReverseResponse {
+    pub address: String,
+    pub names: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct NamesResponse {
+    pub owner: String,
+    pub names: Vec<Resolution>,
+}
+
+#[derive(Serialize)]
+pub struct HealthResponse {
+    pub status: &'static str,
+    pub initialized: bool,
+    pub cluster_time: i64,
+}
+
+/// Resolves names and answers the other gateway queries from an account source
+pub struct Gateway {
+    resolver: Resolver<Source>,
+    price_feed: Pubkey,
+    ttl: Duration,
+    domain_index: Mutex<Option<DomainIndex>>,
+    pricing: Mutex<Option<Pricing>>,
+}
+
+impl Gateway {
+    /// Create a gateway caching responses for `ttl`, reading SOL/USD prices from `price_feed`
+    pub fn new(source: Source, price_feed: Pubkey, ttl: Duration) -> Self {
+        Self {
+            resolver: Resolver::new(source, ttl),
+            price_feed,
+            ttl,
+            domain_index: Mutex::new(None),
+            pricing: Mutex::new(None),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn resolve(&self, name: &str) -> Result<Resolution, GatewayError> {
+        Ok(self.resolver.resolve(&parse_domain_name(name)?)?)
+    }
+
+    /// Names currently resolving to `address`, optionally on one chain only
+    ///
+    /// Addresses are compared as stored on-chain, since each chain has its own format. Only Solana addresses
+    /// (chain 0) are checked, as public keys.
+    pub fn reverse(
+        &self,
+        address: &str,
+        chain_id: Option<u8>,
+    ) -> Result<ReverseResponse, GatewayError> {
+        if address.is_empty() {
+            return Err(GatewayError::InvalidRequest("address is empty".to_string()));
+        }
+        if chain_id == Some(SOLANA_CHAIN_ID) {
+            parse_pubkey(address)?;
+        }
+        let domain_index = self.domain_index()?;
+
+        let mut names: Vec<String> = domain_index
+            .as_ref()
+            .expect("domain index is loaded")
+            .resolutions()
+            .filter(|resolution| {
+                resolution.addresses.iter().any(|chain_address| {
+                    chain_address.address == address
+                        && chain_id.is_none_or(|chain_id| chain_address.chain_id == chain_id)
+                })
+            })
+            .map(|resolution| resolution.domain_name)
+            .collect();
+        names.sort();
+
+        Ok(ReverseResponse {
+            address: address.to_string(),
+            names,
+        })
+    }
+
+    /// Charge of registering, renewing or buying a name for `years`
+    pub fn quote(&self, name: &str, years: u32) -> Result<QuoteResponse, GatewayError> {
+        if years == 0 {
+            return Err(GatewayError::InvalidRequest(
+                "years must be at least 1".to_string(),
+            ));
+        }
+        let resolution = self.resolve(name)?;
+        let pricing = self.pricing()?;
+        let pricing = pricing.as_ref().expect("pricing is loaded");
+
+        let fee_quote = quote_registration(
+            &pricing.program_state,
+            &pricing.price_config,
+            &RegistrationPeriod::Years { count: years },
+            None,
+            &pricing.sol_usd_price,
+        )
+        .map_err(|error| GatewayError::InvalidRequest(error.to_string()))?;
+
+        Ok(QuoteResponse {
+            action: match resolution.state {
+                LifecycleState::Unregistered => QuoteAction::Register,
+                LifecycleState::Active | LifecycleState::Grace => QuoteAction::Renew,
+                LifecycleState::Expired => QuoteAction::Buy,
+            },
+            domain_name: resolution.domain_name,
+            state: resolution.state,
+            years,
+            price_usd_cents: fee_quote.price_usd,
+            lamports: fee_quote.lamports,
+            sol_usd_price: pricing.sol_usd_price.price,
+            sol_usd_exponent: pricing.sol_usd_price.exponent,
+            price_publish_time: pricing.sol_usd_price.publish_time,
+        })
+    }
+
+    /// Names held by `owner`, active or in their grace period
+    pub fn names(&self, owner: &str) -> Result<NamesResponse, GatewayError> {
+        let owner = parse_pubkey(owner)?;
+        let domain_index = self.domain_index()?;
+
+        let mut names: Vec<Resolution> = domain_index
+            .as_ref()
+            .expect("domain index is loaded")
+            .resolutions()
+            .filter(|resolution| resolution.owner == Some(owner))
+            .collect();
+        names.sort_by(|a, b| a.domain_name.cmp(&b.domain_name));
+
+        Ok(NamesResponse {
+            owner: owner.to_string(),
+            names,
+        })
+    }
+
+    /// Check that the account source answers, never cached
+    pub fn health(&self) -> Result<HealthResponse, GatewayError> {
+        let accounts = self
+            .resolver
+            .source()
+            .get_multiple_accounts(&[find_program_state_address().0, sysvar::clock::ID])?;
+        let cluster_time = accounts[1]
+            .as_deref()
+            .map(decode_clock_timestamp)
+            .ok_or(ResolveError::MissingClock)??;
+
+        Ok(HealthResponse {
+            status: "ok",
+            initialized: accounts[0].is_some(),
+            cluster_time,
+        })
+    }
+
+    /// Domain index, scanned again once older than the TTL
+    fn domain_index(&self) -> Result<MutexGuard<'_, Option<DomainIndex>>, GatewayError> {
+        let mut domain_index = self
+            .domain_index
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if domain_index
+            .as_ref()
+            .is_none_or(|index| index.fetched_at.elapsed() >= self.ttl)
+        {
+            *domain_index = Some(self.scan_domain_records()?);
+        }
+
+        Ok(domain_index)
+    }
+
+    fn scan_domain_records(&self) -> Result<DomainIndex, GatewayError> {
+        let source = self.resolver.source();
+        let fetched_at = Instant::now();
+
+        let accounts =
+            source.get_multiple_accounts(&[find_program_state_address().0, sysvar::clock::ID])?;
+        let program_state: ProgramState = accounts[0]
+            .as_deref()
+            .map(|data| decode_account(&find_program_state_address().0, data, "program state"))
+            .transpose()?
+            .ok_or(ResolveError::NotInitialized)?;
+        let cluster_time = accounts[1]
+            .as_deref()
+            .map(decode_clock_timestamp)
+            .ok_or(ResolveError::MissingClock)??;
+
+        let domain_records = source
+            .get_program_accounts(&DomainRecord::DISCRIMINATOR)?
+            .into_iter()
+            .map(|(address, data)| decode_account(&address, &data, "domain record"))
+            .collect::<Result<_, _>>()?;
+
+        Ok(DomainIndex {
+            domain_records,
+            grace_period_seconds: program_state.grace_period_seconds,
+            cluster_time,
+            fetched_at,
+        })
+    }
+
+    /// Pricing accounts, fetched again once older than the TTL
+    fn pricing(&self) -> Result<MutexGuard<'_, Option<Pricing>>, GatewayError> {
+        let mut pricing = self
+            .pricing
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if pricing
+            .as_ref()
+            .is_none_or(|pricing| pricing.fetched_at.elapsed() >= self.ttl)
+        {
+            *pricing = Some(self.fetch_pricing()?);
+        }
+
+        Ok(pricing)
+    }
+
+    fn fetch_pricing(&self) -> Result<Pricing, GatewayError> {
+        let fetched_at = Instant::now();
+        let addresses = [
+            find_program_state_address().0,
+            find_price_config_address().0,
+            self.price_feed,
+        ];
+        let accounts = self.resolver.source().get_multiple_accounts(&addresses)?;
+
+        let program_state = accounts[0]
+            .as_deref()
+            .map(|data| decode_account(&addresses[0], data, "program state"))
+            .transpose()?
+            .ok_or(ResolveError::NotInitialized)?;
+        let price_config = accounts[1]
+            .as_deref()
+            .map(|data| decode_account(&addresses[1], data, "price config"))
+            .transpose()?
+            .ok_or(ResolveError::NotInitialized)?;
+        let sol_usd_price = accounts[2]
+            .as_deref()
+            .ok_or_else(|| {
+                GatewayError::Unavailable(format!("price feed {} does not exist", self.price_feed))
+            })
+            .and_then(|data| {
+                SolUsdPrice::from_price_update(data).map_err(|_| {
+                    GatewayError::Unavailable(format!(
+                        "price feed {} is not a SOL/USD price update",
+                        self.price_feed
+                    ))
+                })
+            })?;
+
+        Ok(Pricing {
+            program_state,
+            price_config,
+            sol_usd_price,
+            fetched_at,
+        })
+    }
+}
+
+fn parse_domain_name(name: &str) -> Result<String, GatewayError> {
+    let domain_name = normalize_domain_name(name);
+    if domain_name.is_empty() {
+        return Err(GatewayError::InvalidRequest(
+            "domain name is empty".to_string(),
+        ));
+    }
+
+    Ok(domain_name)
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey, GatewayError> {
+    Pubkey::from_str(value)
+        .map_err(|_| GatewayError::InvalidRequest(format!("invalid public key {value:?}")))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+    use ca_registrar_client::constants::*;
+    use pyth_solana_receiver_sdk::price_update::{
+        get_feed_id_from_hex, PriceFeedMessage, PriceUpdateV2, VerificationLevel,
+    };
+
+    pub(crate) const NOW: i64 = 1_700_000_000;
+    const GRACE_PERIOD: i64 = 30 * SECONDS_PER_DAY;
+    const WALLET: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+    const ETHEREUM_ADDRESS: &str = "0x7890123456789012345678901234567890123456";
+
+    pub(crate) fn price_feed() -> Pubkey {
+        DEFAULT_SOL_USD_PRICE_FEED
+    }
+
+    pub(crate) fn owner() -> Pubkey {
+        Pubkey::new_from_array([7; 32])
+    }
+
+    fn data<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn address(chain_id: u8, address: &str) -> ChainAddress {
+        ChainAddress {
+            chain_id,
+            address: address.to_string(),
+        }
+    }
+
+    pub(crate) fn insert_domain(
+        snapshot: &mut Snapshot,
+        domain_name: &str,
+        owner: Pubkey,
+        expiry_timestamp: i64,
+        suspended: bool,
+        addresses: Vec<ChainAddress>,
+    ) {
+        let domain_record = DomainRecord {
+            domain_name: domain_name.to_string(),
+            owner,
+            expiry_timestamp,
+            registration_timestamp: expiry_timestamp - 365 * SECONDS_PER_DAY,
+            registration_generation: 0,
+            addresses,
+            bump: 255,
+            suspended,
+            fuses: 0,
+            paid_lamports: 0,
+            paid_timestamp: 0,
+        };
+        snapshot.insert(
+            find_domain_record_address(domain_name).0,
+            ID,
+            data(&domain_record),
+        );
+    }
+
+    /// Initialized registrar at `NOW` with a $5.00 base price, 5% off from two years and SOL at $150
+    pub(crate) fn registrar_snapshot() -> Snapshot {
+        let mut snapshot = Snapshot::new();
+        let program_state = ProgramState {
+            base_price_usd: 500,
+            domains_registered: 0,
+            grace_period_seconds: GRACE_PERIOD,
+            paused: false,
+            timelock_delay_seconds: 0,
+            queued_changes_count: 0,
+            config_bounds: ConfigBounds {
+                min_base_price_usd: 0,
+                max_base_price_usd: u64::MAX,
+                min_grace_period_seconds: 0,
+                max_grace_period_seconds: MAX_GRACE_PERIOD_SECONDS_LIMIT,
+            },
+            fee_destinations: Vec::new(),
+            default_referral_share_bps: 0,
+            sunrise_merkle_root: [0; 32],
+            general_availability_timestamp: 0,
+            airdrop_merkle_root: [0; 32],
+            dispute_timelock_enabled: false,
+            release_refund_rate_bps: 0,
+            max_registration_horizon_seconds: DEFAULT_MAX_REGISTRATION_HORIZON_SECONDS,
+            min_registration_seconds: DEFAULT_MIN_REGISTRATION_SECONDS,
+            subdomain_protocol_fee_bps: 0,
+            registration_generations: 0,
+            bump: 255,
+        };
+        snapshot.insert(find_program_state_address().0, ID, data(&program_state));
+
+        let price_config = PriceConfig {
+            multi_year_discounts: vec![MultiYearDiscount {
+                min_years: 2,
+                discount_bps: 500,
+            }],
+            bump: 255,
+        };
+        snapshot.insert(find_price_config_address().0, ID, data(&price_config));
+
+        let price_update = PriceUpdateV2 {
+            write_authority: Pubkey::new_unique(),
+            verification_level: VerificationLevel::Full,
+            price_message: PriceFeedMessage {
+                feed_id: get_feed_id_from_hex(SOL_USD_PRICE_FEED_ID).unwrap(),
+                price: 15_000_000_000,
+                conf: 0,
+                exponent: -8,
+                publish_time: NOW,
+                prev_publish_time: NOW - 1,
+                ema_price: 15_000_000_000,
+                ema_conf: 0,
+            },
+            posted_slot: 0,
+        };
+        snapshot.insert(price_feed(), Pubkey::new_unique(), data(&price_update));
+
+        let mut clock = vec![0; 32];
+        clock.extend_from_slice(&NOW.to_le_bytes());
+        snapshot.insert(sysvar::clock::ID, sysvar::ID, clock);
+
+        snapshot
+    }
+
+    /// Registrar holding one name in each lifecycle state
+    fn gateway() -> Gateway {
+        let mut snapshot = registrar_snapshot();
+        let wallet = address(SOLANA_CHAIN_ID, WALLET);
+        insert_domain(
+            &mut snapshot,
+            "alice",
+            owner(),
+            NOW + SECONDS_PER_DAY,
+            false,
+            vec![
+                wallet.clone(),
+                address(1, ETHEREUM_ADDRESS),
+                address(DNS_A_CHAIN_ID, "203.0.113.7"),
+            ],
+        );
+        insert_domain(
+            &mut snapshot,
+            "bob",
+            owner(),
+            NOW - 1,
+            false,
+            vec![wallet.clone()],
+        );
+        insert_domain(
+            &mut snapshot,
+            "carol",
+            owner(),
+            NOW - GRACE_PERIOD - 1,
+            false,
+            vec![wallet.clone()],
+        );
+        insert_domain(
+            &mut snapshot,
+            "dave",
+            owner(),
+            NOW + SECONDS_PER_DAY,
+            true,
+            vec![wallet],
+        );
+        insert_domain(
+            &mut snapshot,
+            "erin",
+            Pubkey::new_unique(),
+            NOW + SECONDS_PER_DAY,
+            false,
+            vec![address(1, ETHEREUM_ADDRESS)],
+        );
+
+        Gateway::new(Box::new(snapshot), price_feed(), Duration::from_secs(30))
+    }
+
+    #[test]
+    fn resolve_returns_the_lifecycle_state() {
+        let gateway = gateway();
+
+        let alice = gateway.resolve("Alice.ca").unwrap();
+        assert_eq!(alice.domain_name, "alice");
+        assert_eq!(alice.state, LifecycleState::Active);
+        assert_eq!(alice.owner, Some(owner()));
+        assert_eq!(alice.address(SOLANA_CHAIN_ID), Some(WALLET));
+        assert_eq!(alice.resolved_at, NOW);
+
+        let bob = gateway.resolve("bob").unwrap();
+        assert_eq!(bob.state, LifecycleState::Grace);
+        assert!(bob.addresses.is_empty());
+
+        assert_eq!(
+            gateway.resolve("carol").unwrap().state,
+            LifecycleState::Expired
+        );
+        assert_eq!(
+            gateway.resolve("zed").unwrap().state,
+            LifecycleState::Unregistered
+        );
+        assert!(matches!(
+            gateway.resolve(".ca"),
+            Err(GatewayError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn reverse_matches_addresses_on_their_chain() {
+        let gateway = gateway();
+        let names =
+            |address: &str, chain_id: Option<u8>| gateway.reverse(address, chain_id).unwrap().names;
+
+        // Names in their grace period, expired or suspended do not resolve
+        assert_eq!(names(WALLET, None), ["alice"]);
+        assert_eq!(names(WALLET, Some(SOLANA_CHAIN_ID)), ["alice"]);
+        assert!(names(WALLET, Some(1)).is_empty());
+
+        // Other chains are matched as stored, without parsing
+        assert_eq!(names(ETHEREUM_ADDRESS, None), ["alice", "erin"]);
+        assert_eq!(names(ETHEREUM_ADDRESS, Some(1)), ["alice", "erin"]);
+        assert_eq!(names("203.0.113.7", Some(DNS_A_CHAIN_ID)), ["alice"]);
+        assert!(names(ETHEREUM_ADDRESS, Some(2)).is_empty());
+
+        // Only Solana addresses must be public keys
+        assert!(matches!(
+            gateway.reverse(ETHEREUM_ADDRESS, Some(SOLANA_CHAIN_ID)),
+            Err(GatewayError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            gateway.reverse("", None),
+            Err(GatewayError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn quote_follows_the_lifecycle_state() {
+        let gateway = gateway();
+
+        let renewal = gateway.quote("alice", 2).unwrap();
+        assert_eq!(renewal.action, QuoteAction::Renew);
+        assert_eq!(renewal.price_usd_cents, 950);
+        assert_eq!(renewal.lamports, 63_333_333);
+        assert_eq!(renewal.price_publish_time, NOW);
+
+        assert_eq!(gateway.quote("bob", 1).unwrap().action, QuoteAction::Renew);
+        assert_eq!(gateway.quote("carol", 1).unwrap().action, QuoteAction::Buy);
+
+        let registration = gateway.quote("zed", 1).unwrap();
+        assert_eq!(registration.action, QuoteAction::Register);
+        assert_eq!(registration.price_usd_cents, 500);
+        assert_eq!(registration.lamports, 33_333_333);
+
+        assert!(matches!(
+            gateway.quote("zed", 0),
+            Err(GatewayError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn quote_needs_the_price_feed() {
+        let gateway = Gateway::new(
+            Box::new(registrar_snapshot()),
+            Pubkey::new_unique(),
+            Duration::from_secs(30),
+        );
+
+        assert!(matches!(
+            gateway.quote("zed", 1),
+            Err(GatewayError::Unavailable(_))
+        ));
+    }
+
+    #[test]
+    fn names_lists_the_names_held_by_the_owner() {
+        let gateway = gateway();
+
+        // Expired names are no longer held, suspended ones still are
+        let names: Vec<_> = gateway
+            .names(&owner().to_string())
+            .unwrap()
+            .names
+            .into_iter()
+            .map(|resolution| (resolution.domain_name, resolution.state))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("alice".to_string(), LifecycleState::Active),
+                ("bob".to_string(), LifecycleState::Grace),
+                ("dave".to_string(), LifecycleState::Active),
+            ]
+        );
+
+        assert!(gateway
+            .names(&Pubkey::new_unique().to_string())
+            .unwrap()
+            .names
+            .is_empty());
+        assert!(matches!(
+            gateway.names("alice"),
+            Err(GatewayError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn health_reports_the_cluster_time() {
+        let health = gateway().health().unwrap();
+        assert_eq!(health.status, "ok");
+        assert!(health.initialized);
+        assert_eq!(health.cluster_time, NOW);
+
+        let mut snapshot = Snapshot::new();
+        let mut clock = vec![0; 32];
+        clock.extend_from_slice(&NOW.to_le_bytes());
+        snapshot.insert(sysvar::clock::ID, sysvar::ID, clock);
+        let uninitialized = Gateway::new(Box::new(snapshot), price_feed(), Duration::from_secs(30));
+        assert!(!uninitialized.health().unwrap().initialized);
+        assert!(matches!(
+            uninitialized.resolve("alice"),
+            Err(GatewayError::Unavailable(_))
+        ));
+    }
+}
@@ -0,0 +1,87 @@
+//! HTTP gateway resolving .ca domains
+//!
+//! Serves JSON over REST from an RPC endpoint, or from a snapshot file captured with `ca-registrar snapshot`.
+
+mod api;
+mod gateway;
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use ca_registrar_client::DEFAULT_SOL_USD_PRICE_FEED;
+use ca_registrar_resolver::Snapshot;
+use clap::Parser;
+use solana_rpc_client::rpc_client::RpcClient;
+
+use crate::gateway::{Gateway, Source};
+
+#[derive(Parser)]
+#[command(
+    name = "ca-registrar-gateway",
+    version,
+    about = "HTTP gateway resolving .ca domains"
+)]
+struct Args {
+    /// Address to listen on
+    #[arg(
+        long,
+        env = "CA_REGISTRAR_GATEWAY_LISTEN",
+        default_value = "127.0.0.1:8080"
+    )]
+    listen: SocketAddr,
+
+    /// RPC endpoint of the cluster
+    #[arg(
+        long,
+        short = 'u',
+        env = "CA_REGISTRAR_RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+
+    /// Snapshot file to serve instead of the RPC endpoint
+    #[arg(long, conflicts_with = "url")]
+    snapshot: Option<PathBuf>,
+
+    /// Time responses are cached for, in seconds
+    #[arg(long, default_value_t = 30)]
+    cache_ttl: u64,
+
+    /// Pyth SOL/USD price update account used for quotes
+    #[arg(long, default_value_t = DEFAULT_SOL_USD_PRICE_FEED)]
+    price_feed: Pubkey,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let source: Source = match &args.snapshot {
+        Some(path) => Box::new(Snapshot::load(path)?),
+        None => Box::new(RpcClient::new(args.url.clone())),
+    };
+    let gateway = Arc::new(Gateway::new(
+        source,
+        args.price_feed,
+        Duration::from_secs(args.cache_ttl),
+    ));
+
+    let listener = tokio::net::TcpListener::bind(args.listen)
+        .await
+        .with_context(|| format!("failed to listen on {}", args.listen))?;
+    match &args.snapshot {
+        Some(path) => println!("Serving {} on http://{}", path.display(), args.listen),
+        None => println!("Serving {} on http://{}", args.url, args.listen),
+    }
+
+    axum::serve(listener, api::router(gateway))
+        .with_graceful_shutdown(async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await
+        .context("server failed")
+}
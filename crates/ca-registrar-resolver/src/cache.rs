@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::resolution::Resolution;
+
+/// Maximum number of cached resolutions, expired entries are pruned when it is reached
+const DEFAULT_CAPACITY: usize = 10_000;
+
+struct CacheEntry {
+    resolution: Resolution,
+    expires_at: i64,
+}
+
+/// Cluster time observed in a resolution, and the local time it was observed at
+#[derive(Clone, Copy)]
+struct ClusterClock {
+    cluster_time: i64,
+    observed_at_ms: u64,
+}
+
+/// TTL cache of resolutions, keyed by normalized domain name
+///
+/// Entries expire on the cluster clock the resolutions are made at: `ttl` after `resolved_at`, or at the next
+/// lifecycle state change if sooner, so an expired name is never served as resolving. Between fetches, the current
+/// cluster time is the last `resolved_at` advanced by the local time elapsed since it was inserted. Local times are
+/// passed in by the caller as milliseconds since the Unix epoch, which keeps the cache usable from WASM, and are only
+/// ever subtracted from each other, so a local clock that disagrees with the cluster does not change expiries.
+pub struct ResolutionCache {
+    ttl_seconds: i64,
+    capacity: usize,
+    entries: HashMap<String, CacheEntry>,
+    clock: Option<ClusterClock>,
+}
+
+impl ResolutionCache {
+    /// Create a cache keeping resolutions for `ttl`, rounded down to whole seconds, a zero TTL disables caching
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_capacity(ttl, DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl_seconds: i64::try_from(ttl.as_secs()).unwrap_or(i64::MAX),
+            capacity,
+            entries: HashMap::new(),
+            clock: None,
+        }
+    }
+
+    /// Current cluster time, extrapolated from the last inserted resolution to `now_ms`
+    fn cluster_time(&self, now_ms: u64) -> Option<i64> {
+        self.clock.map(|clock| {
+            let elapsed_seconds = now_ms.saturating_sub(clock.observed_at_ms) / 1_000;
+            clock
+                .cluster_time
+                .saturating_add(i64::try_from(elapsed_seconds).unwrap_or(i64::MAX))
+        })
+    }
+
+    /// Cached resolution of a name, if still fresh at `now_ms`
+    pub fn get(&self, domain_name: &str, now_ms: u64) -> Option<&Resolution> {
+        let cluster_time = self.cluster_time(now_ms)?;
+        self.entries
+            .get(domain_name)
+            .filter(|entry| cluster_time < entry.expires_at)
+            .map(|entry| &entry.resolution)
+    }
+
+    /// Cache a resolution made at `now_ms`
+    pub fn insert(&mut self, resolution: Resolution, now_ms: u64) {
+        self.clock = Some(ClusterClock {
+            cluster_time: resolution.resolved_at,
+            observed_at_ms: now_ms,
+        });
+
+        let expires_at = resolution
+            .next_state_change()
+            .unwrap_or(i64::MAX)
+            .min(resolution.resolved_at.saturating_add(self.ttl_seconds));
+        if expires_at <= resolution.resolved_at {
+            return;
+        }
+
+        if self.entries.len() >= self.capacity
+            && !self.entries.contains_key(&resolution.domain_name)
+        {
+            self.prune(now_ms);
+            if self.entries.len() >= self.capacity {
+                return;
+            }
+        }
+
+        self.entries.insert(
+            resolution.domain_name.clone(),
+            CacheEntry {
+                resolution,
+                expires_at,
+            },
+        );
+    }
+
+    /// Drop the cached resolution of a name, e.g. after sending a transaction that modifies it
+    pub fn remove(&mut self, domain_name: &str) {
+        self.entries.remove(domain_name);
+    }
+
+    /// Drop every entry expired at `now_ms`
+    pub fn prune(&mut self, now_ms: u64) {
+        if let Some(cluster_time) = self.cluster_time(now_ms) {
+            self.entries
+                .retain(|_, entry| cluster_time < entry.expires_at);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolution::LifecycleState;
+
+    // Cluster time of the resolutions, and a local time far from it
+    const CLUSTER_TIME: i64 = 1_700_000_000;
+    const LOCAL_MS: u64 = 1_800_000_000_000;
+
+    fn resolution(domain_name: &str, state: LifecycleState, expiry_timestamp: i64) -> Resolution {
+        Resolution {
+            domain_name: domain_name.to_string(),
+            state,
+            owner: None,
+            addresses: Vec::new(),
+            suspended: false,
+            expiry_timestamp: Some(expiry_timestamp),
+            grace_period_end_timestamp: Some(expiry_timestamp + 86_400),
+            resolved_at: CLUSTER_TIME,
+        }
+    }
+
+    #[test]
+    fn entries_expire_after_the_ttl_whatever_the_local_clock() {
+        let mut cache = ResolutionCache::new(Duration::from_secs(60));
+        let alice = resolution("alice", LifecycleState::Active, CLUSTER_TIME + 86_400);
+        cache.insert(alice.clone(), LOCAL_MS);
+
+        assert_eq!(cache.get("alice", LOCAL_MS), Some(&alice));
+        assert_eq!(cache.get("alice", LOCAL_MS + 59_999), Some(&alice));
+        assert_eq!(cache.get("alice", LOCAL_MS + 60_000), None);
+        assert_eq!(cache.get("bob", LOCAL_MS), None);
+    }
+
+    #[test]
+    fn entries_expire_at_the_next_state_change() {
+        let mut cache = ResolutionCache::new(Duration::from_secs(3_600));
+
+        // Active until the expiry second, then in its grace period
+        cache.insert(
+            resolution("alice", LifecycleState::Active, CLUSTER_TIME + 9),
+            LOCAL_MS,
+        );
+        assert!(cache.get("alice", LOCAL_MS + 9_999).is_some());
+        assert!(cache.get("alice", LOCAL_MS + 10_000).is_none());
+
+        // In its grace period until the grace period ends
+        cache.insert(
+            resolution("bob", LifecycleState::Grace, CLUSTER_TIME - 86_390),
+            LOCAL_MS,
+        );
+        assert!(cache.get("bob", LOCAL_MS + 10_999).is_some());
+        assert!(cache.get("bob", LOCAL_MS + 11_000).is_none());
+
+        // Unregistered names only change with a transaction, they last the TTL
+        let mut carol = resolution("carol", LifecycleState::Unregistered, 0);
+        carol.expiry_timestamp = None;
+        carol.grace_period_end_timestamp = None;
+        cache.insert(carol, LOCAL_MS);
+        assert!(cache.get("carol", LOCAL_MS + 3_599_999).is_some());
+        assert!(cache.get("carol", LOCAL_MS + 3_600_000).is_none());
+    }
+
+    #[test]
+    fn newer_resolutions_move_the_cluster_clock() {
+        let mut cache = ResolutionCache::new(Duration::from_secs(60));
+        cache.insert(
+            resolution("alice", LifecycleState::Active, CLUSTER_TIME + 86_400),
+            LOCAL_MS,
+        );
+
+        // Resolved 30 seconds later on the cluster, but only 1 second later locally
+        let mut bob = resolution("bob", LifecycleState::Active, CLUSTER_TIME + 86_400);
+        bob.resolved_at = CLUSTER_TIME + 30;
+        cache.insert(bob, LOCAL_MS + 1_000);
+
+        assert!(cache.get("alice", LOCAL_MS + 31_000).is_none());
+        assert!(cache.get("bob", LOCAL_MS + 31_000).is_some());
+    }
+
+    #[test]
+    fn zero_ttl_disables_caching() {
+        let mut cache = ResolutionCache::new(Duration::ZERO);
+        cache.insert(
+            resolution("alice", LifecycleState::Active, CLUSTER_TIME + 86_400),
+            LOCAL_MS,
+        );
+
+        assert!(cache.get("alice", LOCAL_MS).is_none());
+    }
+
+    #[test]
+    fn full_cache_prunes_expired_entries() {
+        let mut cache = ResolutionCache::with_capacity(Duration::from_secs(60), 2);
+        cache.insert(
+            resolution("alice", LifecycleState::Active, CLUSTER_TIME + 5),
+            LOCAL_MS,
+        );
+        cache.insert(
+            resolution("bob", LifecycleState::Active, CLUSTER_TIME + 86_400),
+            LOCAL_MS,
+        );
+
+        // Full of fresh entries, new names are not cached
+        cache.insert(
+            resolution("carol", LifecycleState::Active, CLUSTER_TIME + 86_400),
+            LOCAL_MS,
+        );
+        assert!(cache.get("carol", LOCAL_MS).is_none());
+
+        // Once alice expires, her entry makes room
+        let mut dave = resolution("dave", LifecycleState::Active, CLUSTER_TIME + 86_400);
+        dave.resolved_at = CLUSTER_TIME + 10;
+        cache.insert(dave, LOCAL_MS + 10_000);
+        assert!(cache.get("dave", LOCAL_MS + 10_000).is_some());
+        assert!(cache.get("bob", LOCAL_MS + 10_000).is_some());
+        assert!(cache.get("alice", LOCAL_MS + 10_000).is_none());
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::Pubkey;
+use thiserror::Error;
+
+/// Errors returned while resolving names
+#[derive(Debug, Error)]
+pub enum ResolveError {
+    /// The account source failed to return the accounts
+    #[error("failed to fetch accounts: {0}")]
+    Source(String),
+
+    /// The account source returned a different number of accounts than requested
+    #[error("expected {expected} accounts, got {actual}")]
+    AccountCount { expected: usize, actual: usize },
+
+    /// An account could not be deserialized as the expected type
+    #[error("account {address} is not a valid {account_type}")]
+    InvalidAccount {
+        address: Pubkey,
+        account_type: &'static str,
+    },
+
+    /// The program state account does not exist
+    #[error("the registrar is not initialized")]
+    NotInitialized,
+
+    /// A snapshot file could not be read, parsed or written
+    #[error("invalid snapshot: {0}")]
+    Snapshot(String),
+
+    /// The clock sysvar was not returned
+    #[error("the clock sysvar is missing")]
+    MissingClock,
+}
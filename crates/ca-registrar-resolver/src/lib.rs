@@ -0,0 +1,25 @@
+//! Off-chain resolver for .ca domains
+//!
+//! Resolves names to a [`Resolution`] carrying the lifecycle state computed the way the program does, from the
+//! domain record, the grace period of the program state and the cluster clock. All three are read in a single
+//! `getMultipleAccounts` batch, so a resolution never mixes data from different slots. Only active, non-suspended
+//! names resolve to addresses: names in their grace period, expired or unregistered never do.
+//!
+//! Accounts are read through an [`AccountSource`], implemented for the RPC client with the `rpc` feature (enabled by
+//! default), and for local snapshot files with the `snapshot` feature. The `wasm` feature adds JavaScript bindings that resolve accounts fetched by the caller.
+
+mod cache;
+mod error;
+mod resolution;
+mod resolver;
+#[cfg(feature = "snapshot")]
+mod snapshot;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use cache::ResolutionCache;
+pub use error::ResolveError;
+pub use resolution::*;
+pub use resolver::*;
+#[cfg(feature = "snapshot")]
+pub use snapshot::Snapshot;
@@ -0,0 +1,449 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::AccountDeserialize;
+use ca_registrar_client::pda::*;
+use ca_registrar_client::*;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+use crate::error::ResolveError;
+
+/// Offset of `unix_timestamp` in the clock sysvar data
+pub(crate) const CLOCK_UNIX_TIMESTAMP_OFFSET: usize = 32;
+
+/// Strip the optional `.ca` suffix of a domain name and lowercase it
+///
+/// The program rejects names that `str::to_lowercase` changes, so lowercasing with the same function maps every
+/// spelling of a name to the only one that can be registered.
+pub fn normalize_domain_name(name: &str) -> String {
+    let name = name.trim().trim_end_matches('.').to_lowercase();
+    match name.strip_suffix(".ca") {
+        Some(domain_name) => domain_name.to_string(),
+        None => name,
+    }
+}
+
+/// Lifecycle state of a domain name
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LifecycleState {
+    /// Registered and not expired
+    Active,
+    /// Expired, the owner can still renew
+    Grace,
+    /// Beyond the grace period, anyone can buy it
+    Expired,
+    /// Never registered, or released
+    Unregistered,
+}
+
+impl LifecycleState {
+    /// Lifecycle state of a domain record at `current_time`
+    pub fn of(domain_record: &DomainRecord, grace_period_seconds: i64, current_time: i64) -> Self {
+        if !domain_record.is_expired(current_time) {
+            LifecycleState::Active
+        } else if domain_record.is_in_grace_period(current_time, grace_period_seconds) {
+            LifecycleState::Grace
+        } else {
+            LifecycleState::Expired
+        }
+    }
+}
+
+impl fmt::Display for LifecycleState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            LifecycleState::Active => "active",
+            LifecycleState::Grace => "in grace period",
+            LifecycleState::Expired => "expired",
+            LifecycleState::Unregistered => "unregistered",
+        })
+    }
+}
+
+/// Result of resolving a name
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+pub struct Resolution {
+    /// Normalized domain name, without the .ca suffix
+    pub domain_name: String,
+
+    /// Lifecycle state at `resolved_at`
+    pub state: LifecycleState,
+
+    /// Owner, while the owner holds the name (active or in grace period)
+    #[serde(serialize_with = "serialize_optional_pubkey")]
+    pub owner: Option<Pubkey>,
+
+    /// Addresses the name resolves to, empty unless the name is resolving
+    #[serde(serialize_with = "serialize_addresses")]
+    pub addresses: Vec<ChainAddress>,
+
+    /// Whether the name is suspended by the dispute manager
+    pub suspended: bool,
+
+    /// Expiry of the registration, if registered
+    pub expiry_timestamp: Option<i64>,
+
+    /// End of the grace period, if registered
+    pub grace_period_end_timestamp: Option<i64>,
+
+    /// Cluster time the resolution was computed at
+    pub resolved_at: i64,
+}
+
+impl Resolution {
+    /// Resolve a name from its domain record, the program grace period and the cluster time
+    pub fn new(
+        domain_name: &str,
+        domain_record: Option<&DomainRecord>,
+        grace_period_seconds: i64,
+        current_time: i64,
+    ) -> Self {
+        let Some(domain_record) = domain_record else {
+            return Self {
+                domain_name: domain_name.to_string(),
+                state: LifecycleState::Unregistered,
+                owner: None,
+                addresses: Vec::new(),
+                suspended: false,
+                expiry_timestamp: None,
+                grace_period_end_timestamp: None,
+                resolved_at: current_time,
+            };
+        };
+
+        let state = LifecycleState::of(domain_record, grace_period_seconds, current_time);
+        let resolving = state == LifecycleState::Active && !domain_record.suspended;
+
+        Self {
+            domain_name: domain_name.to_string(),
+            state,
+            owner: (state != LifecycleState::Expired).then_some(domain_record.owner),
+            addresses: if resolving {
+                domain_record.addresses.clone()
+            } else {
+                Vec::new()
+            },
+            suspended: domain_record.suspended,
+            expiry_timestamp: Some(domain_record.expiry_timestamp),
+            grace_period_end_timestamp: Some(
+                domain_record
+                    .expiry_timestamp
+                    .saturating_add(grace_period_seconds),
+            ),
+            resolved_at: current_time,
+        }
+    }
+
+    /// Whether the name resolves to its addresses, i.e. it is active and not suspended
+    pub fn is_resolving(&self) -> bool {
+        self.state == LifecycleState::Active && !self.suspended
+    }
+
+    /// Address of the name on a chain, if resolving
+    pub fn address(&self, chain_id: u8) -> Option<&str> {
+        self.addresses
+            .iter()
+            .find(|address| address.chain_id == chain_id)
+            .map(|address| address.address.as_str())
+    }
+
+    /// Cluster time at which the lifecycle state changes next, if it can change without a transaction
+    pub fn next_state_change(&self) -> Option<i64> {
+        match self.state {
+            LifecycleState::Active => self.expiry_timestamp.map(|expiry| expiry.saturating_add(1)),
+            LifecycleState::Grace => self
+                .grace_period_end_timestamp
+                .map(|end| end.saturating_add(1)),
+            LifecycleState::Expired | LifecycleState::Unregistered => None,
+        }
+    }
+}
+
+/// Accounts to fetch, in order, to resolve `domain_names`: the program state, the clock sysvar, then the domain
+/// record of each name
+pub fn resolution_accounts(domain_names: &[String]) -> Vec<Pubkey> {
+    [find_program_state_address().0, sysvar::clock::ID]
+        .into_iter()
+        .chain(
+            domain_names
+                .iter()
+                .map(|name| find_domain_record_address(name).0),
+        )
+        .collect()
+}
+
+/// Resolve `domain_names` from the data of the accounts listed by [`resolution_accounts`], `None` for accounts
+/// that do not exist
+pub fn resolve_accounts(
+    domain_names: &[String],
+    accounts: &[Option<Vec<u8>>],
+) -> Result<Vec<Resolution>, ResolveError> {
+    let addresses = resolution_accounts(domain_names);
+    if accounts.len() != addresses.len() {
+        return Err(ResolveError::AccountCount {
+            expected: addresses.len(),
+            actual: accounts.len(),
+        });
+    }
+
+    let program_state: ProgramState = accounts[0]
+        .as_deref()
+        .map(|data| decode_account(&addresses[0], data, "program state"))
+        .transpose()?
+        .ok_or(ResolveError::NotInitialized)?;
+    let current_time = accounts[1]
+        .as_deref()
+        .map(decode_clock_timestamp)
+        .ok_or(ResolveError::MissingClock)??;
+
+    domain_names
+        .iter()
+        .zip(addresses.iter().zip(accounts).skip(2))
+        .map(|(domain_name, (address, data))| {
+            let domain_record: Option<DomainRecord> = data
+                .as_deref()
+                .map(|data| decode_account(address, data, "domain record"))
+                .transpose()?;
+            Ok(Resolution::new(
+                domain_name,
+                domain_record.as_ref(),
+                program_state.grace_period_seconds,
+                current_time,
+            ))
+        })
+        .collect()
+}
+
+/// Deserialize an account of the program
+pub fn decode_account<T: AccountDeserialize>(
+    address: &Pubkey,
+    mut data: &[u8],
+    account_type: &'static str,
+) -> Result<T, ResolveError> {
+    T::try_deserialize(&mut data).map_err(|_| ResolveError::InvalidAccount {
+        address: *address,
+        account_type,
+    })
+}
+
+/// Read the Unix timestamp of the clock sysvar data
+pub fn decode_clock_timestamp(data: &[u8]) -> Result<i64, ResolveError> {
+    data.get(CLOCK_UNIX_TIMESTAMP_OFFSET..CLOCK_UNIX_TIMESTAMP_OFFSET + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(i64::from_le_bytes)
+        .ok_or(ResolveError::InvalidAccount {
+            address: sysvar::clock::ID,
+            account_type: "clock sysvar",
+        })
+}
+
+fn serialize_optional_pubkey<S: Serializer>(
+    pubkey: &Option<Pubkey>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    pubkey
+        .map(|pubkey| pubkey.to_string())
+        .serialize(serializer)
+}
+
+fn serialize_addresses<S: Serializer>(
+    addresses: &[ChainAddress],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Address<'a> {
+        chain_id: u8,
+        address: &'a str,
+    }
+
+    serializer.collect_seq(addresses.iter().map(|address| Address {
+        chain_id: address.chain_id,
+        address: &address.address,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+    const GRACE_PERIOD: i64 = 30 * 86_400;
+
+    fn domain_record(expiry_timestamp: i64, suspended: bool) -> DomainRecord {
+        DomainRecord {
+            domain_name: "alice".to_string(),
+            owner: Pubkey::new_unique(),
+            expiry_timestamp,
+            registration_timestamp: expiry_timestamp - 365 * 86_400,
+            registration_generation: 0,
+            addresses: vec![ChainAddress {
+                chain_id: 0,
+                address: "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU".to_string(),
+            }],
+            bump: 255,
+            suspended,
+            fuses: 0,
+            paid_lamports: 0,
+            paid_timestamp: 0,
+        }
+    }
+
+    fn account_data<T: anchor_lang::AccountSerialize>(account: &T) -> Option<Vec<u8>> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        Some(data)
+    }
+
+    fn clock_data(unix_timestamp: i64) -> Option<Vec<u8>> {
+        // Slot, epoch start timestamp, epoch and leader schedule epoch come first
+        let mut data = vec![0; CLOCK_UNIX_TIMESTAMP_OFFSET];
+        data.extend_from_slice(&unix_timestamp.to_le_bytes());
+        Some(data)
+    }
+
+    #[test]
+    fn names_are_normalized_to_the_registrable_spelling() {
+        assert_eq!(normalize_domain_name("alice"), "alice");
+        assert_eq!(normalize_domain_name("alice.ca"), "alice");
+        assert_eq!(normalize_domain_name(" Alice.CA. "), "alice");
+        assert_eq!(normalize_domain_name("pay.alice.ca"), "pay.alice");
+        assert_eq!(normalize_domain_name(".ca"), "");
+    }
+
+    #[test]
+    fn lifecycle_follows_expiry_and_grace_period() {
+        let record = domain_record(NOW, false);
+
+        // The expiry second itself is still active, like in the program
+        let active = Resolution::new("alice", Some(&record), GRACE_PERIOD, NOW);
+        assert_eq!(active.state, LifecycleState::Active);
+        assert!(active.is_resolving());
+        assert_eq!(active.owner, Some(record.owner));
+        assert_eq!(
+            active.address(0),
+            Some("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU")
+        );
+        assert_eq!(active.next_state_change(), Some(NOW + 1));
+
+        // In the grace period the owner keeps the name but it no longer resolves
+        for current_time in [NOW + 1, NOW + GRACE_PERIOD] {
+            let grace = Resolution::new("alice", Some(&record), GRACE_PERIOD, current_time);
+            assert_eq!(grace.state, LifecycleState::Grace);
+            assert!(!grace.is_resolving());
+            assert!(grace.addresses.is_empty());
+            assert_eq!(grace.owner, Some(record.owner));
+            assert_eq!(grace.next_state_change(), Some(NOW + GRACE_PERIOD + 1));
+        }
+
+        // Beyond the grace period anyone can buy it
+        let expired = Resolution::new("alice", Some(&record), GRACE_PERIOD, NOW + GRACE_PERIOD + 1);
+        assert_eq!(expired.state, LifecycleState::Expired);
+        assert!(expired.addresses.is_empty());
+        assert_eq!(expired.owner, None);
+        assert_eq!(expired.grace_period_end_timestamp, Some(NOW + GRACE_PERIOD));
+        assert_eq!(expired.next_state_change(), None);
+    }
+
+    #[test]
+    fn suspended_names_keep_their_state_but_do_not_resolve() {
+        let record = domain_record(NOW + 86_400, true);
+        let resolution = Resolution::new("alice", Some(&record), GRACE_PERIOD, NOW);
+
+        assert_eq!(resolution.state, LifecycleState::Active);
+        assert!(resolution.suspended);
+        assert!(!resolution.is_resolving());
+        assert!(resolution.addresses.is_empty());
+        assert_eq!(resolution.owner, Some(record.owner));
+    }
+
+    #[test]
+    fn unregistered_names_have_nothing() {
+        let resolution = Resolution::new("bob", None, GRACE_PERIOD, NOW);
+
+        assert_eq!(resolution.state, LifecycleState::Unregistered);
+        assert_eq!(resolution.owner, None);
+        assert_eq!(resolution.expiry_timestamp, None);
+        assert_eq!(resolution.next_state_change(), None);
+        assert_eq!(resolution.resolved_at, NOW);
+    }
+
+    #[test]
+    fn accounts_resolve_in_the_order_of_the_names() {
+        let program_state = ProgramState {
+            base_price_usd: 500,
+            domains_registered: 2,
+            grace_period_seconds: GRACE_PERIOD,
+            paused: false,
+            timelock_delay_seconds: 0,
+            queued_changes_count: 0,
+            config_bounds: ConfigBounds {
+                min_base_price_usd: 0,
+                max_base_price_usd: u64::MAX,
+                min_grace_period_seconds: 0,
+                max_grace_period_seconds: i64::MAX,
+            },
+            fee_destinations: Vec::new(),
+            default_referral_share_bps: 0,
+            sunrise_merkle_root: [0; 32],
+            general_availability_timestamp: 0,
+            airdrop_merkle_root: [0; 32],
+            dispute_timelock_enabled: false,
+            release_refund_rate_bps: 0,
+            max_registration_horizon_seconds: i64::MAX,
+            min_registration_seconds: 0,
+            subdomain_protocol_fee_bps: 0,
+            registration_generations: 2,
+            bump: 255,
+        };
+        let domain_names = ["carol", "alice", "bob"].map(String::from);
+        let mut carol = domain_record(NOW - 1, false);
+        carol.domain_name = "carol".to_string();
+        let accounts = [
+            account_data(&program_state),
+            clock_data(NOW),
+            account_data(&carol),
+            account_data(&domain_record(NOW + 1, false)),
+            None,
+        ];
+
+        let addresses = resolution_accounts(&domain_names);
+        assert_eq!(addresses[0], find_program_state_address().0);
+        assert_eq!(addresses[1], sysvar::clock::ID);
+        assert_eq!(addresses[4], find_domain_record_address("bob").0);
+
+        let resolutions = resolve_accounts(&domain_names, &accounts).unwrap();
+        let states: Vec<_> = resolutions
+            .iter()
+            .map(|resolution| (resolution.domain_name.as_str(), resolution.state))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                ("carol", LifecycleState::Grace),
+                ("alice", LifecycleState::Active),
+                ("bob", LifecycleState::Unregistered),
+            ]
+        );
+
+        assert!(matches!(
+            resolve_accounts(&domain_names, &accounts[..4]),
+            Err(ResolveError::AccountCount {
+                expected: 5,
+                actual: 4
+            })
+        ));
+        let mut uninitialized = accounts.clone();
+        uninitialized[0] = None;
+        assert!(matches!(
+            resolve_accounts(&domain_names, &uninitialized),
+            Err(ResolveError::NotInitialized)
+        ));
+        let mut without_clock = accounts.clone();
+        without_clock[1] = None;
+        assert!(matches!(
+            resolve_accounts(&domain_names, &without_clock),
+            Err(ResolveError::MissingClock)
+        ));
+    }
+}
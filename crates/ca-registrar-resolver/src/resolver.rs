@@ -0,0 +1,338 @@
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::cache::ResolutionCache;
+use crate::error::ResolveError;
+use crate::resolution::*;
+
+/// Maximum number of accounts per `getMultipleAccounts` request
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Source of account data, e.g. an RPC endpoint or a local snapshot
+pub trait AccountSource {
+    /// Data of each account in `addresses`, in order, `None` for accounts that do not exist
+    fn get_multiple_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, ResolveError>;
+
+    /// Address and data of every account of the program whose data starts with `discriminator`
+    fn get_program_accounts(
+        &self,
+        discriminator: &[u8],
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>, ResolveError>;
+}
+
+impl<S: AccountSource + ?Sized> AccountSource for Box<S> {
+    fn get_multiple_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, ResolveError> {
+        (**self).get_multiple_accounts(addresses)
+    }
+
+    fn get_program_accounts(
+        &self,
+        discriminator: &[u8],
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>, ResolveError> {
+        (**self).get_program_accounts(discriminator)
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl AccountSource for solana_rpc_client::rpc_client::RpcClient {
+    fn get_multiple_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, ResolveError> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let chunk_accounts = self
+                .get_multiple_accounts(chunk)
+                .map_err(|error| ResolveError::Source(error.to_string()))?;
+            accounts.extend(
+                chunk_accounts
+                    .into_iter()
+                    .map(|account| account.map(|account| account.data)),
+            );
+        }
+
+        Ok(accounts)
+    }
+
+    fn get_program_accounts(
+        &self,
+        discriminator: &[u8],
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>, ResolveError> {
+        use solana_account_decoder::UiAccountEncoding;
+        use solana_rpc_client_api::config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+        use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                0,
+                discriminator,
+            ))]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        let accounts = self
+            .get_program_accounts_with_config(&ca_registrar_client::ID, config)
+            .map_err(|error| ResolveError::Source(error.to_string()))?;
+        Ok(accounts
+            .into_iter()
+            .map(|(address, account)| (address, account.data))
+            .collect())
+    }
+}
+
+/// Resolves names through an account source, caching the resolutions
+pub struct Resolver<S> {
+    source: S,
+    cache: Mutex<ResolutionCache>,
+}
+
+impl<S: AccountSource> Resolver<S> {
+    /// Create a resolver caching resolutions for `ttl`, a zero TTL disables caching
+    pub fn new(source: S, ttl: Duration) -> Self {
+        Self {
+            source,
+            cache: Mutex::new(ResolutionCache::new(ttl)),
+        }
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Resolve a name, with or without the .ca suffix
+    pub fn resolve(&self, name: &str) -> Result<Resolution, ResolveError> {
+        let mut resolutions = self.resolve_many(&[name])?;
+        Ok(resolutions.remove(0))
+    }
+
+    /// Resolve several names, fetching those not cached in one batch
+    pub fn resolve_many<N: AsRef<str>>(
+        &self,
+        names: &[N],
+    ) -> Result<Vec<Resolution>, ResolveError> {
+        let domain_names: Vec<String> = names
+            .iter()
+            .map(|name| normalize_domain_name(name.as_ref()))
+            .collect();
+        let now_ms = now_ms();
+
+        let mut resolutions: Vec<Option<Resolution>> = {
+            let cache = self
+                .cache
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            domain_names
+                .iter()
+                .map(|domain_name| cache.get(domain_name, now_ms).cloned())
+                .collect()
+        };
+
+        let missing: Vec<String> = domain_names
+            .iter()
+            .zip(&resolutions)
+            .filter(|(_, resolution)| resolution.is_none())
+            .map(|(domain_name, _)| domain_name.clone())
+            .collect();
+        if !missing.is_empty() {
+            let accounts = self
+                .source
+                .get_multiple_accounts(&resolution_accounts(&missing))?;
+            let mut fetched = resolve_accounts(&missing, &accounts)?.into_iter();
+
+            let mut cache = self
+                .cache
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            for resolution in resolutions
+                .iter_mut()
+                .filter(|resolution| resolution.is_none())
+            {
+                let fetched_resolution = fetched.next().expect("one resolution per missing name");
+                cache.insert(fetched_resolution.clone(), now_ms);
+                *resolution = Some(fetched_resolution);
+            }
+        }
+
+        Ok(resolutions.into_iter().flatten().collect())
+    }
+
+    /// Drop the cached resolution of a name
+    pub fn invalidate(&self, name: &str) {
+        self.cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&normalize_domain_name(name));
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::sysvar;
+    use anchor_lang::AccountSerialize;
+    use ca_registrar_client::pda::*;
+    use ca_registrar_client::*;
+    use std::collections::HashMap;
+
+    const NOW: i64 = 1_700_000_000;
+
+    /// Accounts in memory, recording the names of each `getMultipleAccounts` request
+    #[derive(Default)]
+    struct MemorySource {
+        accounts: HashMap<Pubkey, Vec<u8>>,
+        requests: Mutex<Vec<usize>>,
+    }
+
+    impl MemorySource {
+        fn insert<T: AccountSerialize>(&mut self, address: Pubkey, account: &T) {
+            let mut data = Vec::new();
+            account.try_serialize(&mut data).unwrap();
+            self.accounts.insert(address, data);
+        }
+    }
+
+    impl AccountSource for MemorySource {
+        fn get_multiple_accounts(
+            &self,
+            addresses: &[Pubkey],
+        ) -> Result<Vec<Option<Vec<u8>>>, ResolveError> {
+            self.requests.lock().unwrap().push(addresses.len() - 2);
+            Ok(addresses
+                .iter()
+                .map(|address| self.accounts.get(address).cloned())
+                .collect())
+        }
+
+        fn get_program_accounts(
+            &self,
+            _discriminator: &[u8],
+        ) -> Result<Vec<(Pubkey, Vec<u8>)>, ResolveError> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn source() -> MemorySource {
+        let mut source = MemorySource::default();
+        source.insert(
+            find_program_state_address().0,
+            &ProgramState {
+                base_price_usd: 500,
+                domains_registered: 2,
+                grace_period_seconds: 30 * 86_400,
+                paused: false,
+                timelock_delay_seconds: 0,
+                queued_changes_count: 0,
+                config_bounds: ConfigBounds {
+                    min_base_price_usd: 0,
+                    max_base_price_usd: u64::MAX,
+                    min_grace_period_seconds: 0,
+                    max_grace_period_seconds: i64::MAX,
+                },
+                fee_destinations: Vec::new(),
+                default_referral_share_bps: 0,
+                sunrise_merkle_root: [0; 32],
+                general_availability_timestamp: 0,
+                airdrop_merkle_root: [0; 32],
+                dispute_timelock_enabled: false,
+                release_refund_rate_bps: 0,
+                max_registration_horizon_seconds: i64::MAX,
+                min_registration_seconds: 0,
+                subdomain_protocol_fee_bps: 0,
+                registration_generations: 2,
+                bump: 255,
+            },
+        );
+
+        let mut clock = vec![0; 32];
+        clock.extend_from_slice(&NOW.to_le_bytes());
+        source.accounts.insert(sysvar::clock::ID, clock);
+
+        for (domain_name, expiry_timestamp) in [("alice", NOW + 86_400), ("carol", NOW - 1)] {
+            source.insert(
+                find_domain_record_address(domain_name).0,
+                &DomainRecord {
+                    domain_name: domain_name.to_string(),
+                    owner: Pubkey::new_unique(),
+                    expiry_timestamp,
+                    registration_timestamp: NOW - 86_400,
+                    registration_generation: 0,
+                    addresses: Vec::new(),
+                    bump: 255,
+                    suspended: false,
+                    fuses: 0,
+                    paid_lamports: 0,
+                    paid_timestamp: 0,
+                },
+            );
+        }
+        source
+    }
+
+    fn summary(resolutions: &[Resolution]) -> Vec<(&str, LifecycleState)> {
+        resolutions
+            .iter()
+            .map(|resolution| (resolution.domain_name.as_str(), resolution.state))
+            .collect()
+    }
+
+    #[test]
+    fn batch_keeps_the_order_of_the_names() {
+        let resolver = Resolver::new(source(), Duration::from_secs(60));
+
+        let resolutions = resolver
+            .resolve_many(&["Carol.ca", "bob", "alice", "carol"])
+            .unwrap();
+        assert_eq!(
+            summary(&resolutions),
+            [
+                ("carol", LifecycleState::Grace),
+                ("bob", LifecycleState::Unregistered),
+                ("alice", LifecycleState::Active),
+                ("carol", LifecycleState::Grace),
+            ]
+        );
+        assert_eq!(*resolver.source().requests.lock().unwrap(), [4]);
+    }
+
+    #[test]
+    fn cached_names_are_not_fetched_again() {
+        let resolver = Resolver::new(source(), Duration::from_secs(60));
+        resolver.resolve("alice").unwrap();
+
+        // Only bob is fetched, and the cached alice keeps her place
+        let resolutions = resolver.resolve_many(&["bob", "alice.ca", "dave"]).unwrap();
+        assert_eq!(
+            summary(&resolutions),
+            [
+                ("bob", LifecycleState::Unregistered),
+                ("alice", LifecycleState::Active),
+                ("dave", LifecycleState::Unregistered),
+            ]
+        );
+        assert_eq!(*resolver.source().requests.lock().unwrap(), [1, 2]);
+
+        // Invalidated names are fetched again
+        resolver.invalidate("Alice");
+        resolver.resolve("alice").unwrap();
+        assert_eq!(*resolver.source().requests.lock().unwrap(), [1, 2, 1]);
+    }
+}
@@ -0,0 +1,224 @@
+//! Account snapshots, for resolving without network access
+//!
+//! A snapshot is a JSON file listing accounts with their owner and base64 data:
+//!
+//! ```json
+//! { "accounts": [{ "pubkey": "...", "owner": "...", "data": "..." }] }
+//! ```
+//!
+//! The clock sysvar of a snapshot is served advanced by the local time elapsed since the snapshot was created or
+//! loaded, so names move through their lifecycle while a snapshot is served. When the clock sysvar is not part of the
+//! snapshot, the local system time is used instead.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::sysvar;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ca_registrar_client::ID;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ResolveError;
+use crate::resolution::CLOCK_UNIX_TIMESTAMP_OFFSET;
+use crate::resolver::AccountSource;
+
+/// Size of the clock sysvar data
+const CLOCK_DATA_LEN: usize = 40;
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotFile {
+    accounts: Vec<SnapshotAccount>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotAccount {
+    pubkey: String,
+    owner: String,
+    data: String,
+}
+
+struct AccountData {
+    owner: Pubkey,
+    data: Vec<u8>,
+}
+
+/// In-memory set of accounts, loaded from or saved to a snapshot file
+pub struct Snapshot {
+    accounts: BTreeMap<Pubkey, AccountData>,
+    created_at: Instant,
+}
+
+impl Default for Snapshot {
+    fn default() -> Self {
+        Self {
+            accounts: BTreeMap::new(),
+            created_at: Instant::now(),
+        }
+    }
+}
+
+impl Snapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace an account
+    pub fn insert(&mut self, address: Pubkey, owner: Pubkey, data: Vec<u8>) {
+        self.accounts.insert(address, AccountData { owner, data });
+    }
+
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// Parse a snapshot from its JSON representation
+    pub fn from_json(json: &str) -> Result<Self, ResolveError> {
+        let file: SnapshotFile = serde_json::from_str(json)
+            .map_err(|error| ResolveError::Snapshot(error.to_string()))?;
+
+        let mut snapshot = Self::new();
+        for account in file.accounts {
+            let address = parse_pubkey(&account.pubkey)?;
+            let owner = parse_pubkey(&account.owner)?;
+            let data = STANDARD.decode(&account.data).map_err(|error| {
+                ResolveError::Snapshot(format!("invalid data of account {address}: {error}"))
+            })?;
+            snapshot.insert(address, owner, data);
+        }
+
+        Ok(snapshot)
+    }
+
+    /// JSON representation of the snapshot, accounts sorted by address
+    pub fn to_json(&self) -> String {
+        let file = SnapshotFile {
+            accounts: self
+                .accounts
+                .iter()
+                .map(|(address, account)| SnapshotAccount {
+                    pubkey: address.to_string(),
+                    owner: account.owner.to_string(),
+                    data: STANDARD.encode(&account.data),
+                })
+                .collect(),
+        };
+
+        serde_json::to_string_pretty(&file).expect("snapshots always serialize")
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ResolveError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|error| {
+            ResolveError::Snapshot(format!("failed to read {}: {error}", path.display()))
+        })?;
+        Self::from_json(&json)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ResolveError> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_json()).map_err(|error| {
+            ResolveError::Snapshot(format!("failed to write {}: {error}", path.display()))
+        })
+    }
+
+    /// Capture every account of the program, the clock sysvar and `extra_accounts` (e.g. the SOL/USD price feed)
+    #[cfg(feature = "rpc")]
+    pub fn capture(
+        rpc: &solana_rpc_client::rpc_client::RpcClient,
+        extra_accounts: &[Pubkey],
+    ) -> Result<Self, ResolveError> {
+        let mut snapshot = Self::new();
+
+        let program_accounts = rpc
+            .get_program_accounts(&ID)
+            .map_err(|error| ResolveError::Source(error.to_string()))?;
+        for (address, account) in program_accounts {
+            snapshot.insert(address, account.owner, account.data);
+        }
+
+        let addresses: Vec<Pubkey> = [sysvar::clock::ID]
+            .iter()
+            .chain(extra_accounts)
+            .copied()
+            .collect();
+        let accounts = rpc
+            .get_multiple_accounts(&addresses)
+            .map_err(|error| ResolveError::Source(error.to_string()))?;
+        for (address, account) in addresses.into_iter().zip(accounts) {
+            if let Some(account) = account {
+                snapshot.insert(address, account.owner, account.data);
+            }
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Captured clock sysvar data, its timestamp advanced by the local time elapsed since the snapshot was created
+    fn advanced_clock_data(&self, data: &[u8]) -> Vec<u8> {
+        let mut data = data.to_vec();
+        let elapsed = i64::try_from(self.created_at.elapsed().as_secs()).unwrap_or(i64::MAX);
+        if let Some(bytes) =
+            data.get_mut(CLOCK_UNIX_TIMESTAMP_OFFSET..CLOCK_UNIX_TIMESTAMP_OFFSET + 8)
+        {
+            let unix_timestamp = i64::from_le_bytes(bytes.try_into().expect("8 bytes"));
+            bytes.copy_from_slice(&unix_timestamp.saturating_add(elapsed).to_le_bytes());
+        }
+        data
+    }
+}
+
+impl AccountSource for Snapshot {
+    fn get_multiple_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, ResolveError> {
+        Ok(addresses
+            .iter()
+            .map(|address| match self.accounts.get(address) {
+                Some(account) if *address == sysvar::clock::ID => {
+                    Some(self.advanced_clock_data(&account.data))
+                }
+                Some(account) => Some(account.data.clone()),
+                None if *address == sysvar::clock::ID => Some(system_clock_data()),
+                None => None,
+            })
+            .collect())
+    }
+
+    fn get_program_accounts(
+        &self,
+        discriminator: &[u8],
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>, ResolveError> {
+        Ok(self
+            .accounts
+            .iter()
+            .filter(|(_, account)| account.owner == ID && account.data.starts_with(discriminator))
+            .map(|(address, account)| (*address, account.data.clone()))
+            .collect())
+    }
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey, ResolveError> {
+    Pubkey::from_str(value)
+        .map_err(|_| ResolveError::Snapshot(format!("invalid public key {value:?}")))
+}
+
+/// Clock sysvar data holding the local system time
+fn system_clock_data() -> Vec<u8> {
+    let unix_timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX))
+        .unwrap_or(0);
+
+    let mut data = vec![0; CLOCK_DATA_LEN];
+    data[CLOCK_UNIX_TIMESTAMP_OFFSET..].copy_from_slice(&unix_timestamp.to_le_bytes());
+    data
+}
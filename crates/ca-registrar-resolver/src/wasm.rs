@@ -0,0 +1,119 @@
+//! JavaScript bindings
+//!
+//! The caller fetches the accounts listed by `resolutionAccounts` with `getMultipleAccountsInfo` and hands their
+//! data to `Resolver.resolveAccounts`:
+//!
+//! ```js
+//! const resolver = new Resolver(60);
+//! const names = resolver.missing(["alice.ca", "bob"]);
+//! const keys = resolutionAccounts(names).map((key) => new PublicKey(key));
+//! const infos = await connection.getMultipleAccountsInfo(keys);
+//! resolver.resolveAccounts(names, infos.map((info) => info?.data ?? null));
+//! const alice = resolver.cached("alice.ca");
+//! ```
+
+use ca_registrar_client::pda::find_domain_record_address;
+use js_sys::{Array, Date, Uint8Array};
+use serde::Serialize;
+use std::time::Duration;
+use wasm_bindgen::prelude::*;
+
+use crate::cache::ResolutionCache;
+use crate::resolution::{self, normalize_domain_name};
+
+/// Address of the domain record of a name, base58 encoded
+#[wasm_bindgen(js_name = domainRecordAddress)]
+pub fn domain_record_address(name: &str) -> String {
+    find_domain_record_address(&normalize_domain_name(name))
+        .0
+        .to_string()
+}
+
+/// Accounts to fetch, in order, to resolve `names`, base58 encoded
+#[wasm_bindgen(js_name = resolutionAccounts)]
+pub fn resolution_accounts(names: Vec<String>) -> Vec<String> {
+    let domain_names: Vec<String> = names
+        .iter()
+        .map(|name| normalize_domain_name(name))
+        .collect();
+    resolution::resolution_accounts(&domain_names)
+        .iter()
+        .map(|address| address.to_string())
+        .collect()
+}
+
+/// Resolver caching resolutions made from caller-fetched accounts
+#[wasm_bindgen(js_name = Resolver)]
+pub struct WasmResolver {
+    cache: ResolutionCache,
+}
+
+#[wasm_bindgen(js_class = Resolver)]
+impl WasmResolver {
+    /// Create a resolver caching resolutions for `ttl_seconds`
+    #[wasm_bindgen(constructor)]
+    pub fn new(ttl_seconds: u32) -> Self {
+        Self {
+            cache: ResolutionCache::new(Duration::from_secs(ttl_seconds.into())),
+        }
+    }
+
+    /// Names whose resolution is not cached, normalized
+    pub fn missing(&self, names: Vec<String>) -> Vec<String> {
+        let now_ms = now_ms();
+        names
+            .iter()
+            .map(|name| normalize_domain_name(name))
+            .filter(|domain_name| self.cache.get(domain_name, now_ms).is_none())
+            .collect()
+    }
+
+    /// Cached resolution of a name, or `undefined`
+    pub fn cached(&self, name: &str) -> Result<JsValue, JsError> {
+        match self.cache.get(&normalize_domain_name(name), now_ms()) {
+            Some(resolution) => to_js(resolution),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+
+    /// Resolve `names` from the data of the accounts listed by `resolutionAccounts(names)`, `null` for accounts
+    /// that do not exist, and cache the resolutions
+    #[wasm_bindgen(js_name = resolveAccounts)]
+    pub fn resolve_accounts(
+        &mut self,
+        names: Vec<String>,
+        accounts: Array,
+    ) -> Result<JsValue, JsError> {
+        let domain_names: Vec<String> = names
+            .iter()
+            .map(|name| normalize_domain_name(name))
+            .collect();
+        let accounts: Vec<Option<Vec<u8>>> = accounts
+            .iter()
+            .map(|data| {
+                (!data.is_null() && !data.is_undefined()).then(|| Uint8Array::new(&data).to_vec())
+            })
+            .collect();
+
+        let resolutions = resolution::resolve_accounts(&domain_names, &accounts)?;
+        let now_ms = now_ms();
+        for resolution in &resolutions {
+            self.cache.insert(resolution.clone(), now_ms);
+        }
+
+        to_js(&resolutions)
+    }
+
+    /// Drop the cached resolution of a name
+    pub fn invalidate(&mut self, name: &str) {
+        self.cache.remove(&normalize_domain_name(name));
+    }
+}
+
+fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, JsError> {
+    Ok(value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+fn now_ms() -> u64 {
+    Date::now() as u64
+}
@@ -6,11 +6,49 @@
 
 pub const ANCHOR_DISCRIMINATOR: usize = 8;
 
+// Key allowed to initialize the program when built with the `fixed-initializer` feature,
+// read from the CA_REGISTRAR_INITIALIZER environment variable at build time.
+// Without the feature, only the program upgrade authority can initialize.
+#[cfg(feature = "fixed-initializer")]
+pub const FIXED_INITIALIZER: anchor_lang::prelude::Pubkey = anchor_lang::prelude::Pubkey::new_from_array(
+    ::solana_program::pubkey::Pubkey::from_str_const(env!("CA_REGISTRAR_INITIALIZER")).to_bytes()
+);
+
 // PDA Seeds
 pub const PROGRAM_STATE_SEED: &[u8] = b"state";
+pub const ADMIN_ROLES_SEED: &[u8] = b"admin_roles";
 pub const DOMAIN_RECORD_SEED: &[u8] = b"domain";
 pub const REVERSE_RECORD_SEED: &[u8] = b"reverse";
 pub const PRICE_CONFIG_SEED: &[u8] = b"price_config";
+pub const QUEUED_CHANGE_SEED: &[u8] = b"queued_change";
+pub const REFERRER_CONFIG_SEED: &[u8] = b"referrer";
+pub const PROMO_CODE_SEED: &[u8] = b"promo";
+pub const VOUCHER_NONCE_SEED: &[u8] = b"voucher_nonce";
+pub const RESERVED_NAME_SEED: &[u8] = b"reserved";
+pub const CLAIMED_LEAF_SEED: &[u8] = b"claimed_leaf";
+pub const SUBDOMAIN_RECORD_SEED: &[u8] = b"subdomain";
+pub const SUBDOMAIN_POLICY_SEED: &[u8] = b"subdomain_policy";
+pub const WILDCARD_RECORD_SEED: &[u8] = b"wildcard";
+
+// Permission fuses, burned bits can never be cleared and apply until the record expires
+pub const FUSE_CANNOT_TRANSFER: u32 = 1 << 0;
+pub const FUSE_CANNOT_SET_ADDRESSES: u32 = 1 << 1;
+pub const FUSE_CANNOT_CREATE_SUBDOMAIN: u32 = 1 << 2;
+pub const FUSE_PARENT_CANNOT_CONTROL: u32 = 1 << 3;
+pub const FUSE_CANNOT_RELEASE: u32 = 1 << 4;
+
+// Fuses that can be burned on domains, by their owner
+pub const DOMAIN_FUSES: u32 =
+    FUSE_CANNOT_TRANSFER | FUSE_CANNOT_SET_ADDRESSES | FUSE_CANNOT_CREATE_SUBDOMAIN | FUSE_CANNOT_RELEASE;
+
+// Fuses that can be burned on subdomains by their owner, PARENT_CANNOT_CONTROL is burned by the parent owner
+pub const SUBDOMAIN_OWNER_FUSES: u32 = FUSE_CANNOT_TRANSFER | FUSE_CANNOT_SET_ADDRESSES;
+
+// Maximum subdomain label length, the DNS label limit
+pub const MAX_SUBDOMAIN_LABEL_LENGTH: usize = 63;
+
+// Prefix of the message signed by the voucher signer, followed by the program id and the Borsh-encoded voucher
+pub const VOUCHER_MESSAGE_PREFIX: &[u8] = b"ca-registrar-voucher";
 
 
 // Pyth price feed IDs
@@ -23,6 +61,53 @@ pub const PYTH_PRICE_FEED_MAX_AGE: u64 = 60; // 60 seconds
 // Price precision adjustment factor
 pub const PRICE_FEED_DECIMALS_ADJUSTMENT: u128 = 10;
 
-// Seconds per year for domain registration
+// Domain separation prefixes for Merkle tree hashing
+pub const MERKLE_LEAF_PREFIX: &[u8] = &[0];
+pub const MERKLE_NODE_PREFIX: &[u8] = &[1];
+
+// Basis points denominator for fee shares and discounts
+pub const BASIS_POINTS_DENOMINATOR: u64 = 10_000; // 100%
+
+// Maximum number of fee split destinations
+pub const MAX_FEE_DESTINATIONS: usize = 5;
+
+// Maximum number of multi-year discount tiers
+pub const MAX_MULTI_YEAR_DISCOUNTS: usize = 5;
+
+// Maximum share of a registration fee that can be paid to a referrer
+pub const MAX_REFERRAL_SHARE_BPS: u16 = 5_000; // 50%
+
+// Seconds per year for domain registration, used to prorate prices and count full years
 pub const SECONDS_PER_YEAR: i64 = 31_536_000; // 365 * 24 * 60 * 60
 
+// Seconds per day, used for calendar arithmetic
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+// Default minimum registration duration, the shortest calendar month
+pub const DEFAULT_MIN_REGISTRATION_SECONDS: i64 = 28 * SECONDS_PER_DAY;
+
+// Hard limits for the minimum registration duration
+pub const MIN_REGISTRATION_SECONDS_LOWER_LIMIT: i64 = SECONDS_PER_DAY;
+pub const MIN_REGISTRATION_SECONDS_UPPER_LIMIT: i64 = SECONDS_PER_YEAR;
+
+// Default maximum registration horizon, how far ahead of now a domain can be registered or renewed to
+pub const DEFAULT_MAX_REGISTRATION_HORIZON_SECONDS: i64 = 10 * SECONDS_PER_YEAR;
+
+// Hard limits for the registration horizon, keeps expiry arithmetic far from overflow
+pub const MIN_REGISTRATION_HORIZON_SECONDS: i64 = SECONDS_PER_YEAR;
+pub const MAX_REGISTRATION_HORIZON_SECONDS: i64 = 100 * SECONDS_PER_YEAR;
+
+// Upper limit for the protocol share of paid subdomain registrations, the parent owner keeps most of the price
+pub const MAX_SUBDOMAIN_PROTOCOL_FEE_BPS: u16 = 2_000; // 20%
+
+// Upper limit for the admin timelock delay, so parameters can never be frozen
+pub const MAX_TIMELOCK_DELAY_SECONDS: i64 = 2_592_000; // 30 days
+
+// Default bounds for admin configuration values, adjustable with update_config_bounds
+pub const DEFAULT_MIN_BASE_PRICE_USD: u64 = 100; // $1.00
+pub const DEFAULT_MAX_BASE_PRICE_USD: u64 = 100_000; // $1,000.00
+pub const DEFAULT_MIN_GRACE_PERIOD_SECONDS: i64 = 86_400; // 1 day
+pub const DEFAULT_MAX_GRACE_PERIOD_SECONDS: i64 = 7_776_000; // 90 days
+
+// Hard limit for the grace period bounds, keeps expiry arithmetic far from overflow
+pub const MAX_GRACE_PERIOD_SECONDS_LIMIT: i64 = SECONDS_PER_YEAR;
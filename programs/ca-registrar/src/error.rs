@@ -11,7 +11,7 @@ pub enum CaRegistrarError {
     #[msg("Invalid domain name length")]
     InvalidDomainLength,
     
-    #[msg("Invalid number of years for registration")]
+    #[msg("Invalid registration period")]
     InvalidRegisterYears,
     
     #[msg("Only the domain owner can perform this action")]
@@ -40,4 +40,151 @@ pub enum CaRegistrarError {
     
     #[msg("Too many addresses. Maximum allowed is 20")]
     TooManyAddresses,
+    
+    #[msg("Signer does not hold the admin role required for this action")]
+    MissingAdminRole,
+    
+    #[msg("Program is paused")]
+    ProgramPaused,
+    
+    #[msg("Timelock delay must be between 0 and 30 days")]
+    InvalidTimelockDelay,
+    
+    #[msg("Queued change is not yet executable, timelock delay has not elapsed")]
+    TimelockNotElapsed,
+    
+    #[msg("Base price is outside the configured bounds")]
+    BasePriceOutOfBounds,
+    
+    #[msg("Grace period is outside the configured bounds")]
+    GracePeriodOutOfBounds,
+    
+    #[msg("Invalid configuration bounds")]
+    InvalidConfigBounds,
+    
+    #[msg("Only the program upgrade authority can initialize the program")]
+    NotProgramInitializer,
+    
+    #[msg("Invalid fee split. At most 5 destinations with non-zero shares adding up to at most 10000 basis points")]
+    InvalidFeeSplit,
+    
+    #[msg("Fee destination accounts do not match the configured fee split")]
+    FeeDestinationMismatch,
+    
+    #[msg("Referral share cannot exceed 5000 basis points")]
+    InvalidReferralShare,
+    
+    #[msg("Referrer account does not match the referrer config")]
+    InvalidReferrer,
+    
+    #[msg("Referrer is not active")]
+    ReferrerInactive,
+    
+    #[msg("Invalid discount. At most 5 tiers with at least 1 year and at most 10000 basis points")]
+    InvalidDiscount,
+    
+    #[msg("Promo code does not match the promo code account")]
+    InvalidPromoCode,
+    
+    #[msg("Promo code has expired")]
+    PromoCodeExpired,
+    
+    #[msg("Promo code has no remaining uses")]
+    PromoCodeExhausted,
+    
+    #[msg("Voucher signature is missing or not made by the voucher signer")]
+    InvalidVoucherSignature,
+    
+    #[msg("Voucher has expired")]
+    VoucherExpired,
+    
+    #[msg("Voucher is not valid for this domain name or registration period")]
+    VoucherNotApplicable,
+    
+    #[msg("Voucher was issued to another buyer")]
+    VoucherBuyerMismatch,
+    
+    #[msg("Domain name is reserved")]
+    DomainNameReserved,
+    
+    #[msg("Sunrise period is active, only allowlisted claims are accepted")]
+    SunriseActive,
+    
+    #[msg("Sunrise period has ended, use register_domain instead")]
+    SunriseEnded,
+    
+    #[msg("Invalid Merkle proof")]
+    InvalidMerkleProof,
+    
+    #[msg("Domain is suspended")]
+    DomainSuspended,
+    
+    #[msg("Dispute actions are timelocked and must be queued")]
+    DisputeTimelockEnabled,
+    
+    #[msg("Domain record does not match the queued change")]
+    DomainRecordMismatch,
+    
+    #[msg("Invalid refund rate. At most 10000 basis points")]
+    InvalidRefundRate,
+    
+    #[msg("Expiry would exceed the maximum registration horizon")]
+    RegistrationHorizonExceeded,
+    
+    #[msg("Invalid registration horizon. Must be between 1 and 100 years")]
+    InvalidRegistrationHorizon,
+    
+    #[msg("Registration period is shorter than the minimum registration duration")]
+    RegistrationPeriodTooShort,
+    
+    #[msg("Invalid minimum registration duration. Must be between 1 day and 1 year")]
+    InvalidMinRegistrationDuration,
+    
+    #[msg("Invalid subdomain label. Must be 1 to 63 characters without dots")]
+    InvalidSubdomainLabel,
+    
+    #[msg("Parent domain is expired, suspended or was registered again")]
+    ParentDomainInactive,
+    
+    #[msg("Only the subdomain owner can perform this action")]
+    NotSubdomainOwner,
+    
+    #[msg("Subdomain registration is closed for this domain")]
+    SubdomainIssuanceClosed,
+    
+    #[msg("Subdomain is already registered")]
+    SubdomainNotAvailable,
+    
+    #[msg("Subdomain would expire after its parent domain")]
+    SubdomainExceedsParentExpiry,
+    
+    #[msg("Payment accounts do not match the subdomain policy")]
+    InvalidPaymentAccounts,
+    
+    #[msg("Invalid protocol fee. At most 2000 basis points")]
+    InvalidProtocolFee,
+    
+    #[msg("Subdomain price rounds down to zero for this registration period")]
+    SubdomainPriceTooLow,
+    
+    #[msg("Subdomain was bought through the subdomain policy and has not expired")]
+    SubdomainRegistrationActive,
+    
+    #[msg("Operation is forbidden by a burned fuse")]
+    FuseBurned,
+    
+    #[msg("Invalid fuses for this record or signer")]
+    InvalidFuses,
+    
+    #[msg("CANNOT_RELEASE must be burned on the parent domain first")]
+    ParentReleasable,
+    
+    #[msg("Name does not resolve")]
+    NameNotResolvable,
+    
+    #[msg("Domain name must be lowercase")]
+    DomainNameNotLowercase,
+    
+    #[msg("Treasury balance cannot cover the release refund")]
+    InsufficientRefundFunds,
 }
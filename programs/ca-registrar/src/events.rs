@@ -0,0 +1,777 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// SOL/USD price a payment was converted at, as read from the Pyth price update (price * 10^exponent USD per SOL)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OraclePrice {
+    /// Price in units of 10^exponent USD
+    pub price: i64,
+    
+    /// Price exponent
+    pub exponent: i32,
+    
+    /// Publish time of the price update
+    pub publish_time: i64,
+}
+
+/// Emitted when the dispute manager suspends or reinstates a domain
+#[event]
+pub struct DomainSuspensionUpdated {
+    /// Domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// New suspension state
+    pub suspended: bool,
+    
+    /// Reason code of the dispute decision, defined off-chain
+    pub reason_code: u16,
+    
+    /// Admin who decided the action
+    pub authority: Pubkey,
+    
+    /// Timestamp when the action was applied
+    pub timestamp: i64,
+}
+
+/// Emitted when the dispute manager reassigns a domain
+#[event]
+pub struct DomainForceTransferred {
+    /// Domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Owner before the transfer
+    pub previous_owner: Pubkey,
+    
+    /// Owner after the transfer
+    pub new_owner: Pubkey,
+    
+    /// Reason code of the dispute decision, defined off-chain
+    pub reason_code: u16,
+    
+    /// Admin who decided the action
+    pub authority: Pubkey,
+    
+    /// Timestamp when the action was applied
+    pub timestamp: i64,
+}
+
+/// Emitted when a new domain record is created, by registration, voucher, sunrise or airdrop claim, or admin
+/// assignment of a reserved name
+#[event]
+pub struct DomainRegistered {
+    /// Domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Owner of the new registration
+    pub owner: Pubkey,
+    
+    /// Account that signed and paid for the registration
+    pub payer: Pubkey,
+    
+    /// Addresses set at registration
+    pub addresses: Vec<ChainAddress>,
+    
+    /// Price after discounts in USD cents, 0 for free registrations
+    pub price_usd: u64,
+    
+    /// Lamports paid by the payer, referral share included
+    pub lamports_paid: u64,
+    
+    /// Lamports of the payment sent to the referrer
+    pub referral_lamports: u64,
+    
+    /// SOL/USD price the USD price was converted at, None when nothing was charged in SOL
+    pub oracle_price: Option<OraclePrice>,
+    
+    /// Expiry timestamp of the new registration
+    pub expiry_timestamp: i64,
+    
+    /// Registration timestamp
+    pub timestamp: i64,
+}
+
+/// Emitted when a domain is renewed
+#[event]
+pub struct DomainRenewed {
+    /// Domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Account that signed and paid for the renewal
+    pub payer: Pubkey,
+    
+    /// Price after discounts in USD cents
+    pub price_usd: u64,
+    
+    /// Lamports paid by the payer, referral share included
+    pub lamports_paid: u64,
+    
+    /// Lamports of the payment sent to the referrer
+    pub referral_lamports: u64,
+    
+    /// SOL/USD price the USD price was converted at
+    pub oracle_price: OraclePrice,
+    
+    /// Expiry timestamp before the renewal
+    pub previous_expiry_timestamp: i64,
+    
+    /// Expiry timestamp after the renewal
+    pub new_expiry_timestamp: i64,
+    
+    /// Timestamp of the renewal
+    pub timestamp: i64,
+}
+
+/// Emitted when an expired domain is bought
+#[event]
+pub struct DomainBought {
+    /// Domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Owner of the expired registration
+    pub previous_owner: Pubkey,
+    
+    /// Owner of the new registration
+    pub new_owner: Pubkey,
+    
+    /// Account that signed and paid for the purchase
+    pub buyer: Pubkey,
+    
+    /// Addresses set at purchase
+    pub addresses: Vec<ChainAddress>,
+    
+    /// Price after discounts in USD cents
+    pub price_usd: u64,
+    
+    /// Lamports paid by the buyer, referral share included
+    pub lamports_paid: u64,
+    
+    /// Lamports of the payment sent to the referrer
+    pub referral_lamports: u64,
+    
+    /// SOL/USD price the USD price was converted at
+    pub oracle_price: OraclePrice,
+    
+    /// Expiry timestamp of the expired registration
+    pub previous_expiry_timestamp: i64,
+    
+    /// Expiry timestamp of the new registration
+    pub expiry_timestamp: i64,
+    
+    /// Timestamp of the purchase
+    pub timestamp: i64,
+}
+
+/// Emitted when an owner transfers a domain
+#[event]
+pub struct DomainTransferred {
+    /// Domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Owner before the transfer
+    pub previous_owner: Pubkey,
+    
+    /// Owner after the transfer
+    pub new_owner: Pubkey,
+    
+    /// Timestamp of the transfer
+    pub timestamp: i64,
+}
+
+/// Emitted when an owner replaces the addresses of a domain
+#[event]
+pub struct AddressesUpdated {
+    /// Domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Addresses before the update
+    pub previous_addresses: Vec<ChainAddress>,
+    
+    /// Addresses after the update
+    pub new_addresses: Vec<ChainAddress>,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Emitted when an owner releases a domain
+#[event]
+pub struct DomainReleased {
+    /// Domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Owner releasing the domain
+    pub owner: Pubkey,
+    
+    /// Lamports refunded to the owner
+    pub refund_lamports: u64,
+    
+    /// Timestamp of the release
+    pub timestamp: i64,
+}
+
+/// Emitted when an owner burns fuses on a domain
+#[event]
+pub struct FusesBurned {
+    /// Domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Fuses burned by this instruction
+    pub burned_fuses: u32,
+    
+    /// All fuses burned on the domain
+    pub fuses: u32,
+    
+    /// Timestamp of the burn
+    pub timestamp: i64,
+}
+
+/// Emitted when a parent owner issues a subdomain
+#[event]
+pub struct SubdomainCreated {
+    /// Parent domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Subdomain label
+    pub label: String,
+    
+    /// Owner of the subdomain
+    pub owner: Pubkey,
+    
+    /// Addresses set at creation
+    pub addresses: Vec<ChainAddress>,
+    
+    /// Timestamp of the creation
+    pub timestamp: i64,
+}
+
+/// Emitted when a subdomain is bought through the subdomain policy of its parent
+#[event]
+pub struct SubdomainRegistered {
+    /// Parent domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Subdomain label
+    pub label: String,
+    
+    /// Owner of the subdomain
+    pub owner: Pubkey,
+    
+    /// Account that signed and paid for the subdomain
+    pub buyer: Pubkey,
+    
+    /// Addresses set at registration
+    pub addresses: Vec<ChainAddress>,
+    
+    /// SPL token mint of the payment, None for SOL
+    pub payment_mint: Option<Pubkey>,
+    
+    /// Price paid in lamports or token base units
+    pub price: u64,
+    
+    /// Part of the price paid to the protocol
+    pub protocol_fee: u64,
+    
+    /// Expiry timestamp of the subdomain
+    pub expiry_timestamp: i64,
+    
+    /// Timestamp of the registration
+    pub timestamp: i64,
+}
+
+/// Emitted when a subdomain owner replaces the addresses of a subdomain
+#[event]
+pub struct SubdomainAddressesUpdated {
+    /// Parent domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Subdomain label
+    pub label: String,
+    
+    /// Addresses before the update
+    pub previous_addresses: Vec<ChainAddress>,
+    
+    /// Addresses after the update
+    pub new_addresses: Vec<ChainAddress>,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Emitted when a subdomain owner transfers a subdomain
+#[event]
+pub struct SubdomainTransferred {
+    /// Parent domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Subdomain label
+    pub label: String,
+    
+    /// Owner before the transfer
+    pub previous_owner: Pubkey,
+    
+    /// Owner after the transfer
+    pub new_owner: Pubkey,
+    
+    /// Timestamp of the transfer
+    pub timestamp: i64,
+}
+
+/// Emitted when a parent owner deletes a subdomain
+#[event]
+pub struct SubdomainDeleted {
+    /// Parent domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Subdomain label
+    pub label: String,
+    
+    /// Owner of the deleted subdomain
+    pub owner: Pubkey,
+    
+    /// Timestamp of the deletion
+    pub timestamp: i64,
+}
+
+/// Emitted when fuses are burned on a subdomain
+#[event]
+pub struct SubdomainFusesBurned {
+    /// Parent domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Subdomain label
+    pub label: String,
+    
+    /// Fuses burned by this instruction
+    pub burned_fuses: u32,
+    
+    /// All fuses burned on the subdomain
+    pub fuses: u32,
+    
+    /// Subdomain owner or parent owner who burned the fuses
+    pub authority: Pubkey,
+    
+    /// Timestamp of the burn
+    pub timestamp: i64,
+}
+
+/// Emitted when a parent owner sets its subdomain policy
+#[event]
+pub struct SubdomainPolicyUpdated {
+    /// Parent domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Who can register subdomains
+    pub mode: SubdomainIssuanceMode,
+    
+    /// SPL token mint used for payment, None for SOL
+    pub payment_mint: Option<Pubkey>,
+    
+    /// Price of a one-year subdomain in lamports or token base units
+    pub price_per_year: u64,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Emitted when a parent owner sets the wildcard record of a domain
+#[event]
+pub struct WildcardRecordUpdated {
+    /// Parent domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Addresses returned for subdomains without their own record
+    pub addresses: Vec<ChainAddress>,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Emitted when a parent owner deletes the wildcard record of a domain
+#[event]
+pub struct WildcardRecordDeleted {
+    /// Parent domain name without the .ca suffix
+    pub domain_name: String,
+    
+    /// Timestamp of the deletion
+    pub timestamp: i64,
+}
+
+/// Emitted when the program is initialized
+#[event]
+pub struct ProgramInitialized {
+    /// Initial holder of every admin role
+    pub authority: Pubkey,
+    
+    /// Base price in USD cents
+    pub base_price_usd: u64,
+    
+    /// Grace period in seconds
+    pub grace_period_seconds: i64,
+    
+    /// Timelock delay of queued changes in seconds
+    pub timelock_delay_seconds: i64,
+    
+    /// Timestamp of the initialization
+    pub timestamp: i64,
+}
+
+/// Emitted when the super-admin assigns an admin role
+#[event]
+pub struct RoleUpdated {
+    /// Role assigned
+    pub role: AdminRole,
+    
+    /// Holder before the change
+    pub previous_holder: Pubkey,
+    
+    /// Holder after the change
+    pub new_holder: Pubkey,
+    
+    /// Super-admin who assigned the role
+    pub authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when the pauser pauses or unpauses registrations
+#[event]
+pub struct PausedUpdated {
+    /// New pause state
+    pub paused: bool,
+    
+    /// Admin who changed the pause state
+    pub authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when a name is added to the reserved list
+#[event]
+pub struct ReservedNameAdded {
+    /// SHA-256 hash of the reserved name
+    pub name_hash: [u8; 32],
+    
+    /// Admin who reserved the name
+    pub authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when a name is removed from the reserved list
+#[event]
+pub struct ReservedNameRemoved {
+    /// SHA-256 hash of the reserved name
+    pub name_hash: [u8; 32],
+    
+    /// Admin who removed the name
+    pub authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when a promo code is created
+#[event]
+pub struct PromoCodeCreated {
+    /// SHA-256 hash of the promo code
+    pub code_hash: [u8; 32],
+    
+    /// Discount granted by the code
+    pub discount: PromoDiscount,
+    
+    /// Number of times the code can be redeemed
+    pub max_uses: u32,
+    
+    /// Expiry timestamp of the code
+    pub expiry_timestamp: i64,
+    
+    /// Admin who created the code
+    pub authority: Pubkey,
+    
+    /// Timestamp of the creation
+    pub timestamp: i64,
+}
+
+/// Emitted when a promo code is closed
+#[event]
+pub struct PromoCodeClosed {
+    /// SHA-256 hash of the promo code
+    pub code_hash: [u8; 32],
+    
+    /// Uses left when the code was closed
+    pub remaining_uses: u32,
+    
+    /// Admin who closed the code
+    pub authority: Pubkey,
+    
+    /// Timestamp of the closing
+    pub timestamp: i64,
+}
+
+/// Emitted when a referrer is registered
+#[event]
+pub struct ReferrerCreated {
+    /// Referrer wallet
+    pub referrer: Pubkey,
+    
+    /// Referral share overriding the default, in basis points
+    pub share_bps_override: Option<u16>,
+    
+    /// Admin who registered the referrer
+    pub authority: Pubkey,
+    
+    /// Timestamp of the creation
+    pub timestamp: i64,
+}
+
+/// Emitted when a referrer configuration is updated
+#[event]
+pub struct ReferrerUpdated {
+    /// Referrer wallet
+    pub referrer: Pubkey,
+    
+    /// Referral share overriding the default, in basis points
+    pub share_bps_override: Option<u16>,
+    
+    /// Whether the referrer earns referral shares
+    pub active: bool,
+    
+    /// Admin who updated the referrer
+    pub authority: Pubkey,
+    
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Emitted when the default referral share is updated
+#[event]
+pub struct DefaultReferralShareUpdated {
+    /// Share before the change in basis points
+    pub previous_share_bps: u16,
+    
+    /// Share after the change in basis points
+    pub new_share_bps: u16,
+    
+    /// Admin who changed the share
+    pub authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when the fee split of withdrawals is updated
+#[event]
+pub struct FeeSplitUpdated {
+    /// Fee destinations before the change
+    pub previous_fee_destinations: Vec<FeeDestination>,
+    
+    /// Fee destinations after the change
+    pub new_fee_destinations: Vec<FeeDestination>,
+    
+    /// Admin who changed the split
+    pub authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when the multi-year discount schedule is updated
+#[event]
+pub struct MultiYearDiscountsUpdated {
+    /// Discount tiers before the change
+    pub previous_multi_year_discounts: Vec<MultiYearDiscount>,
+    
+    /// Discount tiers after the change
+    pub new_multi_year_discounts: Vec<MultiYearDiscount>,
+    
+    /// Admin who changed the schedule
+    pub authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when the sunrise allowlist and general availability are configured
+#[event]
+pub struct SunriseConfigured {
+    /// Merkle root of the sunrise allowlist
+    pub sunrise_merkle_root: [u8; 32],
+    
+    /// Timestamp from which anyone can register
+    pub general_availability_timestamp: i64,
+    
+    /// Admin who configured the sunrise
+    pub authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when the airdrop Merkle root is updated
+#[event]
+pub struct AirdropRootUpdated {
+    /// Merkle root before the change
+    pub previous_airdrop_merkle_root: [u8; 32],
+    
+    /// Merkle root after the change
+    pub new_airdrop_merkle_root: [u8; 32],
+    
+    /// Admin who changed the root
+    pub authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when the release refund rate is updated
+#[event]
+pub struct ReleaseRefundRateUpdated {
+    /// Refund rate before the change in basis points
+    pub previous_refund_rate_bps: u16,
+    
+    /// Refund rate after the change in basis points
+    pub new_refund_rate_bps: u16,
+    
+    /// Admin who changed the rate
+    pub authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when the configuration bounds are updated
+#[event]
+pub struct ConfigBoundsUpdated {
+    /// Bounds before the change
+    pub previous_config_bounds: ConfigBounds,
+    
+    /// Bounds after the change
+    pub new_config_bounds: ConfigBounds,
+    
+    /// Admin who changed the bounds
+    pub authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when an admin change is queued behind the timelock
+#[event]
+pub struct ConfigChangeQueued {
+    /// Id of the queued change
+    pub change_id: u64,
+    
+    /// Queued change
+    pub change: ConfigChange,
+    
+    /// Admin who queued the change
+    pub proposer: Pubkey,
+    
+    /// Timestamp from which the change can be executed
+    pub effective_timestamp: i64,
+    
+    /// Timestamp when the change was queued
+    pub timestamp: i64,
+}
+
+/// Emitted when a queued change is cancelled
+#[event]
+pub struct ConfigChangeCancelled {
+    /// Id of the cancelled change
+    pub change_id: u64,
+    
+    /// Cancelled change
+    pub change: ConfigChange,
+    
+    /// Admin who cancelled the change
+    pub authority: Pubkey,
+    
+    /// Timestamp of the cancellation
+    pub timestamp: i64,
+}
+
+/// Emitted when a queued change is executed, alongside the event specific to the change if it has one
+#[event]
+pub struct ConfigChangeApplied {
+    /// Id of the executed change
+    pub change_id: u64,
+    
+    /// Applied change
+    pub change: ConfigChange,
+    
+    /// Admin who queued the change
+    pub authority: Pubkey,
+    
+    /// Timestamp when the change was applied
+    pub timestamp: i64,
+}
+
+/// Emitted when a queued base price change is applied
+#[event]
+pub struct PriceUpdated {
+    /// Base price before the change in USD cents
+    pub previous_base_price_usd: u64,
+    
+    /// Base price after the change in USD cents
+    pub new_base_price_usd: u64,
+    
+    /// Admin who queued the change
+    pub authority: Pubkey,
+    
+    /// Timestamp when the change was applied
+    pub timestamp: i64,
+}
+
+/// Emitted when the super-admin role is transferred
+#[event]
+pub struct AuthorityUpdated {
+    /// Super-admin before the change
+    pub previous_authority: Pubkey,
+    
+    /// Super-admin after the change
+    pub new_authority: Pubkey,
+    
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when a queued grace period change is applied
+#[event]
+pub struct GracePeriodUpdated {
+    /// Grace period before the change in seconds
+    pub previous_grace_period_seconds: i64,
+    
+    /// Grace period after the change in seconds
+    pub new_grace_period_seconds: i64,
+    
+    /// Admin who queued the change
+    pub authority: Pubkey,
+    
+    /// Timestamp when the change was applied
+    pub timestamp: i64,
+}
+
+/// Emitted when collected fees are withdrawn
+#[event]
+pub struct FeesWithdrawn {
+    /// Account that called the withdrawal
+    pub caller: Pubkey,
+    
+    /// Treasurer receiving the remainder
+    pub treasurer: Pubkey,
+    
+    /// Total lamports withdrawn from the program state
+    pub total_lamports: u64,
+    
+    /// Lamports paid to each fee destination, in the configured order
+    pub destination_lamports: Vec<u64>,
+    
+    /// Lamports paid to the treasurer
+    pub treasurer_lamports: u64,
+    
+    /// Timestamp of the withdrawal
+    pub timestamp: i64,
+}
@@ -0,0 +1,64 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for adding a reserved name instruction
+/// 
+/// This instruction allows the reserved-name manager to add a name, identified by its hash, to the reserved names list.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(name_hash: [u8; 32])]
+pub struct AddReservedNameAccountConstraints<'info> {
+    /// Reserved-name manager, must hold the ReservedNameManager role in AdminRoles
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::ReservedNameManager, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Reserved name account (PDA), derived from the hash of the name
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + ReservedName::INIT_SPACE,
+        seeds = [RESERVED_NAME_SEED, name_hash.as_ref()],
+        bump
+    )]
+    pub reserved_name: Account<'info, ReservedName>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Add a name to the reserved names list
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `name_hash` - SHA-256 hash of the lowercase domain name (without .ca suffix)
+pub fn add_reserved_name_handler(
+    context: Context<AddReservedNameAccountConstraints>,
+    name_hash: [u8; 32],
+) -> Result<()> {
+    let reserved_name = &mut context.accounts.reserved_name;
+    reserved_name.name_hash = name_hash;
+    reserved_name.bump = context.bumps.reserved_name;
+    
+    emit_event!(context, ReservedNameAdded {
+        name_hash,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Reserved name {} added", hex::encode(name_hash));
+    
+    Ok(())
+}
@@ -0,0 +1,100 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::admin::utils::*;
+
+/// Account constraints for force-transferring domain instruction
+/// 
+/// This instruction allows the dispute manager to reassign a domain, e.g. after a court order or UDRP-style decision.
+/// The suspension state and expiry of the domain are left unchanged.
+/// If a queued change account is supplied, or if dispute actions are timelocked, the action is queued instead
+/// and applied by execute_queued_change once the timelock delay has elapsed, provided the domain is still
+/// the same registration held by the same owner.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain_name: String)]
+pub struct AdminForceTransferAccountConstraints<'info> {
+    /// Dispute manager
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::DisputeManager, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Domain record subject to the dispute
+    #[account(
+        mut,
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump = domain_record.bump
+    )]
+    pub domain_record: Account<'info, DomainRecord>,
+
+    /// Optional queued change account (PDA), required when dispute actions are timelocked
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + QueuedChange::INIT_SPACE,
+        seeds = [QUEUED_CHANGE_SEED, &program_state.queued_changes_count.to_le_bytes()],
+        bump
+    )]
+    pub queued_change: Option<Account<'info, QueuedChange>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Force-transfer a domain to a new owner
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `domain_name` - Name of the domain (without .ca suffix)
+/// * `new_owner` - Public key of the new owner
+/// * `reason_code` - Reason code of the dispute decision, recorded in the audit event
+/// 
+/// # Errors
+/// * `MissingAdminRole` - Caller is not the dispute manager
+/// * `DisputeTimelockEnabled` - Dispute actions are timelocked and no queued change account was supplied
+pub fn admin_force_transfer_handler(
+    context: Context<AdminForceTransferAccountConstraints>,
+    _domain_name: String,
+    new_owner: Pubkey,
+    reason_code: u16,
+) -> Result<()> {
+    let change = ConfigChange::ForceTransferDomain {
+        domain_record: context.accounts.domain_record.key(),
+        registration_generation: context.accounts.domain_record.registration_generation,
+        current_owner: context.accounts.domain_record.owner,
+        new_owner,
+        reason_code,
+    };
+    let authority = context.accounts.authority.key();
+    let ctx = event_cpi_context!(context);
+    
+    // Queue the action behind the timelock if requested
+    if let (Some(queued_change), Some(bump)) = (context.accounts.queued_change.as_mut(), context.bumps.queued_change) {
+        return queue_config_change(queued_change, &mut context.accounts.program_state, change, authority, bump, &ctx);
+    }
+    
+    // Otherwise apply it immediately, unless dispute actions are timelocked
+    require!(
+        !context.accounts.program_state.dispute_timelock_enabled,
+        CaRegistrarError::DisputeTimelockEnabled
+    );
+    apply_dispute_action(&mut context.accounts.domain_record, &change, authority, &ctx)
+}
@@ -0,0 +1,125 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::instructions::utils::*;
+use crate::instructions::register::utils::*;
+use crate::error::CaRegistrarError;
+
+/// Account constraints for assigning a reserved name instruction
+/// 
+/// This instruction allows the reserved-name manager to register a reserved name to its rightful owner
+/// without payment. The name stays on the reserved names list.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain_name: String)]
+pub struct AdminRegisterReservedAccountConstraints<'info> {
+    /// Reserved-name manager, must hold the ReservedNameManager role in AdminRoles, pays the account rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::ReservedNameManager, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Reserved name account for the domain name
+    #[account(
+        seeds = [RESERVED_NAME_SEED, &hash(domain_name.as_bytes()).to_bytes()],
+        bump = reserved_name.bump
+    )]
+    pub reserved_name: Account<'info, ReservedName>,
+
+    /// Account storing domain information, using the domain name as a seed for PDA derivation
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + DomainRecord::INIT_SPACE,
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump
+    )]
+    pub domain_record: Account<'info, DomainRecord>,
+
+    /// Program state account, storing global configuration and statistics
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Assign a reserved name to its owner without payment
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `domain_name` - Reserved domain name to register (without .ca suffix)
+/// * `period` - Registration period, at least the minimum registration duration
+/// * `addresses` - List of blockchain addresses to set for the domain
+/// * `owner` - Owner of the domain
+/// 
+/// # Errors
+/// * `InvalidDomainLength` - Domain name length is invalid (empty or longer than 253 characters)
+/// * `DomainNameNotLowercase` - Domain name contains uppercase characters
+/// * `InvalidRegisterYears` - Registration period is empty
+/// * `RegistrationPeriodTooShort` - Registration period is shorter than the minimum registration duration
+/// * `RegistrationHorizonExceeded` - Expiry would be further ahead than the maximum registration horizon
+/// * `ProgramPaused` - Registrations are paused
+pub fn admin_register_reserved_handler(
+    context: Context<AdminRegisterReservedAccountConstraints>,
+    domain_name: String,
+    period: RegistrationPeriod,
+    addresses: Vec<ChainAddress>,
+    owner: Pubkey,
+) -> Result<()> {
+    // verify domain name, pause flag and period
+    validate_registration(&context.accounts.program_state, &domain_name, &period)?;
+
+    // get current timestamp
+    let current_timestamp = get_current_timestamp()?;
+
+    // Update domain record
+    let registration_generation = next_registration_generation(&mut context.accounts.program_state)?;
+    let domain_record = &mut context.accounts.domain_record;
+    write_new_domain_record(
+        domain_record,
+        domain_name,
+        owner,
+        addresses,
+        current_timestamp,
+        registration_generation,
+        calculate_expiry_timestamp(&context.accounts.program_state, current_timestamp, current_timestamp, &period)?,
+        0,
+        context.bumps.domain_record,
+    );
+
+    // Update program state
+    let program_state = &mut context.accounts.program_state;
+    program_state.domains_registered += 1;
+
+    emit_event!(context, DomainRegistered {
+        domain_name: domain_record.domain_name.clone(),
+        owner,
+        payer: context.accounts.authority.key(),
+        addresses: domain_record.addresses.clone(),
+        price_usd: 0,
+        lamports_paid: 0,
+        referral_lamports: 0,
+        oracle_price: None,
+        expiry_timestamp: domain_record.expiry_timestamp,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Reserved domain {} assigned for {:?} to owner {}", 
+        domain_record.domain_name, period, owner);
+    
+    Ok(())
+}
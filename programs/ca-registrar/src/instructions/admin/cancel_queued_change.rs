@@ -0,0 +1,65 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for cancelling a queued change instruction
+/// 
+/// This instruction allows any holder of the role that can queue a change to cancel it before execution.
+/// The queued change account is closed and its rent returned to the proposer.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(change_id: u64)]
+pub struct CancelQueuedChangeAccountConstraints<'info> {
+    /// Admin holding the role required by the queued change
+    pub authority: Signer<'info>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(queued_change.change.required_role(), &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Queued change to cancel
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [QUEUED_CHANGE_SEED, &change_id.to_le_bytes()],
+        bump = queued_change.bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    /// Admin who queued the change, receives the rent back
+    #[account(
+        mut,
+        address = queued_change.proposer
+    )]
+    pub proposer: SystemAccount<'info>,
+}
+
+/// Cancel a queued parameter change
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `change_id` - Id of the queued change
+pub fn cancel_queued_change_handler(
+    context: Context<CancelQueuedChangeAccountConstraints>,
+    change_id: u64,
+) -> Result<()> {
+    emit_event!(context, ConfigChangeCancelled {
+        change_id,
+        change: context.accounts.queued_change.change,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Queued change {} cancelled", change_id);
+    
+    Ok(())
+}
@@ -0,0 +1,55 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for closing a promo code instruction
+/// 
+/// This instruction allows the pricing manager to revoke a promo code and reclaim its rent.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClosePromoCodeAccountConstraints<'info> {
+    /// Pricing manager, must hold the PricingManager role in AdminRoles
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::PricingManager, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Promo code to close
+    #[account(
+        mut,
+        close = authority,
+        seeds = [PROMO_CODE_SEED, promo_code.code_hash.as_ref()],
+        bump = promo_code.bump
+    )]
+    pub promo_code: Account<'info, PromoCode>,
+}
+
+/// Close a promo code
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+pub fn close_promo_code_handler(
+    context: Context<ClosePromoCodeAccountConstraints>,
+) -> Result<()> {
+    emit_event!(context, PromoCodeClosed {
+        code_hash: context.accounts.promo_code.code_hash,
+        remaining_uses: context.accounts.promo_code.remaining_uses,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Promo code {} closed", hex::encode(context.accounts.promo_code.code_hash));
+    
+    Ok(())
+}
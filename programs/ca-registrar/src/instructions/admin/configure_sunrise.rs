@@ -0,0 +1,64 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for configuring sunrise instruction
+/// 
+/// This instruction allows the program administrator to set the sunrise allowlist and the start of general availability.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConfigureSunriseAccountConstraints<'info> {
+    /// Program administrator, must be the super-admin in AdminRoles
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::SuperAdmin, &authority.key()) @ CaRegistrarError::NotProgramAuthority
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+}
+
+/// Configure the sunrise period
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `sunrise_merkle_root` - Merkle root of the (domain name, claimant) allowlist
+/// * `general_availability_timestamp` - Timestamp when register_domain opens to everyone
+pub fn configure_sunrise_handler(
+    context: Context<ConfigureSunriseAccountConstraints>,
+    sunrise_merkle_root: [u8; 32],
+    general_availability_timestamp: i64,
+) -> Result<()> {
+    let program_state = &mut context.accounts.program_state;
+    
+    // Update sunrise configuration
+    program_state.sunrise_merkle_root = sunrise_merkle_root;
+    program_state.general_availability_timestamp = general_availability_timestamp;
+    
+    emit_event!(context, SunriseConfigured {
+        sunrise_merkle_root,
+        general_availability_timestamp,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Sunrise allowlist set to {}, general availability at {}",
+        hex::encode(sunrise_merkle_root), general_availability_timestamp);
+    
+    Ok(())
+}
@@ -0,0 +1,88 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for creating a promo code instruction
+/// 
+/// This instruction allows the pricing manager to create a promotional discount code.
+/// Only the hash of the code is stored, the code itself is revealed when it is used.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(code_hash: [u8; 32])]
+pub struct CreatePromoCodeAccountConstraints<'info> {
+    /// Pricing manager, must hold the PricingManager role in AdminRoles
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::PricingManager, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Promo code account (PDA), derived from the hash of the code
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + PromoCode::INIT_SPACE,
+        seeds = [PROMO_CODE_SEED, code_hash.as_ref()],
+        bump
+    )]
+    pub promo_code: Account<'info, PromoCode>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a promo code
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `code_hash` - SHA-256 hash of the code
+/// * `discount` - Percentage or fixed USD discount granted by the code
+/// * `max_uses` - Number of registrations that can use the code
+/// * `expiry_timestamp` - Timestamp after which the code can no longer be used
+/// 
+/// # Errors
+/// * `InvalidDiscount` - Percentage discount above 10000 basis points
+pub fn create_promo_code_handler(
+    context: Context<CreatePromoCodeAccountConstraints>,
+    code_hash: [u8; 32],
+    discount: PromoDiscount,
+    max_uses: u32,
+    expiry_timestamp: i64,
+) -> Result<()> {
+    // Validate discount
+    if let PromoDiscount::Percentage { discount_bps } = discount {
+        require!(
+            discount_bps as u64 <= BASIS_POINTS_DENOMINATOR,
+            CaRegistrarError::InvalidDiscount
+        );
+    }
+
+    let promo_code = &mut context.accounts.promo_code;
+    promo_code.code_hash = code_hash;
+    promo_code.discount = discount;
+    promo_code.remaining_uses = max_uses;
+    promo_code.expiry_timestamp = expiry_timestamp;
+    promo_code.bump = context.bumps.promo_code;
+    
+    emit_event!(context, PromoCodeCreated {
+        code_hash,
+        discount,
+        max_uses,
+        expiry_timestamp,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Promo code {} created with {} uses", hex::encode(code_hash), max_uses);
+    
+    Ok(())
+}
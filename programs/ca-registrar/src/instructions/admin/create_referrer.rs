@@ -0,0 +1,80 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for creating a referrer instruction
+/// 
+/// This instruction allows the treasurer to enroll a partner wallet in the referral program.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateReferrerAccountConstraints<'info> {
+    /// Treasurer, must hold the Treasurer role in AdminRoles
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::Treasurer, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Referrer wallet receiving referral payouts
+    pub referrer: SystemAccount<'info>,
+
+    /// Referrer config account (PDA), derived from the referrer wallet
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + ReferrerConfig::INIT_SPACE,
+        seeds = [REFERRER_CONFIG_SEED, referrer.key().as_ref()],
+        bump
+    )]
+    pub referrer_config: Account<'info, ReferrerConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a referrer config
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `share_bps_override` - Referral share in basis points, or None to use the program default
+/// 
+/// # Errors
+/// * `InvalidReferralShare` - Share exceeds 5000 basis points
+pub fn create_referrer_handler(
+    context: Context<CreateReferrerAccountConstraints>,
+    share_bps_override: Option<u16>,
+) -> Result<()> {
+    // Validate share override
+    require!(
+        share_bps_override.unwrap_or(0) <= MAX_REFERRAL_SHARE_BPS,
+        CaRegistrarError::InvalidReferralShare
+    );
+
+    let referrer_config = &mut context.accounts.referrer_config;
+    referrer_config.referrer = context.accounts.referrer.key();
+    referrer_config.share_bps_override = share_bps_override;
+    referrer_config.active = true;
+    referrer_config.referral_count = 0;
+    referrer_config.total_paid_lamports = 0;
+    referrer_config.bump = context.bumps.referrer_config;
+    
+    emit_event!(context, ReferrerCreated {
+        referrer: referrer_config.referrer,
+        share_bps_override,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Referrer {} created", referrer_config.referrer);
+    
+    Ok(())
+}
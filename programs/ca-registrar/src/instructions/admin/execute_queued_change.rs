@@ -0,0 +1,97 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::admin::utils::*;
+
+/// Account constraints for executing a queued change instruction
+/// 
+/// This instruction allows anyone to apply a queued parameter change once its timelock delay has elapsed.
+/// The queued change account is closed and its rent returned to the proposer.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(change_id: u64)]
+pub struct ExecuteQueuedChangeAccountConstraints<'info> {
+    /// caller - can be anyone
+    pub signer: Signer<'info>,
+
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Queued change to execute
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [QUEUED_CHANGE_SEED, &change_id.to_le_bytes()],
+        bump = queued_change.bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    /// Admin who queued the change, receives the rent back
+    #[account(
+        mut,
+        address = queued_change.proposer
+    )]
+    pub proposer: SystemAccount<'info>,
+
+    /// Domain record targeted by the change, required for dispute actions only
+    #[account(
+        mut,
+        seeds = [DOMAIN_RECORD_SEED, domain_record.domain_name.as_bytes()],
+        bump = domain_record.bump
+    )]
+    pub domain_record: Option<Account<'info, DomainRecord>>,
+}
+
+/// Apply a queued parameter change
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `change_id` - Id of the queued change
+/// 
+/// # Errors
+/// * `TimelockNotElapsed` - Effective timestamp of the change has not been reached
+/// * `DomainRecordMismatch` - Domain record is missing, not the one targeted by a dispute action, or re-registered or transferred since it was queued
+pub fn execute_queued_change_handler(
+    context: Context<ExecuteQueuedChangeAccountConstraints>,
+    change_id: u64,
+) -> Result<()> {
+    let current_timestamp = get_current_timestamp()?;
+    let queued_change = &context.accounts.queued_change;
+    
+    // Verify timelock delay has elapsed
+    require!(
+        current_timestamp >= queued_change.effective_timestamp,
+        CaRegistrarError::TimelockNotElapsed
+    );
+    
+    // Apply change
+    let ctx = event_cpi_context!(context);
+    apply_config_change(
+        &mut context.accounts.program_state,
+        context.accounts.domain_record.as_mut(),
+        &queued_change.change,
+        queued_change.proposer,
+        &ctx,
+    )?;
+    
+    emit_cpi!(ConfigChangeApplied {
+        change_id,
+        change: queued_change.change,
+        authority: queued_change.proposer,
+        timestamp: current_timestamp,
+    });
+    
+    msg!("Queued change {} executed", change_id);
+    
+    Ok(())
+}
@@ -3,26 +3,63 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::admin::utils::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
 
 /// Account constraints for program initialization instruction
 /// 
-/// This instruction is used to set up the initial program state, including admin authority and base price.
-/// It can only be executed once.
+/// This instruction is used to set up the initial program state, including admin roles and base price.
+/// It can only be executed once, by the program upgrade authority or, when built with the
+/// `fixed-initializer` feature, by the key baked in at build time.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeAccountConstraints<'info> {
+    /// Upgrade authority of the program (or the fixed initializer), pays for account creation
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub initializer: Signer<'info>,
+
+    /// Program data account of this program, holding its upgrade authority
+    /// Not required when built with the `fixed-initializer` feature
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
 
     /// Program state account (PDA)
     #[account(
         init,
-        payer = authority,
+        payer = initializer,
         space = ANCHOR_DISCRIMINATOR + ProgramState::INIT_SPACE,
         seeds = [PROGRAM_STATE_SEED],
         bump
     )]
     pub program_state: Account<'info, ProgramState>,
 
+    /// Admin roles account (PDA), every role is initially held by the initial authority
+    #[account(
+        init,
+        payer = initializer,
+        space = ANCHOR_DISCRIMINATOR + AdminRoles::INIT_SPACE,
+        seeds = [ADMIN_ROLES_SEED],
+        bump
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Pricing configuration account (PDA), starts without multi-year discounts
+    #[account(
+        init,
+        payer = initializer,
+        space = ANCHOR_DISCRIMINATOR + PriceConfig::INIT_SPACE,
+        seeds = [PRICE_CONFIG_SEED],
+        bump
+    )]
+    pub price_config: Account<'info, PriceConfig>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -31,25 +68,99 @@ pub struct InitializeAccountConstraints<'info> {
 /// # Parameters
 /// * `context` - Instruction context, containing all relevant accounts
 /// * `base_price_usd` - Base price for domain registration (in USD cents, e.g., 500 means $5.00)
-///                     This price is only used when not using the oracle
+///   This price is only used when not using the oracle
 /// * `grace_period_seconds` - Grace period in seconds for domain registration
+/// * `timelock_delay_seconds` - Delay in seconds before queued admin parameter changes can be executed
+/// * `initial_authority` - Key receiving every admin role, can differ from the initializer (e.g. a multisig)
+/// 
+/// # Errors
+/// * `NotProgramInitializer` - Signer is not the upgrade authority (or the fixed initializer)
+/// * `BasePriceOutOfBounds` - Base price is outside the default bounds
+/// * `GracePeriodOutOfBounds` - Grace period is outside the default bounds
+/// * `InvalidTimelockDelay` - Delay is negative or longer than 30 days
 pub fn initialize_handler(
     context: Context<InitializeAccountConstraints>,
     base_price_usd: u64,
     grace_period_seconds: i64,
+    timelock_delay_seconds: i64,
+    initial_authority: Pubkey,
 ) -> Result<()> {
+    // verify the signer is allowed to initialize the program
+    let initializer = context.accounts.initializer.key();
+    #[cfg(feature = "fixed-initializer")]
+    require_keys_eq!(initializer, FIXED_INITIALIZER, CaRegistrarError::NotProgramInitializer);
+    #[cfg(not(feature = "fixed-initializer"))]
+    require!(
+        context.accounts.program_data
+            .as_ref()
+            .and_then(|program_data| program_data.upgrade_authority_address)
+            == Some(initializer),
+        CaRegistrarError::NotProgramInitializer
+    );
+
+    // verify initial values against the default bounds
+    let config_bounds = ConfigBounds {
+        min_base_price_usd: DEFAULT_MIN_BASE_PRICE_USD,
+        max_base_price_usd: DEFAULT_MAX_BASE_PRICE_USD,
+        min_grace_period_seconds: DEFAULT_MIN_GRACE_PERIOD_SECONDS,
+        max_grace_period_seconds: DEFAULT_MAX_GRACE_PERIOD_SECONDS,
+    };
+    validate_config_change(&config_bounds, &ConfigChange::BasePrice { base_price_usd })?;
+    validate_config_change(&config_bounds, &ConfigChange::GracePeriod { grace_period_seconds })?;
+    validate_config_change(&config_bounds, &ConfigChange::TimelockDelay { timelock_delay_seconds })?;
+
+    let authority = initial_authority;
     let program_state = &mut context.accounts.program_state;
     
     // Set program state
-    program_state.authority = context.accounts.authority.key();
     program_state.base_price_usd = base_price_usd;
     program_state.domains_registered = 0;
+    program_state.registration_generations = 0;
     program_state.grace_period_seconds = grace_period_seconds;
+    program_state.paused = false;
+    program_state.timelock_delay_seconds = timelock_delay_seconds;
+    program_state.queued_changes_count = 0;
+    program_state.config_bounds = config_bounds;
+    program_state.fee_destinations = Vec::new();
+    program_state.default_referral_share_bps = 0;
+    program_state.sunrise_merkle_root = [0; 32];
+    program_state.general_availability_timestamp = 0;
+    program_state.airdrop_merkle_root = [0; 32];
+    program_state.dispute_timelock_enabled = false;
+    program_state.release_refund_rate_bps = 0;
+    program_state.max_registration_horizon_seconds = DEFAULT_MAX_REGISTRATION_HORIZON_SECONDS;
+    program_state.min_registration_seconds = DEFAULT_MIN_REGISTRATION_SECONDS;
+    program_state.subdomain_protocol_fee_bps = 0;
     program_state.bump = context.bumps.program_state;
     
-    msg!("Program initialized with authority: {}", program_state.authority);
+    // Assign every role to the initial authority
+    let admin_roles = &mut context.accounts.admin_roles;
+    admin_roles.super_admin = authority;
+    admin_roles.pricing_manager = authority;
+    admin_roles.treasurer = authority;
+    admin_roles.reserved_name_manager = authority;
+    admin_roles.pauser = authority;
+    admin_roles.voucher_signer = authority;
+    admin_roles.dispute_manager = authority;
+    admin_roles.bump = context.bumps.admin_roles;
+    
+    // Start without multi-year discounts
+    let price_config = &mut context.accounts.price_config;
+    price_config.multi_year_discounts = Vec::new();
+    price_config.bump = context.bumps.price_config;
+    
+    emit_event!(context, ProgramInitialized {
+        authority,
+        base_price_usd,
+        grace_period_seconds,
+        timelock_delay_seconds,
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Program initialized with authority: {}", authority);
     msg!("Base price set to: {} USD cents", program_state.base_price_usd);
     msg!("Grace period set to: {} days", program_state.grace_period_seconds / 86400);
+    msg!("Timelock delay set to: {} seconds", program_state.timelock_delay_seconds);
     
     Ok(())
 } 
\ No newline at end of file
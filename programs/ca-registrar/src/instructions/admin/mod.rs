@@ -1,3 +1,5 @@
+mod utils;
+
 pub use initialize::*;
 mod initialize;
 
@@ -11,4 +13,79 @@ pub use update_grace_period::*;
 mod update_grace_period;
 
 pub use withdraw_fees::*;
-mod withdraw_fees; 
\ No newline at end of file
+mod withdraw_fees; 
+
+pub use update_role::*;
+mod update_role;
+
+pub use set_paused::*;
+mod set_paused;
+
+pub use update_timelock_delay::*;
+mod update_timelock_delay;
+
+pub use cancel_queued_change::*;
+mod cancel_queued_change;
+
+pub use execute_queued_change::*;
+mod execute_queued_change;
+
+pub use update_config_bounds::*;
+mod update_config_bounds;
+
+pub use update_fee_split::*;
+mod update_fee_split;
+
+pub use create_referrer::*;
+mod create_referrer;
+
+pub use update_referrer::*;
+mod update_referrer;
+
+pub use update_default_referral_share::*;
+mod update_default_referral_share;
+
+pub use update_multi_year_discounts::*;
+mod update_multi_year_discounts;
+
+pub use create_promo_code::*;
+mod create_promo_code;
+
+pub use close_promo_code::*;
+mod close_promo_code;
+
+pub use add_reserved_name::*;
+mod add_reserved_name;
+
+pub use remove_reserved_name::*;
+mod remove_reserved_name;
+
+pub use admin_register_reserved::*;
+mod admin_register_reserved;
+
+pub use configure_sunrise::*;
+mod configure_sunrise;
+
+pub use update_airdrop_root::*;
+mod update_airdrop_root;
+
+pub use update_dispute_timelock::*;
+mod update_dispute_timelock;
+
+pub use admin_suspend_domain::*;
+mod admin_suspend_domain;
+
+pub use admin_force_transfer::*;
+mod admin_force_transfer;
+
+pub use update_release_refund_rate::*;
+mod update_release_refund_rate;
+
+pub use update_registration_horizon::*;
+mod update_registration_horizon;
+
+pub use update_min_registration_duration::*;
+mod update_min_registration_duration;
+
+pub use update_subdomain_protocol_fee::*;
+mod update_subdomain_protocol_fee;
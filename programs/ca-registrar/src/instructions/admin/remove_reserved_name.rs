@@ -0,0 +1,54 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for removing a reserved name instruction
+/// 
+/// This instruction allows the reserved-name manager to release a name from the reserved names list.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveReservedNameAccountConstraints<'info> {
+    /// Reserved-name manager, must hold the ReservedNameManager role in AdminRoles
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::ReservedNameManager, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Reserved name to remove
+    #[account(
+        mut,
+        close = authority,
+        seeds = [RESERVED_NAME_SEED, reserved_name.name_hash.as_ref()],
+        bump = reserved_name.bump
+    )]
+    pub reserved_name: Account<'info, ReservedName>,
+}
+
+/// Remove a name from the reserved names list
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+pub fn remove_reserved_name_handler(
+    context: Context<RemoveReservedNameAccountConstraints>,
+) -> Result<()> {
+    emit_event!(context, ReservedNameRemoved {
+        name_hash: context.accounts.reserved_name.name_hash,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Reserved name {} removed", hex::encode(context.accounts.reserved_name.name_hash));
+    
+    Ok(())
+}
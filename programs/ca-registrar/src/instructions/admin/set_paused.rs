@@ -0,0 +1,59 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for pausing instruction
+/// 
+/// This instruction allows the pauser to pause or resume registrations, renewals and purchases.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPausedAccountConstraints<'info> {
+    /// Pauser, must hold the Pauser role in AdminRoles
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::Pauser, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+}
+
+/// Pause or resume the registrar
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `paused` - Whether registrations, renewals and purchases should be paused
+pub fn set_paused_handler(
+    context: Context<SetPausedAccountConstraints>,
+    paused: bool,
+) -> Result<()> {
+    let program_state = &mut context.accounts.program_state;
+    
+    // Update pause flag
+    program_state.paused = paused;
+    
+    emit_event!(context, PausedUpdated {
+        paused,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Program paused: {}", paused);
+    
+    Ok(())
+}
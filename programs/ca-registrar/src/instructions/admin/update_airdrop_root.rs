@@ -0,0 +1,65 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for updating the airdrop root instruction
+/// 
+/// This instruction allows the program administrator to publish the Merkle root of pre-allocated domains.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateAirdropRootAccountConstraints<'info> {
+    /// Program administrator, must be the super-admin in AdminRoles
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::SuperAdmin, &authority.key()) @ CaRegistrarError::NotProgramAuthority
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+}
+
+/// Update the airdrop Merkle root
+/// 
+/// Leaves that were already claimed stay claimed, a new root only adds allocations that can be claimed. Allocated
+/// names must be added to the reserved names list before the root is published, otherwise anyone can register them
+/// before their owner claims them.
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `airdrop_merkle_root` - Merkle root of the (domain name, owner, expiry) leaves
+pub fn update_airdrop_root_handler(
+    context: Context<UpdateAirdropRootAccountConstraints>,
+    airdrop_merkle_root: [u8; 32],
+) -> Result<()> {
+    let program_state = &mut context.accounts.program_state;
+    
+    // Update airdrop root
+    let previous_airdrop_merkle_root = program_state.airdrop_merkle_root;
+    program_state.airdrop_merkle_root = airdrop_merkle_root;
+    
+    emit_event!(context, AirdropRootUpdated {
+        previous_airdrop_merkle_root,
+        new_airdrop_merkle_root: airdrop_merkle_root,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Airdrop root updated to {}", hex::encode(airdrop_merkle_root));
+    
+    Ok(())
+}
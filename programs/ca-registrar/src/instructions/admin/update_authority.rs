@@ -3,24 +3,28 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::state::*;
+use crate::events::*;
 use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
 
 /// Account constraints for updating authority instruction
 /// 
-/// This instruction allows the current program administrator to transfer authority to a new administrator.
+/// This instruction allows the current super-admin to transfer the super-admin role to a new administrator.
+/// Other roles are left unchanged and can be reassigned with the update_role instruction.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateAuthorityAccountConstraints<'info> {
-    /// Current program administrator, must match the authority in ProgramState
+    /// Current program administrator, must be the super-admin in AdminRoles
     pub authority: Signer<'info>,
     
-    /// Program state account
+    /// Admin roles account
     #[account(
         mut,
-        has_one = authority @ CaRegistrarError::NotProgramAuthority,
-        seeds = [PROGRAM_STATE_SEED],
-        bump = program_state.bump
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.super_admin == authority.key() @ CaRegistrarError::NotProgramAuthority
     )]
-    pub program_state: Account<'info, ProgramState>,
+    pub admin_roles: Account<'info, AdminRoles>,
     
     /// New program administrator account
     pub new_authority: SystemAccount<'info>,
@@ -33,11 +37,18 @@ pub struct UpdateAuthorityAccountConstraints<'info> {
 pub fn update_authority_handler(
     context: Context<UpdateAuthorityAccountConstraints>,
 ) -> Result<()> {
-    let program_state = &mut context.accounts.program_state;
+    let admin_roles = &mut context.accounts.admin_roles;
     let new_authority = context.accounts.new_authority.key();
     
     // Update administrator
-    program_state.authority = new_authority;
+    let previous_authority = admin_roles.super_admin;
+    admin_roles.super_admin = new_authority;
+    
+    emit_event!(context, AuthorityUpdated {
+        previous_authority,
+        new_authority,
+        timestamp: get_current_timestamp()?,
+    });
     
     msg!("Authority updated to: {}", new_authority);
     
@@ -0,0 +1,72 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::admin::utils::*;
+
+/// Account constraints for updating configuration bounds instruction
+/// 
+/// This instruction allows the program administrator to update the allowed ranges for the base price
+/// and grace period. Bounds only restrict future changes and do not alter the current values.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateConfigBoundsAccountConstraints<'info> {
+    /// Program administrator, must be the super-admin in AdminRoles
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::SuperAdmin, &authority.key()) @ CaRegistrarError::NotProgramAuthority
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+}
+
+/// Update configuration bounds
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `config_bounds` - New allowed ranges for the base price and grace period
+/// 
+/// # Errors
+/// * `InvalidConfigBounds` - Minimum price is 0, a minimum exceeds its maximum, the minimum grace
+///   period is negative or the maximum grace period exceeds one year
+pub fn update_config_bounds_handler(
+    context: Context<UpdateConfigBoundsAccountConstraints>,
+    config_bounds: ConfigBounds,
+) -> Result<()> {
+    validate_config_bounds(&config_bounds)?;
+    
+    let program_state = &mut context.accounts.program_state;
+    
+    // Update bounds
+    let previous_config_bounds = program_state.config_bounds;
+    program_state.config_bounds = config_bounds;
+    
+    emit_event!(context, ConfigBoundsUpdated {
+        previous_config_bounds,
+        new_config_bounds: config_bounds,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Base price bounds updated to: {}..={} USD cents",
+        config_bounds.min_base_price_usd, config_bounds.max_base_price_usd);
+    msg!("Grace period bounds updated to: {}..={} seconds",
+        config_bounds.min_grace_period_seconds, config_bounds.max_grace_period_seconds);
+    
+    Ok(())
+}
@@ -0,0 +1,66 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for updating default referral share instruction
+/// 
+/// This instruction allows the treasurer to update the referral share used by referrers without an override.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateDefaultReferralShareAccountConstraints<'info> {
+    /// Treasurer, must hold the Treasurer role in AdminRoles
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::Treasurer, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+}
+
+/// Update default referral share
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `share_bps` - New default referral share in basis points
+/// 
+/// # Errors
+/// * `InvalidReferralShare` - Share exceeds 5000 basis points
+pub fn update_default_referral_share_handler(
+    context: Context<UpdateDefaultReferralShareAccountConstraints>,
+    share_bps: u16,
+) -> Result<()> {
+    require!(share_bps <= MAX_REFERRAL_SHARE_BPS, CaRegistrarError::InvalidReferralShare);
+
+    let program_state = &mut context.accounts.program_state;
+    
+    // Update default referral share
+    let previous_share_bps = program_state.default_referral_share_bps;
+    program_state.default_referral_share_bps = share_bps;
+    
+    emit_event!(context, DefaultReferralShareUpdated {
+        previous_share_bps,
+        new_share_bps: share_bps,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Default referral share updated to: {} basis points", share_bps);
+    
+    Ok(())
+}
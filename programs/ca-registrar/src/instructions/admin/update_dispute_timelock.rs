@@ -0,0 +1,72 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::admin::utils::*;
+
+/// Account constraints for updating dispute timelock instruction
+/// 
+/// This instruction allows the program administrator to queue enabling or disabling the timelock on
+/// dispute actions. The change is itself subject to the current delay.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateDisputeTimelockAccountConstraints<'info> {
+    /// Program administrator, must be the super-admin in AdminRoles
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::SuperAdmin, &authority.key()) @ CaRegistrarError::NotProgramAuthority
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Queued change account (PDA), publicly visible until executed or cancelled
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + QueuedChange::INIT_SPACE,
+        seeds = [QUEUED_CHANGE_SEED, &program_state.queued_changes_count.to_le_bytes()],
+        bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a dispute timelock update
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `enabled` - Whether domain suspensions and force-transfers must be queued behind the timelock
+pub fn update_dispute_timelock_handler(
+    context: Context<UpdateDisputeTimelockAccountConstraints>,
+    enabled: bool,
+) -> Result<()> {
+    // Queue dispute timelock update
+    queue_config_change(
+        &mut context.accounts.queued_change,
+        &mut context.accounts.program_state,
+        ConfigChange::DisputeTimelock { enabled },
+        context.accounts.authority.key(),
+        context.bumps.queued_change,
+        &event_cpi_context!(context),
+    )?;
+    
+    msg!("Dispute timelock update to {} queued", enabled);
+    
+    Ok(())
+}
@@ -0,0 +1,82 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for updating fee split instruction
+/// 
+/// This instruction allows the treasurer to configure how withdrawn fees are split between destinations.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateFeeSplitAccountConstraints<'info> {
+    /// Treasurer, must hold the Treasurer role in AdminRoles
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::Treasurer, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+}
+
+/// Update fee split destinations
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `fee_destinations` - New fee destinations, completely replacing the existing list
+/// 
+/// # Errors
+/// * `InvalidFeeSplit` - More than 5 destinations, a zero share, or shares adding up to more than 10000 basis points
+pub fn update_fee_split_handler(
+    context: Context<UpdateFeeSplitAccountConstraints>,
+    fee_destinations: Vec<FeeDestination>,
+) -> Result<()> {
+    // Validate destination count and shares
+    require!(
+        fee_destinations.len() <= MAX_FEE_DESTINATIONS,
+        CaRegistrarError::InvalidFeeSplit
+    );
+    require!(
+        fee_destinations.iter().all(|fee_destination| fee_destination.share_bps > 0),
+        CaRegistrarError::InvalidFeeSplit
+    );
+    let total_share_bps: u64 = fee_destinations
+        .iter()
+        .map(|fee_destination| fee_destination.share_bps as u64)
+        .sum();
+    require!(
+        total_share_bps <= BASIS_POINTS_DENOMINATOR,
+        CaRegistrarError::InvalidFeeSplit
+    );
+    
+    let program_state = &mut context.accounts.program_state;
+    
+    // Update fee split
+    let previous_fee_destinations = std::mem::replace(&mut program_state.fee_destinations, fee_destinations);
+    
+    emit_event!(context, FeeSplitUpdated {
+        previous_fee_destinations,
+        new_fee_destinations: program_state.fee_destinations.clone(),
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Fee split updated to {} destinations, {} basis points allocated",
+        program_state.fee_destinations.len(), total_share_bps);
+    
+    Ok(())
+}
@@ -4,26 +4,51 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::state::*;
 use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::admin::utils::*;
 
 /// Account constraints for updating grace period instruction
 /// 
-/// This instruction allows the program administrator to update the grace period duration after domain expiration.
+/// This instruction allows the program administrator to queue an update of the grace period duration after
+/// domain expiration. The new grace period takes effect once the timelock delay has elapsed and
+/// execute_queued_change is called.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateGracePeriodAccountConstraints<'info> {
-    /// Program administrator, must match the authority in ProgramState
+    /// Program administrator, must be the super-admin in AdminRoles
+    #[account(mut)]
     pub authority: Signer<'info>,
     
     /// Program state account
     #[account(
         mut,
-        has_one = authority @ CaRegistrarError::NotProgramAuthority,
         seeds = [PROGRAM_STATE_SEED],
         bump = program_state.bump
     )]
     pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::SuperAdmin, &authority.key()) @ CaRegistrarError::NotProgramAuthority
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Queued change account (PDA), publicly visible until executed or cancelled
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + QueuedChange::INIT_SPACE,
+        seeds = [QUEUED_CHANGE_SEED, &program_state.queued_changes_count.to_le_bytes()],
+        bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    pub system_program: Program<'info, System>,
 }
 
-/// Update grace period duration after domain expiration
+/// Queue a grace period update
 /// 
 /// # Parameters
 /// * `context` - Instruction context, containing all relevant accounts
@@ -32,12 +57,17 @@ pub fn update_grace_period_handler(
     context: Context<UpdateGracePeriodAccountConstraints>,
     grace_period_seconds: i64,
 ) -> Result<()> {
-    let program_state = &mut context.accounts.program_state;
-
-    // Update grace period
-    program_state.grace_period_seconds = grace_period_seconds;
+    // Queue grace period update
+    queue_config_change(
+        &mut context.accounts.queued_change,
+        &mut context.accounts.program_state,
+        ConfigChange::GracePeriod { grace_period_seconds },
+        context.accounts.authority.key(),
+        context.bumps.queued_change,
+        &event_cpi_context!(context),
+    )?;
     
-    msg!("Grace period updated to: {} seconds", 
+    msg!("Grace period update to {} seconds queued", 
         grace_period_seconds
     );
     
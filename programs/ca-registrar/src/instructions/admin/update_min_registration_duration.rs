@@ -0,0 +1,76 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::admin::utils::*;
+
+/// Account constraints for updating minimum registration duration instruction
+/// 
+/// This instruction allows the pricing manager to queue an update of the shortest period a domain can be
+/// registered or renewed for. The new minimum takes effect once the timelock delay has elapsed and
+/// execute_queued_change is called.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMinRegistrationDurationAccountConstraints<'info> {
+    /// Pricing manager, must hold the PricingManager role in AdminRoles
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::PricingManager, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Queued change account (PDA), publicly visible until executed or cancelled
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + QueuedChange::INIT_SPACE,
+        seeds = [QUEUED_CHANGE_SEED, &program_state.queued_changes_count.to_le_bytes()],
+        bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a minimum registration duration update
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `min_registration_seconds` - New minimum registration duration in seconds, between 1 day and 1 year
+/// 
+/// # Errors
+/// * `InvalidMinRegistrationDuration` - Duration is shorter than 1 day or longer than 1 year
+pub fn update_min_registration_duration_handler(
+    context: Context<UpdateMinRegistrationDurationAccountConstraints>,
+    min_registration_seconds: i64,
+) -> Result<()> {
+    // Queue minimum registration duration update
+    queue_config_change(
+        &mut context.accounts.queued_change,
+        &mut context.accounts.program_state,
+        ConfigChange::MinRegistrationDuration { min_registration_seconds },
+        context.accounts.authority.key(),
+        context.bumps.queued_change,
+        &event_cpi_context!(context),
+    )?;
+    
+    msg!("Minimum registration duration update to {} seconds queued", min_registration_seconds);
+    
+    Ok(())
+}
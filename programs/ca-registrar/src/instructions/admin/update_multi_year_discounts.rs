@@ -0,0 +1,75 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for updating multi-year discounts instruction
+/// 
+/// This instruction allows the pricing manager to update the multi-year discount schedule.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMultiYearDiscountsAccountConstraints<'info> {
+    /// Pricing manager, must hold the PricingManager role in AdminRoles
+    pub authority: Signer<'info>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::PricingManager, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Pricing configuration account
+    #[account(
+        mut,
+        seeds = [PRICE_CONFIG_SEED],
+        bump = price_config.bump
+    )]
+    pub price_config: Account<'info, PriceConfig>,
+}
+
+/// Update multi-year discount schedule
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `multi_year_discounts` - New discount tiers, completely replacing the existing schedule
+/// 
+/// # Errors
+/// * `InvalidDiscount` - More than 5 tiers, a tier below 1 year or a discount above 10000 basis points
+pub fn update_multi_year_discounts_handler(
+    context: Context<UpdateMultiYearDiscountsAccountConstraints>,
+    multi_year_discounts: Vec<MultiYearDiscount>,
+) -> Result<()> {
+    // Validate discount tiers
+    require!(
+        multi_year_discounts.len() <= MAX_MULTI_YEAR_DISCOUNTS,
+        CaRegistrarError::InvalidDiscount
+    );
+    require!(
+        multi_year_discounts.iter().all(|discount| {
+            discount.min_years > 0 && discount.discount_bps as u64 <= BASIS_POINTS_DENOMINATOR
+        }),
+        CaRegistrarError::InvalidDiscount
+    );
+    
+    let price_config = &mut context.accounts.price_config;
+    
+    // Update discount schedule
+    let previous_multi_year_discounts = std::mem::replace(&mut price_config.multi_year_discounts, multi_year_discounts);
+    
+    emit_event!(context, MultiYearDiscountsUpdated {
+        previous_multi_year_discounts,
+        new_multi_year_discounts: price_config.multi_year_discounts.clone(),
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Multi-year discounts updated to {} tiers", price_config.multi_year_discounts.len());
+    
+    Ok(())
+}
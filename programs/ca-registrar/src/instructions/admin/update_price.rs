@@ -4,27 +4,51 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::state::*;
 use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::admin::utils::*;
 
 /// Account constraints for updating base price instruction
 /// 
-/// This instruction allows the program administrator to update the base price (in USD cents) for domain registration.
+/// This instruction allows the pricing manager to queue an update of the base price (in USD cents)
+/// for domain registration. The new price takes effect once the timelock delay has elapsed and
+/// execute_queued_change is called.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdatePriceAccountConstraints<'info> {
-    /// Program administrator, must match the authority in ProgramState
+    /// Pricing manager, must hold the PricingManager role in AdminRoles
     #[account(mut)]
     pub authority: Signer<'info>,
     
     /// Program state account
     #[account(
         mut,
-        has_one = authority @ CaRegistrarError::NotProgramAuthority,
         seeds = [PROGRAM_STATE_SEED],
         bump = program_state.bump
     )]
     pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::PricingManager, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Queued change account (PDA), publicly visible until executed or cancelled
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + QueuedChange::INIT_SPACE,
+        seeds = [QUEUED_CHANGE_SEED, &program_state.queued_changes_count.to_le_bytes()],
+        bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    pub system_program: Program<'info, System>,
 }
 
-/// Update base price for domain registration
+/// Queue a base price update for domain registration
 /// 
 /// # Parameters
 /// * `context` - Instruction context, containing all relevant accounts
@@ -33,12 +57,17 @@ pub fn update_price_handler(
     context: Context<UpdatePriceAccountConstraints>,
     new_price: u64,
 ) -> Result<()> {
-    let program_state = &mut context.accounts.program_state;
-    
-    // Update base price
-    program_state.base_price_usd = new_price;
+    // Queue base price update
+    queue_config_change(
+        &mut context.accounts.queued_change,
+        &mut context.accounts.program_state,
+        ConfigChange::BasePrice { base_price_usd: new_price },
+        context.accounts.authority.key(),
+        context.bumps.queued_change,
+        &event_cpi_context!(context),
+    )?;
     
-    msg!("Base price updated to: {} USD cents", new_price);
+    msg!("Base price update to {} USD cents queued", new_price);
     
     Ok(())
 } 
\ No newline at end of file
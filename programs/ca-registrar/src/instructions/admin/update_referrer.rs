@@ -0,0 +1,71 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for updating a referrer instruction
+/// 
+/// This instruction allows the treasurer to change a referrer's share override or deactivate it.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateReferrerAccountConstraints<'info> {
+    /// Treasurer, must hold the Treasurer role in AdminRoles
+    pub authority: Signer<'info>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::Treasurer, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Referrer config to update
+    #[account(
+        mut,
+        seeds = [REFERRER_CONFIG_SEED, referrer_config.referrer.as_ref()],
+        bump = referrer_config.bump
+    )]
+    pub referrer_config: Account<'info, ReferrerConfig>,
+}
+
+/// Update a referrer config
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `share_bps_override` - Referral share in basis points, or None to use the program default
+/// * `active` - Whether referrals through this config are accepted
+/// 
+/// # Errors
+/// * `InvalidReferralShare` - Share exceeds 5000 basis points
+pub fn update_referrer_handler(
+    context: Context<UpdateReferrerAccountConstraints>,
+    share_bps_override: Option<u16>,
+    active: bool,
+) -> Result<()> {
+    // Validate share override
+    require!(
+        share_bps_override.unwrap_or(0) <= MAX_REFERRAL_SHARE_BPS,
+        CaRegistrarError::InvalidReferralShare
+    );
+
+    let referrer_config = &mut context.accounts.referrer_config;
+    referrer_config.share_bps_override = share_bps_override;
+    referrer_config.active = active;
+    
+    emit_event!(context, ReferrerUpdated {
+        referrer: referrer_config.referrer,
+        share_bps_override,
+        active,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Referrer {} updated, active: {}", referrer_config.referrer, active);
+    
+    Ok(())
+}
@@ -0,0 +1,75 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::admin::utils::*;
+
+/// Account constraints for updating registration horizon instruction
+/// 
+/// This instruction allows the program administrator to queue an update of the maximum time ahead of now
+/// a domain can be registered or renewed to. The new horizon takes effect once the timelock delay has elapsed.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRegistrationHorizonAccountConstraints<'info> {
+    /// Program administrator, must be the super-admin in AdminRoles
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::SuperAdmin, &authority.key()) @ CaRegistrarError::NotProgramAuthority
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Queued change account (PDA), publicly visible until executed or cancelled
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + QueuedChange::INIT_SPACE,
+        seeds = [QUEUED_CHANGE_SEED, &program_state.queued_changes_count.to_le_bytes()],
+        bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a registration horizon update
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `max_registration_horizon_seconds` - New registration horizon in seconds, between 1 and 100 years
+/// 
+/// # Errors
+/// * `InvalidRegistrationHorizon` - Horizon is shorter than 1 year or longer than 100 years
+pub fn update_registration_horizon_handler(
+    context: Context<UpdateRegistrationHorizonAccountConstraints>,
+    max_registration_horizon_seconds: i64,
+) -> Result<()> {
+    // Queue registration horizon update
+    queue_config_change(
+        &mut context.accounts.queued_change,
+        &mut context.accounts.program_state,
+        ConfigChange::RegistrationHorizon { max_registration_horizon_seconds },
+        context.accounts.authority.key(),
+        context.bumps.queued_change,
+        &event_cpi_context!(context),
+    )?;
+    
+    msg!("Registration horizon update to {} seconds queued", max_registration_horizon_seconds);
+    
+    Ok(())
+}
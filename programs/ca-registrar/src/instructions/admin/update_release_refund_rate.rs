@@ -0,0 +1,69 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for updating release refund rate instruction
+/// 
+/// This instruction allows the treasurer to update the share of the unused paid amount refunded when a domain is released.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateReleaseRefundRateAccountConstraints<'info> {
+    /// Treasurer, must hold the Treasurer role in AdminRoles
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::Treasurer, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+}
+
+/// Update release refund rate
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `refund_rate_bps` - New refund rate in basis points, 0 disables refunds
+/// 
+/// # Errors
+/// * `InvalidRefundRate` - Rate exceeds 10000 basis points
+pub fn update_release_refund_rate_handler(
+    context: Context<UpdateReleaseRefundRateAccountConstraints>,
+    refund_rate_bps: u16,
+) -> Result<()> {
+    require!(
+        refund_rate_bps as u64 <= BASIS_POINTS_DENOMINATOR,
+        CaRegistrarError::InvalidRefundRate
+    );
+
+    let program_state = &mut context.accounts.program_state;
+    
+    // Update release refund rate
+    let previous_refund_rate_bps = program_state.release_refund_rate_bps;
+    program_state.release_refund_rate_bps = refund_rate_bps;
+    
+    emit_event!(context, ReleaseRefundRateUpdated {
+        previous_refund_rate_bps,
+        new_refund_rate_bps: refund_rate_bps,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Release refund rate updated to: {} basis points", refund_rate_bps);
+    
+    Ok(())
+}
@@ -0,0 +1,59 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for updating an admin role instruction
+/// 
+/// This instruction allows the super-admin to assign any admin role to a new key.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRoleAccountConstraints<'info> {
+    /// Current program administrator, must be the super-admin in AdminRoles
+    pub authority: Signer<'info>,
+    
+    /// Admin roles account
+    #[account(
+        mut,
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.super_admin == authority.key() @ CaRegistrarError::NotProgramAuthority
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+    
+    /// New holder of the role
+    pub new_holder: SystemAccount<'info>,
+}
+
+/// Assign an admin role to a new key
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `role` - Role to assign
+pub fn update_role_handler(
+    context: Context<UpdateRoleAccountConstraints>,
+    role: AdminRole,
+) -> Result<()> {
+    let admin_roles = &mut context.accounts.admin_roles;
+    let new_holder = context.accounts.new_holder.key();
+    
+    // Update role holder
+    let previous_holder = admin_roles.holder(role);
+    admin_roles.set_holder(role, new_holder);
+    
+    emit_event!(context, RoleUpdated {
+        role,
+        previous_holder,
+        new_holder,
+        authority: context.accounts.authority.key(),
+        timestamp: get_current_timestamp()?,
+    });
+    
+    msg!("Role {:?} assigned to: {}", role, new_holder);
+    
+    Ok(())
+}
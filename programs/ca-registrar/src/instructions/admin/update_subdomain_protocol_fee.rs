@@ -0,0 +1,76 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::admin::utils::*;
+
+/// Account constraints for updating subdomain protocol fee instruction
+/// 
+/// This instruction allows the treasurer to queue an update of the protocol share of paid subdomain registrations.
+/// The new fee takes effect once the timelock delay has elapsed and execute_queued_change is called, so parent
+/// owners can adjust their prices before it applies.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateSubdomainProtocolFeeAccountConstraints<'info> {
+    /// Treasurer, must hold the Treasurer role in AdminRoles
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Program state account
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump,
+        constraint = admin_roles.has_role(AdminRole::Treasurer, &authority.key()) @ CaRegistrarError::MissingAdminRole
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Queued change account (PDA), publicly visible until executed or cancelled
+    #[account(
+        init,
+        payer = authority,
+        space = ANCHOR_DISCRIMINATOR + QueuedChange::INIT_SPACE,
+        seeds = [QUEUED_CHANGE_SEED, &program_state.queued_changes_count.to_le_bytes()],
+        bump
+    )]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a subdomain protocol fee update
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `fee_bps` - New protocol fee in basis points
+/// 
+/// # Errors
+/// * `InvalidProtocolFee` - Fee exceeds 2000 basis points
+pub fn update_subdomain_protocol_fee_handler(
+    context: Context<UpdateSubdomainProtocolFeeAccountConstraints>,
+    fee_bps: u16,
+) -> Result<()> {
+    // Queue subdomain protocol fee update
+    queue_config_change(
+        &mut context.accounts.queued_change,
+        &mut context.accounts.program_state,
+        ConfigChange::SubdomainProtocolFee { subdomain_protocol_fee_bps: fee_bps },
+        context.accounts.authority.key(),
+        context.bumps.queued_change,
+        &event_cpi_context!(context),
+    )?;
+    
+    msg!("Subdomain protocol fee update to {} basis points queued", fee_bps);
+    
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::error::CaRegistrarError;
+use crate::events::*;
+use crate::instructions::utils::*;
+
+/// Validate configuration bounds before they are stored
+pub fn validate_config_bounds(bounds: &ConfigBounds) -> Result<()> {
+    require!(
+        bounds.min_base_price_usd > 0 && bounds.min_base_price_usd <= bounds.max_base_price_usd,
+        CaRegistrarError::InvalidConfigBounds
+    );
+    require!(
+        bounds.min_grace_period_seconds >= 0
+            && bounds.min_grace_period_seconds <= bounds.max_grace_period_seconds
+            && bounds.max_grace_period_seconds <= MAX_GRACE_PERIOD_SECONDS_LIMIT,
+        CaRegistrarError::InvalidConfigBounds
+    );
+    
+    Ok(())
+}
+
+/// Validate a parameter change against the configured bounds before it is queued or applied
+pub fn validate_config_change(bounds: &ConfigBounds, change: &ConfigChange) -> Result<()> {
+    match *change {
+        ConfigChange::BasePrice { base_price_usd } => {
+            require!(
+                bounds.contains_base_price(base_price_usd),
+                CaRegistrarError::BasePriceOutOfBounds
+            );
+        }
+        ConfigChange::GracePeriod { grace_period_seconds } => {
+            require!(
+                bounds.contains_grace_period(grace_period_seconds),
+                CaRegistrarError::GracePeriodOutOfBounds
+            );
+        }
+        ConfigChange::TimelockDelay { timelock_delay_seconds } => {
+            require!(
+                (0..=MAX_TIMELOCK_DELAY_SECONDS).contains(&timelock_delay_seconds),
+                CaRegistrarError::InvalidTimelockDelay
+            );
+        }
+        ConfigChange::RegistrationHorizon { max_registration_horizon_seconds } => {
+            require!(
+                (MIN_REGISTRATION_HORIZON_SECONDS..=MAX_REGISTRATION_HORIZON_SECONDS)
+                    .contains(&max_registration_horizon_seconds),
+                CaRegistrarError::InvalidRegistrationHorizon
+            );
+        }
+        ConfigChange::MinRegistrationDuration { min_registration_seconds } => {
+            require!(
+                (MIN_REGISTRATION_SECONDS_LOWER_LIMIT..=MIN_REGISTRATION_SECONDS_UPPER_LIMIT)
+                    .contains(&min_registration_seconds),
+                CaRegistrarError::InvalidMinRegistrationDuration
+            );
+        }
+        ConfigChange::SubdomainProtocolFee { subdomain_protocol_fee_bps } => {
+            require!(
+                subdomain_protocol_fee_bps <= MAX_SUBDOMAIN_PROTOCOL_FEE_BPS,
+                CaRegistrarError::InvalidProtocolFee
+            );
+        }
+        ConfigChange::DisputeTimelock { .. }
+        | ConfigChange::SuspendDomain { .. }
+        | ConfigChange::ForceTransferDomain { .. } => {}
+    }
+    
+    Ok(())
+}
+
+/// Queue a parameter change, effective once the current timelock delay has elapsed
+pub fn queue_config_change(
+    queued_change: &mut QueuedChange,
+    program_state: &mut ProgramState,
+    change: ConfigChange,
+    proposer: Pubkey,
+    bump: u8,
+    ctx: &EventCpiContext,
+) -> Result<()> {
+    validate_config_change(&program_state.config_bounds, &change)?;
+    
+    let current_timestamp = get_current_timestamp()?;
+    let effective_timestamp = current_timestamp
+        .checked_add(program_state.timelock_delay_seconds)
+        .ok_or(error!(CaRegistrarError::MathOverflow))?;
+    
+    queued_change.change_id = program_state.queued_changes_count;
+    queued_change.change = change;
+    queued_change.proposer = proposer;
+    queued_change.queued_timestamp = current_timestamp;
+    queued_change.effective_timestamp = effective_timestamp;
+    queued_change.bump = bump;
+    
+    program_state.queued_changes_count = program_state.queued_changes_count
+        .checked_add(1)
+        .ok_or(error!(CaRegistrarError::MathOverflow))?;
+    
+    emit_cpi!(ConfigChangeQueued {
+        change_id: queued_change.change_id,
+        change,
+        proposer,
+        effective_timestamp,
+        timestamp: current_timestamp,
+    });
+    
+    msg!("Queued change {} effective at {}", queued_change.change_id, effective_timestamp);
+    
+    Ok(())
+}
+
+/// Apply a parameter change to the program state, or a dispute action to the targeted domain record
+/// 
+/// `authority` is the admin who decided the change, recorded in the emitted events
+pub fn apply_config_change(
+    program_state: &mut ProgramState,
+    domain_record: Option<&mut Account<DomainRecord>>,
+    change: &ConfigChange,
+    authority: Pubkey,
+    ctx: &EventCpiContext,
+) -> Result<()> {
+    validate_config_change(&program_state.config_bounds, change)?;
+    
+    // Dispute actions need the domain record they target
+    if let Some(target) = change.target_domain_record() {
+        let domain_record = domain_record.ok_or(error!(CaRegistrarError::DomainRecordMismatch))?;
+        require_keys_eq!(domain_record.key(), target, CaRegistrarError::DomainRecordMismatch);
+        return apply_dispute_action(domain_record, change, authority, ctx);
+    }
+    
+    match *change {
+        ConfigChange::BasePrice { base_price_usd } => {
+            emit_cpi!(PriceUpdated {
+                previous_base_price_usd: program_state.base_price_usd,
+                new_base_price_usd: base_price_usd,
+                authority,
+                timestamp: get_current_timestamp()?,
+            });
+            program_state.base_price_usd = base_price_usd;
+            msg!("Base price updated to: {} USD cents", base_price_usd);
+        }
+        ConfigChange::GracePeriod { grace_period_seconds } => {
+            emit_cpi!(GracePeriodUpdated {
+                previous_grace_period_seconds: program_state.grace_period_seconds,
+                new_grace_period_seconds: grace_period_seconds,
+                authority,
+                timestamp: get_current_timestamp()?,
+            });
+            program_state.grace_period_seconds = grace_period_seconds;
+            msg!("Grace period updated to: {} seconds", grace_period_seconds);
+        }
+        ConfigChange::TimelockDelay { timelock_delay_seconds } => {
+            program_state.timelock_delay_seconds = timelock_delay_seconds;
+            msg!("Timelock delay updated to: {} seconds", timelock_delay_seconds);
+        }
+        ConfigChange::DisputeTimelock { enabled } => {
+            program_state.dispute_timelock_enabled = enabled;
+            msg!("Dispute timelock enabled: {}", enabled);
+        }
+        ConfigChange::RegistrationHorizon { max_registration_horizon_seconds } => {
+            program_state.max_registration_horizon_seconds = max_registration_horizon_seconds;
+            msg!("Registration horizon updated to: {} seconds", max_registration_horizon_seconds);
+        }
+        ConfigChange::MinRegistrationDuration { min_registration_seconds } => {
+            program_state.min_registration_seconds = min_registration_seconds;
+            msg!("Minimum registration duration updated to: {} seconds", min_registration_seconds);
+        }
+        ConfigChange::SubdomainProtocolFee { subdomain_protocol_fee_bps } => {
+            program_state.subdomain_protocol_fee_bps = subdomain_protocol_fee_bps;
+            msg!("Subdomain protocol fee updated to: {} basis points", subdomain_protocol_fee_bps);
+        }
+        ConfigChange::SuspendDomain { .. } | ConfigChange::ForceTransferDomain { .. } => {}
+    }
+    
+    Ok(())
+}
+
+/// Apply a suspension or force-transfer to a domain record and emit its audit event
+pub fn apply_dispute_action(
+    domain_record: &mut DomainRecord,
+    change: &ConfigChange,
+    authority: Pubkey,
+    ctx: &EventCpiContext,
+) -> Result<()> {
+    let timestamp = get_current_timestamp()?;
+    
+    match *change {
+        ConfigChange::SuspendDomain { suspended, reason_code, .. } => {
+            domain_record.suspended = suspended;
+            
+            emit_cpi!(DomainSuspensionUpdated {
+                domain_name: domain_record.domain_name.clone(),
+                suspended,
+                reason_code,
+                authority,
+                timestamp,
+            });
+            msg!("Domain {} suspended: {} (reason {})", domain_record.domain_name, suspended, reason_code);
+        }
+        ConfigChange::ForceTransferDomain { registration_generation, current_owner, new_owner, reason_code, .. } => {
+            // The decision applies to the registration and owner it was taken against, not to a later one
+            require!(
+                domain_record.registration_generation == registration_generation
+                    && domain_record.owner == current_owner,
+                CaRegistrarError::DomainRecordMismatch
+            );
+            
+            let previous_owner = domain_record.owner;
+            domain_record.owner = new_owner;
+            
+            emit_cpi!(DomainForceTransferred {
+                domain_name: domain_record.domain_name.clone(),
+                previous_owner,
+                new_owner,
+                reason_code,
+                authority,
+                timestamp,
+            });
+            msg!("Domain {} force-transferred to {} (reason {})", domain_record.domain_name, new_owner, reason_code);
+        }
+        _ => return err!(CaRegistrarError::DomainRecordMismatch),
+    }
+    
+    Ok(())
+}
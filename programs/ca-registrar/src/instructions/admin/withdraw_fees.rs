@@ -2,24 +2,28 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::state::*;
+use crate::events::*;
 use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
 
 /// Account constraints for withdrawing fees instruction
 /// 
 /// This instruction allows anyone to withdraw SOL from the program state account.
-/// The withdrawn funds will be sent to the authority account.
+/// The withdrawn funds are split between the configured fee destinations, which must be passed as writable
+/// remaining accounts in the configured order. The treasurer account receives the unallocated remainder.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct WithdrawFeesAccountConstraints<'info> {
     /// caller - can be anyone
     #[account(mut)]
     pub signer: Signer<'info>,
     
-    /// fee receiver - must be the authority in ProgramState
+    /// fee receiver - must be the treasurer in AdminRoles
     #[account(
         mut, 
-        address = program_state.authority
+        address = admin_roles.treasurer
     )]
-    pub authority: SystemAccount<'info>,
+    pub treasurer: SystemAccount<'info>,
     
     /// Program state account
     #[account(
@@ -28,17 +32,27 @@ pub struct WithdrawFeesAccountConstraints<'info> {
         bump = program_state.bump
     )]
     pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
     
     pub system_program: Program<'info, System>,
 }
 
 /// Withdraw all available SOL from program state account while preserving rent-exempt amount
+/// 
+/// # Errors
+/// * `FeeDestinationMismatch` - Remaining accounts do not match the configured fee destinations
 pub fn withdraw_fees_handler(
     context: Context<WithdrawFeesAccountConstraints>,
 ) -> Result<()> {
     // Get account infos
     let program_state_info = context.accounts.program_state.to_account_info();
-    let authority_info = context.accounts.authority.to_account_info();
+    let treasurer_info = context.accounts.treasurer.to_account_info();
 
     // Calculate minimum rent-exempt balance needed
     let rent = Rent::get()?;
@@ -53,10 +67,43 @@ pub fn withdraw_fees_handler(
     // Verify there are funds to withdraw
     require!(withdraw_amount > 0, CaRegistrarError::InsufficientPayment);
 
+    // Verify fee destination accounts match the configured split
+    let fee_destinations = &context.accounts.program_state.fee_destinations;
+    require!(
+        context.remaining_accounts.len() == fee_destinations.len(),
+        CaRegistrarError::FeeDestinationMismatch
+    );
+
     // Transfer all available balance except rent-exempt amount
     **program_state_info.try_borrow_mut_lamports()? -= withdraw_amount;
-    **authority_info.try_borrow_mut_lamports()? += withdraw_amount;
 
-    msg!("Withdrew {} lamports to authority", withdraw_amount);
+    // Pay each destination its share, the treasurer receives the remainder
+    let mut treasurer_amount = withdraw_amount;
+    let mut destination_amounts = Vec::with_capacity(fee_destinations.len());
+    for (fee_destination, destination_info) in fee_destinations.iter().zip(context.remaining_accounts) {
+        require!(
+            destination_info.key() == fee_destination.wallet && destination_info.is_writable,
+            CaRegistrarError::FeeDestinationMismatch
+        );
+
+        let share = calculate_share(withdraw_amount, fee_destination.share_bps)?;
+        **destination_info.try_borrow_mut_lamports()? += share;
+        treasurer_amount -= share;
+        destination_amounts.push(share);
+
+        msg!("Withdrew {} lamports to {}", share, fee_destination.wallet);
+    }
+    **treasurer_info.try_borrow_mut_lamports()? += treasurer_amount;
+
+    emit_event!(context, FeesWithdrawn {
+        caller: context.accounts.signer.key(),
+        treasurer: context.accounts.treasurer.key(),
+        total_lamports: withdraw_amount,
+        destination_lamports: destination_amounts,
+        treasurer_lamports: treasurer_amount,
+        timestamp: get_current_timestamp()?,
+    });
+
+    msg!("Withdrew {} lamports to treasurer", treasurer_amount);
     Ok(())
 }
@@ -10,4 +10,10 @@ mod update;
 pub use transfer::*;
 mod transfer;
 
+pub use release::*;
+mod release;
+
+pub use subdomain::*;
+mod subdomain;
+
 pub mod utils; 
\ No newline at end of file
@@ -1,9 +1,10 @@
 #![allow(unexpected_cfgs)]
 
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{self, transfer};
+use anchor_lang::solana_program::hash::hash;
 use crate::constants::*;
 use crate::state::*;
+use crate::events::*;
 use crate::instructions::utils::*;
 use crate::instructions::register::utils::*;
 use crate::error::CaRegistrarError;
@@ -13,6 +14,7 @@ use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 /// 
 /// This instruction allows users to purchase domains that have expired and are beyond the grace period.
 /// This operation completely resets domain ownership and clears all address records.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(domain_name: String)]
 pub struct BuyDomainAccountConstraints<'info> {
@@ -25,9 +27,18 @@ pub struct BuyDomainAccountConstraints<'info> {
         mut,
         seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
         bump = domain_record.bump,
+        constraint = !domain_record.suspended @ CaRegistrarError::DomainSuspended,
     )]
     pub domain_record: Account<'info, DomainRecord>,
 
+    /// Reserved name PDA for the domain name, must not be initialized
+    /// CHECK: only checked for emptiness, address is derived from the hash of the domain name
+    #[account(
+        seeds = [RESERVED_NAME_SEED, &hash(domain_name.as_bytes()).to_bytes()],
+        bump
+    )]
+    pub reserved_name: UncheckedAccount<'info>,
+
     /// Program state account
     #[account(
         mut,
@@ -36,10 +47,29 @@ pub struct BuyDomainAccountConstraints<'info> {
     )]
     pub program_state: Account<'info, ProgramState>,
 
+    /// Pricing configuration account, holding the multi-year discount schedule
+    #[account(
+        seeds = [PRICE_CONFIG_SEED],
+        bump = price_config.bump
+    )]
+    pub price_config: Account<'info, PriceConfig>,
+
     /// Pyth price oracle account (SOL/USD price)
     /// Used to calculate accurate SOL amounts
     pub pyth_price_update: Account<'info, PriceUpdateV2>,
 
+    /// Optional referrer config, a share of the fee is paid to its referrer
+    #[account(
+        mut,
+        seeds = [REFERRER_CONFIG_SEED, referrer_config.referrer.as_ref()],
+        bump = referrer_config.bump
+    )]
+    pub referrer_config: Option<Account<'info, ReferrerConfig>>,
+
+    /// Optional referrer wallet, must match the referrer config
+    #[account(mut)]
+    pub referrer: Option<SystemAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -47,20 +77,31 @@ pub struct BuyDomainAccountConstraints<'info> {
 /// 
 /// # Parameters
 /// * `context` - Instruction context, containing all relevant accounts
-/// * `years` - Purchase period in years, minimum 1 year
+/// * `period` - Purchase period, at least the minimum registration duration
 /// * `addresses` - List of blockchain addresses to set for the domain
 /// * `owner` - Owner of the domain, can be any public key, not necessarily the transaction signer
 /// 
 /// # Errors
-/// * `InvalidRegisterYears` - Registration period is invalid
+/// * `InvalidRegisterYears` - Registration period is empty
+/// * `RegistrationPeriodTooShort` - Registration period is shorter than the minimum registration duration
+/// * `RegistrationHorizonExceeded` - Expiry would be further ahead than the maximum registration horizon
+/// * `DomainNameReserved` - Domain name is on the reserved names list
+/// * `DomainSuspended` - Domain is suspended
+/// * `InvalidReferrer` - Referrer wallet does not match the referrer config, or only one of them is supplied
 pub fn buy_domain_handler(
     context: Context<BuyDomainAccountConstraints>,
-    years: u64,
+    period: RegistrationPeriod,
     addresses: Vec<ChainAddress>,
     owner: Pubkey,
 ) -> Result<()> {
-    // Validate years
-    require!(years > 0 && years <= 99, CaRegistrarError::InvalidRegisterYears);
+    // Registrations are blocked while the program is paused
+    require!(!context.accounts.program_state.paused, CaRegistrarError::ProgramPaused);
+
+    // Reserved names can only be assigned by the reserved-name manager
+    ensure_name_not_reserved(&context.accounts.reserved_name)?;
+
+    // Validate period
+    require!(!period.is_empty(), CaRegistrarError::InvalidRegisterYears);
 
     // Get current timestamp
     let current_timestamp = get_current_timestamp()?;
@@ -83,32 +124,59 @@ pub fn buy_domain_handler(
     );
     
     // Calculate fee using Pyth oracle
-    let yearly_fee = calculate_yearly_fee_in_lamports(
-        &context.accounts.pyth_price_update,
-        context.accounts.program_state.base_price_usd, 
-        years,
+    let price_usd = calculate_price_usd(
+        context.accounts.program_state.base_price_usd,
+        &period,
+        context.accounts.price_config.multi_year_discount_bps(period.whole_years()),
+        None,
     )?;
+    let (fee, oracle_price) = calculate_fee_in_lamports(&context.accounts.pyth_price_update, price_usd)?;
     
-    // Transfer fee to program state account
-    transfer(
-        CpiContext::new(
-            context.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: context.accounts.buyer.to_account_info(),
-                to: context.accounts.program_state.to_account_info(),
-            },
-        ),
-        yearly_fee,
+    // Transfer fee to program state account, paying the referral share if a referrer is supplied
+    let referral_fee = collect_registration_fee(
+        context.accounts.buyer.to_account_info(),
+        context.accounts.program_state.to_account_info(),
+        context.accounts.system_program.to_account_info(),
+        context.accounts.program_state.default_referral_share_bps,
+        context.accounts.referrer_config.as_mut(),
+        context.accounts.referrer.as_ref(),
+        fee,
     )?;
 
     // Reset and update domain record
+    let previous_owner = domain_record.owner;
+    let previous_expiry_timestamp = domain_record.expiry_timestamp;
     domain_record.owner = owner;
     domain_record.registration_timestamp = current_timestamp;
-    domain_record.expiry_timestamp = calculate_expiry_timestamp(current_timestamp, years);
+    domain_record.registration_generation = next_registration_generation(&mut context.accounts.program_state)?;
+    domain_record.expiry_timestamp = calculate_expiry_timestamp(
+        &context.accounts.program_state,
+        current_timestamp,
+        current_timestamp,
+        &period,
+    )?;
     domain_record.addresses = addresses; 
+    domain_record.fuses = 0;
+    domain_record.paid_lamports = fee - referral_fee;
+    domain_record.paid_timestamp = current_timestamp;
+
+    emit_event!(context, DomainBought {
+        domain_name: domain_record.domain_name.clone(),
+        previous_owner,
+        new_owner: owner,
+        buyer: context.accounts.buyer.key(),
+        addresses: domain_record.addresses.clone(),
+        price_usd,
+        lamports_paid: fee,
+        referral_lamports: referral_fee,
+        oracle_price,
+        previous_expiry_timestamp,
+        expiry_timestamp: domain_record.expiry_timestamp,
+        timestamp: current_timestamp,
+    });
 
-    msg!("Domain {} purchased successfully for {} years with owner {}", 
-        domain_record.domain_name, years, owner);
+    msg!("Domain {} purchased successfully for {:?} with owner {}", 
+        domain_record.domain_name, period, owner);
     
     Ok(())
 } 
\ No newline at end of file
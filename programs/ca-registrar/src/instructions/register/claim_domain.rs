@@ -0,0 +1,159 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::register::utils::*;
+
+/// Account constraints for airdrop domain claim instruction
+/// 
+/// This instruction creates a pre-allocated domain from the airdrop Merkle tree stored in the program state.
+/// It can be submitted by the owner or by anyone on their behalf, the payer only covers rent. Allocated names are
+/// reserved until they are claimed, so the public cannot register them first.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain_name: String, owner: Pubkey, expiry_timestamp: i64)]
+pub struct ClaimDomainAccountConstraints<'info> {
+    /// User paying for account rent, not necessarily the owner
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Account storing domain information, using the domain name as a seed for PDA derivation
+    #[account(
+        init,
+        payer = payer,
+        space = ANCHOR_DISCRIMINATOR + DomainRecord::INIT_SPACE,
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump
+    )]
+    pub domain_record: Account<'info, DomainRecord>,
+
+    /// Reserved name PDA for the domain name, its reservation is lifted by the claim
+    /// CHECK: only closed if initialized, address is derived from the hash of the domain name
+    #[account(
+        mut,
+        seeds = [RESERVED_NAME_SEED, &hash(domain_name.as_bytes()).to_bytes()],
+        bump
+    )]
+    pub reserved_name: UncheckedAccount<'info>,
+
+    /// Marker of the claimed leaf, its creation fails if the leaf was already claimed
+    #[account(
+        init,
+        payer = payer,
+        space = ANCHOR_DISCRIMINATOR + ClaimedLeaf::INIT_SPACE,
+        seeds = [
+            CLAIMED_LEAF_SEED,
+            &hash_merkle_leaf(&[domain_name.as_bytes(), owner.as_ref(), &expiry_timestamp.to_le_bytes()])
+        ],
+        bump
+    )]
+    pub claimed_leaf: Account<'info, ClaimedLeaf>,
+
+    /// Program state account, storing global configuration and statistics
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Solana system program, used for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Airdrop domain claim instruction handler
+/// 
+/// The domain follows the regular lifecycle from the allocated expiry, an allocation that already
+/// expired is created in its grace period or as an expired domain. The reservation protecting the
+/// name until the claim is lifted, and its rent goes to the payer.
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `domain_name` - Name of the allocated domain (without .ca suffix)
+/// * `owner` - Owner of the allocated domain
+/// * `expiry_timestamp` - Expiry timestamp of the allocated domain
+/// * `proof` - Merkle proof of the (domain name, owner, expiry) leaf
+/// 
+/// # Errors
+/// * `InvalidDomainLength` - Domain name length is invalid (empty or longer than 253 characters)
+/// * `DomainNameNotLowercase` - Domain name contains uppercase characters
+/// * `ProgramPaused` - Registrations are paused
+/// * `InvalidMerkleProof` - The allocation is not part of the airdrop tree
+/// * `RegistrationHorizonExceeded` - Expiry of the allocation is further ahead than the maximum registration horizon
+pub fn claim_domain_handler(
+    context: Context<ClaimDomainAccountConstraints>,
+    domain_name: String,
+    owner: Pubkey,
+    expiry_timestamp: i64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    // verify domain name
+    validate_domain_name(&domain_name)?;
+
+    // Claims are blocked while the program is paused
+    require!(!context.accounts.program_state.paused, CaRegistrarError::ProgramPaused);
+
+    // Verify the allocation is part of the airdrop tree
+    let leaf = hash_merkle_leaf(&[domain_name.as_bytes(), owner.as_ref(), &expiry_timestamp.to_le_bytes()]);
+    require!(
+        verify_merkle_proof(&proof, &context.accounts.program_state.airdrop_merkle_root, leaf),
+        CaRegistrarError::InvalidMerkleProof
+    );
+
+    // get current timestamp
+    let current_timestamp = get_current_timestamp()?;
+
+    // Allocations are bound by the registration horizon like any registration, they may already be expired
+    ensure_within_horizon(&context.accounts.program_state, current_timestamp, expiry_timestamp)?;
+
+    // Update domain record
+    let registration_generation = next_registration_generation(&mut context.accounts.program_state)?;
+    let domain_record = &mut context.accounts.domain_record;
+    write_new_domain_record(
+        domain_record,
+        domain_name,
+        owner,
+        Vec::new(),
+        current_timestamp,
+        registration_generation,
+        expiry_timestamp,
+        0,
+        context.bumps.domain_record,
+    );
+
+    // The name no longer needs protecting from public registration
+    lift_reservation(&context.accounts.reserved_name, &context.accounts.payer.to_account_info())?;
+
+    // Mark the leaf as claimed
+    let claimed_leaf = &mut context.accounts.claimed_leaf;
+    claimed_leaf.leaf = leaf;
+    claimed_leaf.domain_record = domain_record.key();
+    claimed_leaf.bump = context.bumps.claimed_leaf;
+
+    // Update program state
+    let program_state = &mut context.accounts.program_state;
+    program_state.domains_registered += 1;
+
+    emit_event!(context, DomainRegistered {
+        domain_name: domain_record.domain_name.clone(),
+        owner,
+        payer: context.accounts.payer.key(),
+        addresses: Vec::new(),
+        price_usd: 0,
+        lamports_paid: 0,
+        referral_lamports: 0,
+        oracle_price: None,
+        expiry_timestamp,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Domain {} claimed from airdrop for owner {} until {}", 
+        domain_record.domain_name, owner, expiry_timestamp);
+    
+    Ok(())
+}
@@ -0,0 +1,171 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::register::utils::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+/// Account constraints for sunrise domain claim instruction
+/// 
+/// This instruction allows an allowlisted claimant to register their domain before general availability.
+/// The (domain name, claimant) pair must be included in the sunrise Merkle tree stored in the program state.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain_name: String)]
+pub struct ClaimSunriseDomainAccountConstraints<'info> {
+    /// Designated claimant paying for domain registration fees, who will also be the owner
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    /// Account storing domain information, using the domain name as a seed for PDA derivation
+    #[account(
+        init,
+        payer = claimant,
+        space = ANCHOR_DISCRIMINATOR + DomainRecord::INIT_SPACE,
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump
+    )]
+    pub domain_record: Account<'info, DomainRecord>,
+
+    /// Reserved name PDA for the domain name, must not be initialized
+    /// CHECK: only checked for emptiness, address is derived from the hash of the domain name
+    #[account(
+        seeds = [RESERVED_NAME_SEED, &hash(domain_name.as_bytes()).to_bytes()],
+        bump
+    )]
+    pub reserved_name: UncheckedAccount<'info>,
+
+    /// Program state account, storing global configuration and statistics
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Pricing configuration account, holding the multi-year discount schedule
+    #[account(
+        seeds = [PRICE_CONFIG_SEED],
+        bump = price_config.bump
+    )]
+    pub price_config: Account<'info, PriceConfig>,
+
+    /// Pyth price oracle account (SOL/USD price)
+    /// Used to calculate accurate SOL amounts
+    pub pyth_price_update: Account<'info, PriceUpdateV2>,
+
+    /// Solana system program, used for transfer operations
+    pub system_program: Program<'info, System>,
+}
+
+/// Sunrise domain claim instruction handler
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `domain_name` - Name of the domain to claim (without .ca suffix)
+/// * `period` - Registration period, at least the minimum registration duration
+/// * `addresses` - List of blockchain addresses to set for the domain
+/// * `proof` - Merkle proof of the (domain name, claimant) leaf
+/// 
+/// # Errors
+/// * `InvalidDomainLength` - Domain name length is invalid (empty or longer than 253 characters)
+/// * `DomainNameNotLowercase` - Domain name contains uppercase characters
+/// * `InvalidRegisterYears` - Registration period is empty
+/// * `RegistrationPeriodTooShort` - Registration period is shorter than the minimum registration duration
+/// * `RegistrationHorizonExceeded` - Expiry would be further ahead than the maximum registration horizon
+/// * `ProgramPaused` - Registrations are paused
+/// * `DomainNameReserved` - Domain name is on the reserved names list
+/// * `SunriseEnded` - General availability has already started
+/// * `InvalidMerkleProof` - The claim is not part of the sunrise allowlist
+pub fn claim_sunrise_domain_handler(
+    context: Context<ClaimSunriseDomainAccountConstraints>,
+    domain_name: String,
+    period: RegistrationPeriod,
+    addresses: Vec<ChainAddress>,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    // verify domain name, pause flag and period
+    validate_registration(&context.accounts.program_state, &domain_name, &period)?;
+
+    // Reserved names can only be assigned by the reserved-name manager
+    ensure_name_not_reserved(&context.accounts.reserved_name)?;
+
+    // get current timestamp
+    let current_timestamp = get_current_timestamp()?;
+
+    // Claims are only accepted before general availability
+    require!(
+        current_timestamp < context.accounts.program_state.general_availability_timestamp,
+        CaRegistrarError::SunriseEnded
+    );
+
+    // Verify the claim is on the allowlist
+    let claimant = context.accounts.claimant.key();
+    let leaf = hash_merkle_leaf(&[domain_name.as_bytes(), claimant.as_ref()]);
+    require!(
+        verify_merkle_proof(&proof, &context.accounts.program_state.sunrise_merkle_root, leaf),
+        CaRegistrarError::InvalidMerkleProof
+    );
+
+    // Calculate fee using Pyth oracle
+    let price_usd = calculate_price_usd(
+        context.accounts.program_state.base_price_usd,
+        &period,
+        context.accounts.price_config.multi_year_discount_bps(period.whole_years()),
+        None,
+    )?;
+    let (fee, oracle_price) = calculate_fee_in_lamports(&context.accounts.pyth_price_update, price_usd)?;
+    
+    // Transfer fee to program state account
+    collect_registration_fee(
+        context.accounts.claimant.to_account_info(),
+        context.accounts.program_state.to_account_info(),
+        context.accounts.system_program.to_account_info(),
+        context.accounts.program_state.default_referral_share_bps,
+        None,
+        None,
+        fee,
+    )?;
+
+    // Update domain record
+    let registration_generation = next_registration_generation(&mut context.accounts.program_state)?;
+    let domain_record = &mut context.accounts.domain_record;
+    write_new_domain_record(
+        domain_record,
+        domain_name,
+        claimant,
+        addresses,
+        current_timestamp,
+        registration_generation,
+        calculate_expiry_timestamp(&context.accounts.program_state, current_timestamp, current_timestamp, &period)?,
+        fee,
+        context.bumps.domain_record,
+    );
+
+    // Update program state
+    let program_state = &mut context.accounts.program_state;
+    program_state.domains_registered += 1;
+
+    emit_event!(context, DomainRegistered {
+        domain_name: domain_record.domain_name.clone(),
+        owner: claimant,
+        payer: claimant,
+        addresses: domain_record.addresses.clone(),
+        price_usd,
+        lamports_paid: fee,
+        referral_lamports: 0,
+        oracle_price: Some(oracle_price),
+        expiry_timestamp: domain_record.expiry_timestamp,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Domain {} claimed during sunrise for {:?} by {}", 
+        domain_record.domain_name, period, claimant);
+    
+    Ok(())
+}
@@ -1,4 +1,4 @@
-mod utils;
+pub(crate) mod utils;
 
 pub use register_domain::*;
 mod register_domain;
@@ -7,5 +7,13 @@ pub use renew_domain::*;
 mod renew_domain;
 
 pub use buy_domain::*;
-mod buy_domain; 
+mod buy_domain;
 
+pub use register_domain_with_voucher::*;
+mod register_domain_with_voucher;
+
+pub use claim_sunrise_domain::*;
+mod claim_sunrise_domain;
+
+pub use claim_domain::*;
+mod claim_domain;
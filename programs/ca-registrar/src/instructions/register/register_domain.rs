@@ -1,18 +1,19 @@
 #![allow(unexpected_cfgs)]
 
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{self, transfer};
+use anchor_lang::solana_program::hash::hash;
 use crate::constants::*;
 use crate::state::*;
+use crate::events::*;
 use crate::instructions::utils::*;
 use crate::instructions::register::utils::*;
-use crate::error::CaRegistrarError;
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
 /// Account constraints for domain registration instruction
 /// 
 /// This instruction allows users to register a new .ca domain, provided the domain has never been registered before.
 /// If the domain has been registered before but has expired, the buy_domain instruction must be used.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(domain_name: String)]
 pub struct RegisterDomainAccountConstraints<'info> {
@@ -30,6 +31,14 @@ pub struct RegisterDomainAccountConstraints<'info> {
     )]
     pub domain_record: Account<'info, DomainRecord>,
 
+    /// Reserved name PDA for the domain name, must not be initialized
+    /// CHECK: only checked for emptiness, address is derived from the hash of the domain name
+    #[account(
+        seeds = [RESERVED_NAME_SEED, &hash(domain_name.as_bytes()).to_bytes()],
+        bump
+    )]
+    pub reserved_name: UncheckedAccount<'info>,
+
     /// Program state account, storing global configuration and statistics
     #[account(
         mut,
@@ -38,10 +47,37 @@ pub struct RegisterDomainAccountConstraints<'info> {
     )]
     pub program_state: Account<'info, ProgramState>,
 
+    /// Pricing configuration account, holding the multi-year discount schedule
+    #[account(
+        seeds = [PRICE_CONFIG_SEED],
+        bump = price_config.bump
+    )]
+    pub price_config: Account<'info, PriceConfig>,
+
     /// Pyth price oracle account (SOL/USD price)
     /// Used to calculate accurate SOL amounts
     pub pyth_price_update: Account<'info, PriceUpdateV2>,
 
+    /// Optional promo code account, must match the promo code argument
+    #[account(
+        mut,
+        seeds = [PROMO_CODE_SEED, promo_code_account.code_hash.as_ref()],
+        bump = promo_code_account.bump
+    )]
+    pub promo_code_account: Option<Account<'info, PromoCode>>,
+
+    /// Optional referrer config, a share of the fee is paid to its referrer
+    #[account(
+        mut,
+        seeds = [REFERRER_CONFIG_SEED, referrer_config.referrer.as_ref()],
+        bump = referrer_config.bump
+    )]
+    pub referrer_config: Option<Account<'info, ReferrerConfig>>,
+
+    /// Optional referrer wallet, must match the referrer config
+    #[account(mut)]
+    pub referrer: Option<SystemAccount<'info>>,
+
     /// Solana system program, used for transfer operations
     pub system_program: Program<'info, System>,
 }
@@ -51,66 +87,105 @@ pub struct RegisterDomainAccountConstraints<'info> {
 /// # Parameters
 /// * `context` - Instruction context, containing all relevant accounts
 /// * `domain_name` - Name of the domain to register (without .ca suffix)
-/// * `years` - Registration period in years, minimum 1 year
+/// * `period` - Registration period, at least the minimum registration duration
 /// * `addresses` - List of blockchain addresses to set for the domain
 /// * `owner` - Owner of the domain, can be any public key, not necessarily the transaction signer
+/// * `promo_code` - Optional promotional code, requires the matching promo code account
 /// 
 /// # Errors
+/// * `InvalidReferrer` - Referrer wallet does not match the referrer config, or only one of them is supplied
 /// * `InvalidDomainLength` - Domain name length is invalid (empty or longer than 253 characters)
-/// * `InvalidRegisterYears` - Registration period is invalid (less than 1 year)
+/// * `DomainNameNotLowercase` - Domain name contains uppercase characters
+/// * `InvalidRegisterYears` - Registration period is empty
+/// * `RegistrationPeriodTooShort` - Registration period is shorter than the minimum registration duration
+/// * `RegistrationHorizonExceeded` - Expiry would be further ahead than the maximum registration horizon
+/// * `ProgramPaused` - Registrations are paused
+/// * `DomainNameReserved` - Domain name is on the reserved names list
+/// * `SunriseActive` - General availability has not started yet
+/// * `InvalidPromoCode` - Promo code does not match the promo code account
+/// * `PromoCodeExpired` - Promo code has expired
+/// * `PromoCodeExhausted` - Promo code has no remaining uses
 pub fn register_domain_handler(
     context: Context<RegisterDomainAccountConstraints>,
     domain_name: String,
-    years: u64,
+    period: RegistrationPeriod,
     addresses: Vec<ChainAddress>,
     owner: Pubkey,
+    promo_code: Option<String>,
 ) -> Result<()> {
-    // verify domain name length: > 0 && <= 253 (from state definition)
-    require!(
-        domain_name.len() > 0 && domain_name.len() <= 253,
-        CaRegistrarError::InvalidDomainLength
-    );
+    // verify domain name, pause flag and period
+    validate_registration(&context.accounts.program_state, &domain_name, &period)?;
 
-    // verify years: > 0 && <= 99 
-    require!(years > 0 && years <= 99, CaRegistrarError::InvalidRegisterYears);
+    // Reserved names can only be assigned by the reserved-name manager
+    ensure_name_not_reserved(&context.accounts.reserved_name)?;
 
     // get current timestamp
     let current_timestamp = get_current_timestamp()?;
 
+    // Only sunrise claims are accepted before general availability
+    ensure_general_availability(&context.accounts.program_state, current_timestamp)?;
+
+    // Redeem promo code, if any
+    let promo_discount = redeem_promo_code(
+        context.accounts.promo_code_account.as_mut(),
+        promo_code,
+        current_timestamp,
+    )?;
+
     // Calculate fee using Pyth oracle
-    let yearly_fee = calculate_yearly_fee_in_lamports(
-        &context.accounts.pyth_price_update,
-        context.accounts.program_state.base_price_usd,  // Pass price value directly
-        years,
+    let price_usd = calculate_price_usd(
+        context.accounts.program_state.base_price_usd,
+        &period,
+        context.accounts.price_config.multi_year_discount_bps(period.whole_years()),
+        promo_discount.as_ref(),
     )?;
+    let (fee, oracle_price) = calculate_fee_in_lamports(&context.accounts.pyth_price_update, price_usd)?;
     
-    // Transfer fee to program state account
-    transfer(
-        CpiContext::new(
-            context.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: context.accounts.buyer.to_account_info(),
-                to: context.accounts.program_state.to_account_info(),
-            },
-        ),
-        yearly_fee,
+    // Transfer fee to program state account, paying the referral share if a referrer is supplied
+    let referral_fee = collect_registration_fee(
+        context.accounts.buyer.to_account_info(),
+        context.accounts.program_state.to_account_info(),
+        context.accounts.system_program.to_account_info(),
+        context.accounts.program_state.default_referral_share_bps,
+        context.accounts.referrer_config.as_mut(),
+        context.accounts.referrer.as_ref(),
+        fee,
     )?;
 
     // Update domain record
+    let registration_generation = next_registration_generation(&mut context.accounts.program_state)?;
     let domain_record = &mut context.accounts.domain_record;
-    domain_record.domain_name = domain_name;
-    domain_record.owner = owner;
-    domain_record.registration_timestamp = current_timestamp;
-    domain_record.expiry_timestamp = calculate_expiry_timestamp(current_timestamp, years);
-    domain_record.addresses = addresses;
-    domain_record.bump = context.bumps.domain_record;
+    write_new_domain_record(
+        domain_record,
+        domain_name,
+        owner,
+        addresses,
+        current_timestamp,
+        registration_generation,
+        calculate_expiry_timestamp(&context.accounts.program_state, current_timestamp, current_timestamp, &period)?,
+        fee - referral_fee,
+        context.bumps.domain_record,
+    );
 
     // Update program state
     let program_state = &mut context.accounts.program_state;
     program_state.domains_registered += 1;
 
-    msg!("Domain {} registered successfully for {} years with owner {}", 
-        domain_record.domain_name, years, owner);
+    emit_event!(context, DomainRegistered {
+        domain_name: domain_record.domain_name.clone(),
+        owner,
+        payer: context.accounts.buyer.key(),
+        addresses: domain_record.addresses.clone(),
+        price_usd,
+        lamports_paid: fee,
+        referral_lamports: referral_fee,
+        oracle_price: Some(oracle_price),
+        expiry_timestamp: domain_record.expiry_timestamp,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Domain {} registered successfully for {:?} with owner {}", 
+        domain_record.domain_name, period, owner);
     
     Ok(())
 } 
\ No newline at end of file
@@ -0,0 +1,226 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::sysvar;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::instructions::utils::*;
+use crate::instructions::register::utils::*;
+use crate::error::CaRegistrarError;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+/// Account constraints for voucher domain registration instruction
+/// 
+/// This instruction registers a new .ca domain like register_domain, with the price discounted by a voucher
+/// signed off-chain by the voucher signer. The transaction must contain an Ed25519 program instruction
+/// verifying the voucher signature immediately before this instruction.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain_name: String, period: RegistrationPeriod, addresses: Vec<ChainAddress>, owner: Pubkey, voucher: Voucher)]
+pub struct RegisterDomainWithVoucherAccountConstraints<'info> {
+    /// User redeeming the voucher and paying any remaining fee, must be the buyer named in the voucher
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Account storing domain information, using the domain name as a seed for PDA derivation
+    #[account(
+        init,
+        payer = buyer,
+        space = ANCHOR_DISCRIMINATOR + DomainRecord::INIT_SPACE,
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump
+    )]
+    pub domain_record: Account<'info, DomainRecord>,
+
+    /// Reserved name PDA for the domain name, must not be initialized
+    /// CHECK: only checked for emptiness, address is derived from the hash of the domain name
+    #[account(
+        seeds = [RESERVED_NAME_SEED, &hash(domain_name.as_bytes()).to_bytes()],
+        bump
+    )]
+    pub reserved_name: UncheckedAccount<'info>,
+
+    /// Program state account, storing global configuration and statistics
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account, holding the voucher signer
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Pricing configuration account, holding the multi-year discount schedule
+    #[account(
+        seeds = [PRICE_CONFIG_SEED],
+        bump = price_config.bump
+    )]
+    pub price_config: Account<'info, PriceConfig>,
+
+    /// Pyth price oracle account (SOL/USD price)
+    /// Used to calculate accurate SOL amounts
+    pub pyth_price_update: Account<'info, PriceUpdateV2>,
+
+    /// Redeemed nonce marker (PDA), creation fails if the voucher was already redeemed
+    #[account(
+        init,
+        payer = buyer,
+        space = ANCHOR_DISCRIMINATOR + VoucherNonce::INIT_SPACE,
+        seeds = [VOUCHER_NONCE_SEED, &voucher.nonce.to_le_bytes()],
+        bump
+    )]
+    pub voucher_nonce: Account<'info, VoucherNonce>,
+
+    /// Instructions sysvar, used to inspect the Ed25519 signature verification instruction
+    /// CHECK: address is verified against the instructions sysvar id
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// Solana system program, used for transfer operations
+    pub system_program: Program<'info, System>,
+}
+
+/// Voucher domain registration instruction handler
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `domain_name` - Name of the domain to register (without .ca suffix)
+/// * `period` - Registration period, ending at most the voucher's max_years from now
+/// * `addresses` - List of blockchain addresses to set for the domain
+/// * `owner` - Owner of the domain, can be any public key, not necessarily the transaction signer
+/// * `voucher` - Voucher signed by the voucher signer
+/// 
+/// # Errors
+/// * `InvalidDomainLength` - Domain name length is invalid (empty or longer than 253 characters)
+/// * `DomainNameNotLowercase` - Domain name contains uppercase characters
+/// * `InvalidRegisterYears` - Registration period is empty
+/// * `RegistrationPeriodTooShort` - Registration period is shorter than the minimum registration duration
+/// * `RegistrationHorizonExceeded` - Expiry would be further ahead than the maximum registration horizon
+/// * `ProgramPaused` - Registrations are paused
+/// * `DomainNameReserved` - Domain name is on the reserved names list
+/// * `SunriseActive` - General availability has not started yet
+/// * `InvalidVoucherSignature` - No matching Ed25519 verification by the voucher signer precedes this instruction
+/// * `VoucherExpired` - Voucher has expired
+/// * `VoucherNotApplicable` - Voucher does not cover the domain name or registration period
+/// * `VoucherBuyerMismatch` - Voucher was issued to another wallet than the buyer
+/// * `InvalidDiscount` - Voucher discount exceeds 10000 basis points
+pub fn register_domain_with_voucher_handler(
+    context: Context<RegisterDomainWithVoucherAccountConstraints>,
+    domain_name: String,
+    period: RegistrationPeriod,
+    addresses: Vec<ChainAddress>,
+    owner: Pubkey,
+    voucher: Voucher,
+) -> Result<()> {
+    // verify domain name, pause flag and period
+    validate_registration(&context.accounts.program_state, &domain_name, &period)?;
+
+    // Reserved names can only be assigned by the reserved-name manager
+    ensure_name_not_reserved(&context.accounts.reserved_name)?;
+
+    // get current timestamp
+    let current_timestamp = get_current_timestamp()?;
+
+    // Only sunrise claims are accepted before general availability
+    ensure_general_availability(&context.accounts.program_state, current_timestamp)?;
+
+    // Verify the voucher was signed by the voucher signer
+    let (signer, message) = load_preceding_ed25519_signature(&context.accounts.instructions_sysvar)?;
+    require!(
+        context.accounts.admin_roles.has_role(AdminRole::VoucherSigner, &signer),
+        CaRegistrarError::InvalidVoucherSignature
+    );
+    let expected_message = [
+        VOUCHER_MESSAGE_PREFIX,
+        crate::ID.as_ref(),
+        &voucher.try_to_vec()?,
+    ].concat();
+    require!(message == expected_message, CaRegistrarError::InvalidVoucherSignature);
+
+    // Verify the voucher applies to this registration, and is redeemed by the buyer it was issued to
+    require!(current_timestamp <= voucher.expiry_timestamp, CaRegistrarError::VoucherExpired);
+    require_keys_eq!(voucher.buyer, context.accounts.buyer.key(), CaRegistrarError::VoucherBuyerMismatch);
+    let max_period = RegistrationPeriod::Years {
+        count: u32::try_from(voucher.max_years).unwrap_or(u32::MAX),
+    };
+    require!(
+        voucher.covers(&domain_name)
+            && calculate_period_end(current_timestamp, &period)? <= calculate_period_end(current_timestamp, &max_period)?,
+        CaRegistrarError::VoucherNotApplicable
+    );
+    require!(voucher.discount_bps as u64 <= BASIS_POINTS_DENOMINATOR, CaRegistrarError::InvalidDiscount);
+
+    // Calculate discounted fee using Pyth oracle
+    let price_usd = calculate_price_usd(
+        context.accounts.program_state.base_price_usd,
+        &period,
+        context.accounts.price_config.multi_year_discount_bps(period.whole_years()),
+        Some(&PromoDiscount::Percentage { discount_bps: voucher.discount_bps }),
+    )?;
+    let mut fee = 0;
+    let mut oracle_price = None;
+    if price_usd > 0 {
+        let (discounted_fee, fee_oracle_price) = calculate_fee_in_lamports(&context.accounts.pyth_price_update, price_usd)?;
+        fee = discounted_fee;
+        oracle_price = Some(fee_oracle_price);
+        collect_registration_fee(
+            context.accounts.buyer.to_account_info(),
+            context.accounts.program_state.to_account_info(),
+            context.accounts.system_program.to_account_info(),
+            context.accounts.program_state.default_referral_share_bps,
+            None,
+            None,
+            fee,
+        )?;
+    }
+
+    // Record redeemed nonce
+    let voucher_nonce = &mut context.accounts.voucher_nonce;
+    voucher_nonce.nonce = voucher.nonce;
+    voucher_nonce.domain_record = context.accounts.domain_record.key();
+    voucher_nonce.bump = context.bumps.voucher_nonce;
+
+    // Update domain record
+    let registration_generation = next_registration_generation(&mut context.accounts.program_state)?;
+    let domain_record = &mut context.accounts.domain_record;
+    write_new_domain_record(
+        domain_record,
+        domain_name,
+        owner,
+        addresses,
+        current_timestamp,
+        registration_generation,
+        calculate_expiry_timestamp(&context.accounts.program_state, current_timestamp, current_timestamp, &period)?,
+        fee,
+        context.bumps.domain_record,
+    );
+
+    // Update program state
+    let program_state = &mut context.accounts.program_state;
+    program_state.domains_registered += 1;
+
+    emit_event!(context, DomainRegistered {
+        domain_name: domain_record.domain_name.clone(),
+        owner,
+        payer: context.accounts.buyer.key(),
+        addresses: domain_record.addresses.clone(),
+        price_usd,
+        lamports_paid: fee,
+        referral_lamports: 0,
+        oracle_price,
+        expiry_timestamp: domain_record.expiry_timestamp,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Domain {} registered with voucher {} for {:?} with owner {}", 
+        domain_record.domain_name, voucher.nonce, period, owner);
+    
+    Ok(())
+}
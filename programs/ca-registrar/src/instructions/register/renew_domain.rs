@@ -1,9 +1,9 @@
 #![allow(unexpected_cfgs)]
 
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{self, transfer};
 use crate::constants::*;
 use crate::state::*;
+use crate::events::*;
 use crate::instructions::utils::*;
 use crate::instructions::register::utils::*;
 use crate::error::CaRegistrarError;
@@ -13,6 +13,7 @@ use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 /// 
 /// This instruction allows anyone to renew any domain that is not expired or is within the grace period.
 /// Renewal only extends the domain's expiration date and does not change ownership.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(domain_name: String)]
 pub struct RenewDomainAccountConstraints<'info> {
@@ -36,10 +37,29 @@ pub struct RenewDomainAccountConstraints<'info> {
     )]
     pub program_state: Account<'info, ProgramState>,
 
+    /// Pricing configuration account, holding the multi-year discount schedule
+    #[account(
+        seeds = [PRICE_CONFIG_SEED],
+        bump = price_config.bump
+    )]
+    pub price_config: Account<'info, PriceConfig>,
+
     /// Pyth price oracle account (SOL/USD price)
     /// Used to calculate accurate SOL amounts
     pub pyth_price_update: Account<'info, PriceUpdateV2>,
 
+    /// Optional referrer config, a share of the fee is paid to its referrer
+    #[account(
+        mut,
+        seeds = [REFERRER_CONFIG_SEED, referrer_config.referrer.as_ref()],
+        bump = referrer_config.bump
+    )]
+    pub referrer_config: Option<Account<'info, ReferrerConfig>>,
+
+    /// Optional referrer wallet, must match the referrer config
+    #[account(mut)]
+    pub referrer: Option<SystemAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -47,16 +67,22 @@ pub struct RenewDomainAccountConstraints<'info> {
 /// 
 /// # Parameters
 /// * `context` - Instruction context, containing all relevant accounts
-/// * `years` - Renewal period in years, minimum 1 year
+/// * `period` - Renewal period, at least the minimum registration duration
 /// 
 /// # Errors
-/// * `InvalidRegisterYears` - Registration period is invalid
+/// * `InvalidRegisterYears` - Registration period is empty
+/// * `RegistrationPeriodTooShort` - Registration period is shorter than the minimum registration duration
+/// * `RegistrationHorizonExceeded` - Expiry would be further ahead than the maximum registration horizon
+/// * `InvalidReferrer` - Referrer wallet does not match the referrer config, or only one of them is supplied
 pub fn renew_domain_handler(
     context: Context<RenewDomainAccountConstraints>,
-    years: u64,
+    period: RegistrationPeriod,
 ) -> Result<()> {
-    // Validate years
-    require!(years > 0 && years <= 99, CaRegistrarError::InvalidRegisterYears);
+    // Registrations are blocked while the program is paused
+    require!(!context.accounts.program_state.paused, CaRegistrarError::ProgramPaused);
+
+    // Validate period
+    require!(!period.is_empty(), CaRegistrarError::InvalidRegisterYears);
 
     // Get current timestamp
     let current_timestamp = get_current_timestamp()?;
@@ -79,36 +105,52 @@ pub fn renew_domain_handler(
     );
     
     // Calculate fee using Pyth oracle
-    let yearly_fee = calculate_yearly_fee_in_lamports(
-        &context.accounts.pyth_price_update,
-        context.accounts.program_state.base_price_usd,  // Pass price value directly
-        years,
+    let price_usd = calculate_price_usd(
+        context.accounts.program_state.base_price_usd,
+        &period,
+        context.accounts.price_config.multi_year_discount_bps(period.whole_years()),
+        None,
     )?;
+    let (fee, oracle_price) = calculate_fee_in_lamports(&context.accounts.pyth_price_update, price_usd)?;
     
-    // Transfer fee to program state account
-    transfer(
-        CpiContext::new(
-            context.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: context.accounts.payer.to_account_info(),
-                to: context.accounts.program_state.to_account_info(),
-            },
-        ),
-        yearly_fee,
+    // Transfer fee to program state account, paying the referral share if a referrer is supplied
+    let referral_fee = collect_registration_fee(
+        context.accounts.payer.to_account_info(),
+        context.accounts.program_state.to_account_info(),
+        context.accounts.system_program.to_account_info(),
+        context.accounts.program_state.default_referral_share_bps,
+        context.accounts.referrer_config.as_mut(),
+        context.accounts.referrer.as_ref(),
+        fee,
     )?;
 
     // Update domain expiry time
     // If current time is past the original expiry time, calculate from current time
-    // Otherwise, add years to the original expiry time
-    let new_expiry_timestamp = if current_timestamp > domain_record.expiry_timestamp {
-        calculate_expiry_timestamp(current_timestamp, years)
-    } else {
-        calculate_expiry_timestamp(domain_record.expiry_timestamp, years)
-    };
+    // Otherwise, add the period to the original expiry time
+    let new_expiry_timestamp = calculate_expiry_timestamp(
+        &context.accounts.program_state,
+        current_timestamp.max(domain_record.expiry_timestamp),
+        current_timestamp,
+        &period,
+    )?;
     
+    let previous_expiry_timestamp = domain_record.expiry_timestamp;
+    record_payment(domain_record, fee - referral_fee, current_timestamp)?;
     domain_record.expiry_timestamp = new_expiry_timestamp;
     
-    msg!("Domain {} renewed successfully for {} years", domain_record.domain_name, years);
+    emit_event!(context, DomainRenewed {
+        domain_name: domain_record.domain_name.clone(),
+        payer: context.accounts.payer.key(),
+        price_usd,
+        lamports_paid: fee,
+        referral_lamports: referral_fee,
+        oracle_price,
+        previous_expiry_timestamp,
+        new_expiry_timestamp,
+        timestamp: current_timestamp,
+    });
+    
+    msg!("Domain {} renewed successfully for {:?}", domain_record.domain_name, period);
     
     Ok(())
 } 
\ No newline at end of file
@@ -1,17 +1,145 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, transfer};
 use pyth_solana_receiver_sdk::price_update::{PriceUpdateV2, get_feed_id_from_hex};
-use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_lang::solana_program::hash::hash;
 use crate::constants::*;
+use crate::state::*;
 use crate::error::CaRegistrarError;
+use crate::events::OraclePrice;
+use crate::instructions::utils::*;
 
-/// Calculate yearly fee in lamports based on Pyth price oracle
+/// Validate a domain name length: > 0 && <= 253 (from state definition)
+/// and lowercase, so a reserved name hash and lowercased lookups cover every spelling of a name
+pub fn validate_domain_name(domain_name: &str) -> Result<()> {
+    require!(
+        !domain_name.is_empty() && domain_name.len() <= 253,
+        CaRegistrarError::InvalidDomainLength
+    );
+    require!(
+        domain_name.to_lowercase() == domain_name,
+        CaRegistrarError::DomainNameNotLowercase
+    );
+
+    Ok(())
+}
+
+/// Validate the arguments shared by every path creating a new domain registration
+pub fn validate_registration(
+    program_state: &ProgramState,
+    domain_name: &str,
+    period: &RegistrationPeriod,
+) -> Result<()> {
+    validate_domain_name(domain_name)?;
+
+    // Registrations are blocked while the program is paused
+    require!(!program_state.paused, CaRegistrarError::ProgramPaused);
+
+    // verify period is not empty, its limits are the minimum duration and the registration horizon
+    require!(!period.is_empty(), CaRegistrarError::InvalidRegisterYears);
+
+    Ok(())
+}
+
+/// Verify general availability has started, registrations before it go through sunrise claims
+pub fn ensure_general_availability(program_state: &ProgramState, current_timestamp: i64) -> Result<()> {
+    require!(
+        current_timestamp >= program_state.general_availability_timestamp,
+        CaRegistrarError::SunriseActive
+    );
+
+    Ok(())
+}
+
+/// Verify a domain name is not on the reserved names list
+/// 
+/// The reserved name account is the PDA derived from the hash of the name, it only holds data if the name is reserved
+pub fn ensure_name_not_reserved(reserved_name: &AccountInfo) -> Result<()> {
+    require!(
+        reserved_name.data_is_empty(),
+        CaRegistrarError::DomainNameReserved
+    );
+
+    Ok(())
+}
+
+/// Lift the reservation of a domain name, if any, closing the reserved name account
 /// 
-/// Converts the base price to lamports according to current SOL/USD rate
-pub fn calculate_yearly_fee_in_lamports(
+/// The account rent goes to `destination`. An empty account, i.e. a name that is not reserved, is left untouched.
+pub fn lift_reservation<'info>(reserved_name: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    if reserved_name.data_is_empty() {
+        return Ok(());
+    }
+    
+    let rent = reserved_name.lamports();
+    **reserved_name.try_borrow_mut_lamports()? = 0;
+    **destination.try_borrow_mut_lamports()? = destination.lamports()
+        .checked_add(rent)
+        .ok_or(error!(CaRegistrarError::MathOverflow))?;
+    reserved_name.assign(&System::id());
+    reserved_name.realloc(0, false)?;
+
+    Ok(())
+}
+
+/// Write a newly registered domain record, `paid_lamports` being the part of the fee kept by the treasury
+#[allow(clippy::too_many_arguments)]
+pub fn write_new_domain_record(
+    domain_record: &mut DomainRecord,
+    domain_name: String,
+    owner: Pubkey,
+    addresses: Vec<ChainAddress>,
+    registration_timestamp: i64,
+    registration_generation: u64,
+    expiry_timestamp: i64,
+    paid_lamports: u64,
+    bump: u8,
+) {
+    domain_record.domain_name = domain_name;
+    domain_record.owner = owner;
+    domain_record.registration_timestamp = registration_timestamp;
+    domain_record.registration_generation = registration_generation;
+    domain_record.expiry_timestamp = expiry_timestamp;
+    domain_record.addresses = addresses;
+    domain_record.bump = bump;
+    domain_record.suspended = false;
+    domain_record.fuses = 0;
+    domain_record.paid_lamports = paid_lamports;
+    domain_record.paid_timestamp = registration_timestamp;
+}
+
+/// Allocate the generation of a new registration of a name
+/// 
+/// Records derived from a registration (subdomains, wildcard, policy) compare generations rather than
+/// timestamps, so a release and re-registration within the same second still starts afresh.
+pub fn next_registration_generation(program_state: &mut ProgramState) -> Result<u64> {
+    let registration_generation = program_state.registration_generations;
+    program_state.registration_generations = registration_generation
+        .checked_add(1)
+        .ok_or(error!(CaRegistrarError::MathOverflow))?;
+
+    Ok(registration_generation)
+}
+
+/// Record a payment extending a registration, before its expiry is moved
+/// 
+/// The unused part of earlier payments is carried over, so the paid lamports cover the time from now until the
+/// new expiry.
+pub fn record_payment(domain_record: &mut DomainRecord, lamports: u64, current_timestamp: i64) -> Result<()> {
+    domain_record.paid_lamports = domain_record.unused_paid_lamports(current_timestamp)
+        .checked_add(lamports)
+        .ok_or(error!(CaRegistrarError::MathOverflow))?;
+    domain_record.paid_timestamp = current_timestamp;
+
+    Ok(())
+}
+
+/// Calculate fee in lamports based on Pyth price oracle
+/// 
+/// Converts a price in USD cents to lamports according to current SOL/USD rate, returned along with the fee
+pub fn calculate_fee_in_lamports(
     price_update: &Account<PriceUpdateV2>,
-    base_price_usd: u64,
-    years: u64,
-) -> Result<u64> {
+    price_usd: u64,
+) -> Result<(u64, OraclePrice)> {
     // Get SOL/USD price information
     let feed_id = get_feed_id_from_hex(SOL_USD_PRICE_FEED_ID)?;
     let price_info = price_update.get_price_no_older_than(
@@ -20,22 +148,158 @@ pub fn calculate_yearly_fee_in_lamports(
         &feed_id
     )?;
     
-    // verify price is valid
-    require!(price_info.price > 0, CaRegistrarError::InvalidPriceFeed);
-    
-    // calculate lamports per year
-    let lamports = (base_price_usd as u128 * LAMPORTS_PER_SOL as u128 * 10_u128.pow((-price_info.exponent) as u32)) 
-                  / (price_info.price as u128 * 100);
+    let fee = convert_usd_to_lamports(price_usd, price_info.price, price_info.exponent)?;
+    let oracle_price = OraclePrice {
+        price: price_info.price,
+        exponent: price_info.exponent,
+        publish_time: price_info.publish_time,
+    };
     
-    let yearly_fee = lamports
-        .checked_mul(years as u128)
-        .ok_or(error!(CaRegistrarError::MathOverflow))?;
-    
-    Ok(yearly_fee as u64)
+    Ok((fee, oracle_price))
+}
+
+/// Redeem a promo code, returning its discount
+/// 
+/// The code must match the hash of the promo code account, be unexpired and have uses left.
+/// One use is consumed.
+pub fn redeem_promo_code(
+    promo_code_account: Option<&mut Account<PromoCode>>,
+    promo_code: Option<String>,
+    current_timestamp: i64,
+) -> Result<Option<PromoDiscount>> {
+    let (promo_code_account, promo_code) = match (promo_code_account, promo_code) {
+        (Some(promo_code_account), Some(promo_code)) => (promo_code_account, promo_code),
+        (None, None) => return Ok(None),
+        _ => return err!(CaRegistrarError::InvalidPromoCode),
+    };
+
+    require!(
+        hash(promo_code.as_bytes()).to_bytes() == promo_code_account.code_hash,
+        CaRegistrarError::InvalidPromoCode
+    );
+    require!(
+        current_timestamp <= promo_code_account.expiry_timestamp,
+        CaRegistrarError::PromoCodeExpired
+    );
+    require!(
+        promo_code_account.remaining_uses > 0,
+        CaRegistrarError::PromoCodeExhausted
+    );
+
+    promo_code_account.remaining_uses -= 1;
+
+    Ok(Some(promo_code_account.discount))
+}
+
+/// Calculate the end of a registration period starting at `start_timestamp`
+pub fn calculate_period_end(start_timestamp: i64, period: &RegistrationPeriod) -> Result<i64> {
+    match *period {
+        RegistrationPeriod::Years { count } => add_calendar_months(start_timestamp, count as u64 * 12),
+        RegistrationPeriod::Months { count } => add_calendar_months(start_timestamp, count as u64),
+        RegistrationPeriod::Seconds { seconds } => start_timestamp
+            .checked_add(seconds)
+            .ok_or(error!(CaRegistrarError::MathOverflow)),
+    }
+}
+
+/// Calculate domain expiry timestamp, a registration period after `start_timestamp`
+/// 
+/// The period must last at least the minimum registration duration, and the expiry must not exceed the
+/// maximum registration horizon counted from the current time
+pub fn calculate_expiry_timestamp(
+    program_state: &ProgramState,
+    start_timestamp: i64,
+    current_timestamp: i64,
+    period: &RegistrationPeriod,
+) -> Result<i64> {
+    let expiry_timestamp = calculate_period_end(start_timestamp, period)?;
+    require!(
+        expiry_timestamp - start_timestamp >= program_state.min_registration_seconds,
+        CaRegistrarError::RegistrationPeriodTooShort
+    );
+
+    // Names cannot be held further ahead than the registration horizon, including through repeated renewals
+    ensure_within_horizon(program_state, current_timestamp, expiry_timestamp)?;
+
+    Ok(expiry_timestamp)
 }
 
-/// Calculate domain expiry timestamp
-pub fn calculate_expiry_timestamp(current_timestamp: i64, years: u64) -> i64 {  
-    current_timestamp + (SECONDS_PER_YEAR * years as i64)
+/// Verify an expiry timestamp is not further ahead than the maximum registration horizon
+pub fn ensure_within_horizon(
+    program_state: &ProgramState,
+    current_timestamp: i64,
+    expiry_timestamp: i64,
+) -> Result<()> {
+    let horizon_timestamp = current_timestamp
+        .checked_add(program_state.max_registration_horizon_seconds)
+        .ok_or(error!(CaRegistrarError::MathOverflow))?;
+    require!(
+        expiry_timestamp <= horizon_timestamp,
+        CaRegistrarError::RegistrationHorizonExceeded
+    );
+
+    Ok(())
 }
 
+/// Collect a registration fee from the payer into the program state account
+/// 
+/// When a referrer config and referrer wallet are supplied, the referral share is paid directly to
+/// the referrer and recorded on the referrer config. Returns the lamports paid to the referrer.
+pub fn collect_registration_fee<'info>(
+    payer: AccountInfo<'info>,
+    program_state: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    default_referral_share_bps: u16,
+    referrer_config: Option<&mut Account<'info, ReferrerConfig>>,
+    referrer: Option<&SystemAccount<'info>>,
+    fee: u64,
+) -> Result<u64> {
+    // Pay the referral share, if any
+    let referral_fee = match (referrer_config, referrer) {
+        (Some(referrer_config), Some(referrer)) => {
+            require_keys_eq!(referrer.key(), referrer_config.referrer, CaRegistrarError::InvalidReferrer);
+            require!(referrer_config.active, CaRegistrarError::ReferrerInactive);
+
+            let referral_fee = calculate_share(fee, referrer_config.share_bps(default_referral_share_bps))?;
+            if referral_fee > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        system_program::Transfer {
+                            from: payer.clone(),
+                            to: referrer.to_account_info(),
+                        },
+                    ),
+                    referral_fee,
+                )?;
+            }
+
+            // Track referral totals for auditing payouts
+            referrer_config.referral_count = referrer_config.referral_count
+                .checked_add(1)
+                .ok_or(error!(CaRegistrarError::MathOverflow))?;
+            referrer_config.total_paid_lamports = referrer_config.total_paid_lamports
+                .checked_add(referral_fee)
+                .ok_or(error!(CaRegistrarError::MathOverflow))?;
+
+            msg!("Paid {} lamports referral fee to {}", referral_fee, referrer.key());
+            referral_fee
+        }
+        (None, None) => 0,
+        _ => return err!(CaRegistrarError::InvalidReferrer),
+    };
+
+    // Transfer the rest of the fee to program state account
+    transfer(
+        CpiContext::new(
+            system_program,
+            system_program::Transfer {
+                from: payer,
+                to: program_state,
+            },
+        ),
+        fee - referral_fee,
+    )?;
+
+    Ok(referral_fee)
+}
@@ -0,0 +1,2 @@
+pub use release_domain::*;
+mod release_domain;
@@ -0,0 +1,222 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for releasing domain instruction
+/// 
+/// This instruction allows domain owners to give their domain back. The domain record is closed and its rent
+/// returned to the owner, so the name is immediately available for registration again.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain_name: String)]
+pub struct ReleaseDomainAccountConstraints<'info> {
+    /// Domain owner, receives the account rent and the refund
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Domain record to release
+    #[account(
+        mut,
+        close = owner,
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump = domain_record.bump,
+        has_one = owner @ CaRegistrarError::NotDomainOwner,
+        constraint = !domain_record.suspended @ CaRegistrarError::DomainSuspended,
+    )]
+    pub domain_record: Account<'info, DomainRecord>,
+
+    /// Program state account, the treasury paying refunds
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Release a domain, refunding the unused whole years of what was paid at the configured refund rate
+/// 
+/// The refund is the share of the lamports paid for the registration that covers the whole years left until
+/// expiry, times the refund rate, so a registration with less than a year left gets nothing back. Free
+/// registrations (airdrop claims, reserved names, fully discounted vouchers and promo codes) get nothing back
+/// either. The refund is paid from the treasury balance above the rent-exempt minimum.
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// 
+/// # Errors
+/// * `NotDomainOwner` - Caller is not the domain owner
+/// * `DomainSuspended` - Domain is suspended
+/// * `FuseBurned` - A fuse is burned on the domain
+/// * `InsufficientRefundFunds` - Treasury balance above the rent-exempt minimum is lower than the refund
+pub fn release_domain_handler(
+    context: Context<ReleaseDomainAccountConstraints>,
+) -> Result<()> {
+    // Get current timestamp
+    let current_timestamp = get_current_timestamp()?;
+
+    // Releasing would let the owner register the name again without its fuses
+    let domain_record = &context.accounts.domain_record;
+    require!(
+        !domain_record.is_fuse_burned(u32::MAX, current_timestamp),
+        CaRegistrarError::FuseBurned
+    );
+
+    // Pay the refund, if any
+    let program_state = &mut context.accounts.program_state;
+    let refund = calculate_release_refund(domain_record, program_state.release_refund_rate_bps, current_timestamp)?;
+    if refund > 0 {
+        // Never dip below the rent-exempt minimum of the program state
+        let program_state_info = program_state.to_account_info();
+        let min_rent = Rent::get()?.minimum_balance(ANCHOR_DISCRIMINATOR + ProgramState::INIT_SPACE);
+        let available = program_state_info.lamports().saturating_sub(min_rent);
+        require!(refund <= available, CaRegistrarError::InsufficientRefundFunds);
+
+        **program_state_info.try_borrow_mut_lamports()? -= refund;
+        **context.accounts.owner.to_account_info().try_borrow_mut_lamports()? += refund;
+
+        msg!("Refunded {} lamports of {} paid", refund, domain_record.paid_lamports);
+    }
+
+    // The closed record no longer counts as registered
+    program_state.domains_registered = program_state.domains_registered.saturating_sub(1);
+
+    emit_event!(context, DomainReleased {
+        domain_name: domain_record.domain_name.clone(),
+        owner: domain_record.owner,
+        refund_lamports: refund,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Domain {} released by {}", domain_record.domain_name, domain_record.owner);
+
+    Ok(())
+}
+
+/// Refund due on release, a share of the paid lamports covering unused whole years, never more than was paid
+pub fn calculate_release_refund(
+    domain_record: &DomainRecord,
+    refund_rate_bps: u16,
+    current_timestamp: i64,
+) -> Result<u64> {
+    calculate_share(domain_record.unused_whole_year_lamports(current_timestamp), refund_rate_bps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::register::utils::*;
+
+    const START: i64 = 1_700_000_000;
+    
+    fn registered_record(paid_lamports: u64) -> DomainRecord {
+        let mut domain_record = DomainRecord {
+            domain_name: String::new(),
+            owner: Pubkey::default(),
+            expiry_timestamp: 0,
+            registration_timestamp: 0,
+            registration_generation: 0,
+            addresses: Vec::new(),
+            bump: 0,
+            suspended: false,
+            fuses: 0,
+            paid_lamports: 0,
+            paid_timestamp: 0,
+        };
+        write_new_domain_record(
+            &mut domain_record,
+            "alice".to_string(),
+            Pubkey::new_unique(),
+            Vec::new(),
+            START,
+            0,
+            START + SECONDS_PER_YEAR,
+            paid_lamports,
+            255,
+        );
+        domain_record
+    }
+
+    #[test]
+    fn free_claim_is_not_refunded() {
+        // Airdrop claims and reserved names are written without payment
+        let domain_record = registered_record(0);
+        
+        assert_eq!(calculate_release_refund(&domain_record, 5_000, START).unwrap(), 0);
+        assert_eq!(calculate_release_refund(&domain_record, 10_000, START + 1).unwrap(), 0);
+    }
+
+    #[test]
+    fn fully_discounted_voucher_is_not_refunded() {
+        // A 100% voucher costs nothing at any list price
+        let price_usd = calculate_price_usd(
+            500,
+            &RegistrationPeriod::Years { count: 1 },
+            0,
+            Some(&PromoDiscount::Percentage { discount_bps: 10_000 }),
+        ).unwrap();
+        assert_eq!(price_usd, 0);
+        let domain_record = registered_record(0);
+        
+        assert_eq!(calculate_release_refund(&domain_record, 10_000, START).unwrap(), 0);
+    }
+
+    #[test]
+    fn discounted_registration_refunds_a_share_of_the_amount_paid() {
+        // Half-price registration, paid 1_000_000 lamports instead of 2_000_000
+        let domain_record = registered_record(1_000_000);
+        
+        // Released right away, the refund is the rate applied to what was paid
+        assert_eq!(calculate_release_refund(&domain_record, 5_000, START).unwrap(), 500_000);
+        assert_eq!(calculate_release_refund(&domain_record, 10_000, START).unwrap(), 1_000_000);
+        
+        // Once less than a whole year is left, nothing is refunded
+        assert_eq!(calculate_release_refund(&domain_record, 10_000, START + 1).unwrap(), 0);
+        assert_eq!(calculate_release_refund(&domain_record, 10_000, START + SECONDS_PER_YEAR / 2).unwrap(), 0);
+        
+        // Nothing is left once expired
+        assert_eq!(calculate_release_refund(&domain_record, 10_000, START + SECONDS_PER_YEAR).unwrap(), 0);
+    }
+
+    #[test]
+    fn multi_year_registration_refunds_unused_whole_years() {
+        // Five years paid 5_000_000 lamports
+        let mut domain_record = registered_record(5_000_000);
+        domain_record.expiry_timestamp = START + 5 * SECONDS_PER_YEAR;
+        
+        // A day in, four whole years are left
+        assert_eq!(calculate_release_refund(&domain_record, 10_000, START + SECONDS_PER_DAY).unwrap(), 4_000_000);
+        assert_eq!(calculate_release_refund(&domain_record, 5_000, START + SECONDS_PER_DAY).unwrap(), 2_000_000);
+        
+        // Two and a half years in, two whole years are left
+        let halfway = START + 5 * SECONDS_PER_YEAR / 2;
+        assert_eq!(calculate_release_refund(&domain_record, 10_000, halfway).unwrap(), 2_000_000);
+        
+        // A second before expiry, nothing
+        assert_eq!(calculate_release_refund(&domain_record, 10_000, START + 5 * SECONDS_PER_YEAR - 1).unwrap(), 0);
+    }
+
+    #[test]
+    fn renewal_carries_over_the_unused_payment() {
+        let mut domain_record = registered_record(1_000_000);
+        
+        // Renewed for a year halfway through: half of the first payment is left, plus the renewal
+        let halfway = START + SECONDS_PER_YEAR / 2;
+        record_payment(&mut domain_record, 2_000_000, halfway).unwrap();
+        domain_record.expiry_timestamp = START + 2 * SECONDS_PER_YEAR;
+        
+        // Of the year and a half covered, one whole year is left
+        assert_eq!(domain_record.paid_lamports, 2_500_000);
+        assert_eq!(calculate_release_refund(&domain_record, 10_000, halfway).unwrap(), 1_666_666);
+        
+        // Renewing a domain in its grace period carries nothing over
+        let mut expired_record = registered_record(1_000_000);
+        record_payment(&mut expired_record, 0, START + SECONDS_PER_YEAR + 1).unwrap();
+        assert_eq!(expired_record.paid_lamports, 0);
+    }
+}
@@ -0,0 +1,91 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::subdomain::utils::*;
+
+/// Account constraints for burning subdomain fuses instruction
+/// 
+/// The subdomain owner can burn CANNOT_TRANSFER and CANNOT_SET_ADDRESSES, the parent domain owner can burn
+/// PARENT_CANNOT_CONTROL to give up deleting the subdomain. Since releasing and registering the parent again
+/// would make the subdomain stale, PARENT_CANNOT_CONTROL requires CANNOT_RELEASE to be burned on the parent.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BurnSubdomainFusesAccountConstraints<'info> {
+    /// Subdomain owner or parent domain owner
+    pub authority: Signer<'info>,
+
+    /// Parent domain record
+    #[account(address = subdomain_record.parent)]
+    pub parent_domain_record: Account<'info, DomainRecord>,
+
+    /// Subdomain record to burn fuses on
+    #[account(
+        mut,
+        seeds = [SUBDOMAIN_RECORD_SEED, subdomain_record.parent.as_ref(), &hash(subdomain_record.label.as_bytes()).to_bytes()],
+        bump = subdomain_record.bump,
+    )]
+    pub subdomain_record: Account<'info, SubdomainRecord>,
+}
+
+/// Burn permission fuses on a subdomain, burned fuses can never be cleared
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `fuses` - Fuses to burn
+/// 
+/// # Errors
+/// * `ParentDomainInactive` - Parent domain is expired, suspended or was registered again
+/// * `InvalidFuses` - No fuses given or the signer cannot burn one of the fuses
+/// * `ParentReleasable` - PARENT_CANNOT_CONTROL is burned while the parent can still be released
+pub fn burn_subdomain_fuses_handler(
+    context: Context<BurnSubdomainFusesAccountConstraints>,
+    fuses: u32,
+) -> Result<()> {
+    // Subdomains can only be modified while their parent is active
+    let current_timestamp = get_current_timestamp()?;
+    let parent = &context.accounts.parent_domain_record;
+    let subdomain_record = &mut context.accounts.subdomain_record;
+    ensure_subdomain_active(subdomain_record, parent, current_timestamp)?;
+
+    // Each signer can only give up its own rights
+    let authority = context.accounts.authority.key();
+    let mut allowed_fuses = 0;
+    if authority == subdomain_record.owner {
+        allowed_fuses |= SUBDOMAIN_OWNER_FUSES;
+    }
+    if authority == parent.owner {
+        allowed_fuses |= FUSE_PARENT_CANNOT_CONTROL;
+    }
+    require!(
+        fuses != 0 && fuses & !allowed_fuses == 0,
+        CaRegistrarError::InvalidFuses
+    );
+
+    // The parent owner must not be able to release the parent, which would drop the subdomain with its fuses
+    require!(
+        fuses & FUSE_PARENT_CANNOT_CONTROL == 0 || parent.is_fuse_burned(FUSE_CANNOT_RELEASE, current_timestamp),
+        CaRegistrarError::ParentReleasable
+    );
+
+    // Fuses are only ever set, never cleared
+    subdomain_record.fuses |= fuses;
+
+    emit_event!(context, SubdomainFusesBurned {
+        domain_name: parent.domain_name.clone(),
+        label: subdomain_record.label.clone(),
+        burned_fuses: fuses,
+        fuses: subdomain_record.fuses,
+        authority,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Burned fuses {:#x} on subdomain {}.{}", fuses, subdomain_record.label, parent.domain_name);
+
+    Ok(())
+}
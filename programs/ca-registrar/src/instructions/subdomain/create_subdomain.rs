@@ -0,0 +1,98 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::subdomain::utils::*;
+
+/// Account constraints for creating subdomain instruction
+/// 
+/// This instruction allows the owner of an unexpired domain to issue a subdomain (e.g. "pay.alice.ca")
+/// to any owner, paying for the account rent.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain_name: String, label: String)]
+pub struct CreateSubdomainAccountConstraints<'info> {
+    /// Parent domain owner, pays for the subdomain account
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Parent domain record
+    #[account(
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump = parent_domain_record.bump,
+        constraint = parent_domain_record.owner == owner.key() @ CaRegistrarError::NotDomainOwner,
+    )]
+    pub parent_domain_record: Account<'info, DomainRecord>,
+
+    /// Subdomain record, using the parent record and the hash of the label as seeds for PDA derivation
+    #[account(
+        init,
+        payer = owner,
+        space = ANCHOR_DISCRIMINATOR + SubdomainRecord::INIT_SPACE,
+        seeds = [SUBDOMAIN_RECORD_SEED, parent_domain_record.key().as_ref(), &hash(label.as_bytes()).to_bytes()],
+        bump
+    )]
+    pub subdomain_record: Account<'info, SubdomainRecord>,
+
+    /// Solana system program, used for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a subdomain under a domain
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `label` - Subdomain label without the parent name (e.g., "pay" for "pay.alice.ca")
+/// * `subdomain_owner` - Owner of the subdomain, can be any public key
+/// * `addresses` - List of blockchain addresses to set for the subdomain
+/// 
+/// # Errors
+/// * `NotDomainOwner` - Caller is not the parent domain owner
+/// * `InvalidSubdomainLabel` - Label is empty, longer than 63 characters or contains a dot or "*"
+/// * `ParentDomainInactive` - Parent domain is expired or suspended
+/// * `FuseBurned` - CANNOT_CREATE_SUBDOMAIN fuse is burned on the parent
+/// * `TooManyAddresses` - Number of addresses exceeds the limit
+pub fn create_subdomain_handler(
+    context: Context<CreateSubdomainAccountConstraints>,
+    label: String,
+    subdomain_owner: Pubkey,
+    addresses: Vec<ChainAddress>,
+) -> Result<()> {
+    // Validate label and address count
+    validate_subdomain_label(&label)?;
+    require!(addresses.len() <= 10, CaRegistrarError::TooManyAddresses);
+
+    // Only an active parent can issue subdomains
+    let current_timestamp = get_current_timestamp()?;
+    let parent = &context.accounts.parent_domain_record;
+    ensure_parent_can_issue(parent, current_timestamp)?;
+
+    // Write subdomain record
+    let subdomain_record = &mut context.accounts.subdomain_record;
+    subdomain_record.parent = parent.key();
+    subdomain_record.label = label;
+    subdomain_record.owner = subdomain_owner;
+    subdomain_record.parent_registration_generation = parent.registration_generation;
+    subdomain_record.addresses = addresses;
+    subdomain_record.bump = context.bumps.subdomain_record;
+    subdomain_record.expiry_timestamp = i64::MAX;
+    subdomain_record.fuses = 0;
+
+    emit_event!(context, SubdomainCreated {
+        domain_name: parent.domain_name.clone(),
+        label: subdomain_record.label.clone(),
+        owner: subdomain_owner,
+        addresses: subdomain_record.addresses.clone(),
+        timestamp: current_timestamp,
+    });
+
+    msg!("Subdomain {}.{} created with owner {}",
+        subdomain_record.label, parent.domain_name, subdomain_owner);
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for deleting subdomain instruction
+/// 
+/// This instruction allows the current parent domain owner to revoke a subdomain, including stale subdomains
+/// left over from a previous registration of the parent. Subdomains bought through the subdomain policy can only
+/// be deleted once they expired. The account rent is returned to the parent owner.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DeleteSubdomainAccountConstraints<'info> {
+    /// Parent domain owner
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Parent domain record
+    #[account(
+        address = subdomain_record.parent,
+        constraint = parent_domain_record.owner == owner.key() @ CaRegistrarError::NotDomainOwner,
+    )]
+    pub parent_domain_record: Account<'info, DomainRecord>,
+
+    /// Subdomain record to delete
+    #[account(
+        mut,
+        close = owner,
+        seeds = [SUBDOMAIN_RECORD_SEED, subdomain_record.parent.as_ref(), &hash(subdomain_record.label.as_bytes()).to_bytes()],
+        bump = subdomain_record.bump,
+    )]
+    pub subdomain_record: Account<'info, SubdomainRecord>,
+}
+
+/// Delete a subdomain
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// 
+/// # Errors
+/// * `NotDomainOwner` - Caller is not the parent domain owner
+/// * `FuseBurned` - PARENT_CANNOT_CONTROL fuse is burned on the subdomain
+/// * `SubdomainRegistrationActive` - Subdomain was bought through the subdomain policy and has not expired
+pub fn delete_subdomain_handler(
+    context: Context<DeleteSubdomainAccountConstraints>,
+) -> Result<()> {
+    let current_timestamp = get_current_timestamp()?;
+    let subdomain_record = &context.accounts.subdomain_record;
+    let parent = &context.accounts.parent_domain_record;
+
+    // The parent cannot take back a subdomain it gave up control of
+    require!(
+        !subdomain_record.is_fuse_burned(FUSE_PARENT_CANNOT_CONTROL, parent, current_timestamp),
+        CaRegistrarError::FuseBurned
+    );
+
+    // Nor a subdomain bought through its policy, until the period it was bought for is over
+    require!(
+        !subdomain_record.is_policy_registration_active(parent, current_timestamp),
+        CaRegistrarError::SubdomainRegistrationActive
+    );
+
+    emit_event!(context, SubdomainDeleted {
+        domain_name: parent.domain_name.clone(),
+        label: subdomain_record.label.clone(),
+        owner: subdomain_record.owner,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Subdomain {}.{} deleted", subdomain_record.label, parent.domain_name);
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for deleting wildcard record instruction
+/// 
+/// This instruction allows the current parent domain owner to remove the wildcard record, including a stale record
+/// left over from a previous registration of the parent. The account rent is returned to the parent owner.
+/// Like setting it, deleting the wildcard record of the current registration is blocked by the CANNOT_CREATE_SUBDOMAIN
+/// fuse of the parent, so burning that fuse freezes what unlisted subdomains resolve to.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DeleteWildcardRecordAccountConstraints<'info> {
+    /// Parent domain owner
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Parent domain record
+    #[account(
+        address = wildcard_record.parent,
+        constraint = parent_domain_record.owner == owner.key() @ CaRegistrarError::NotDomainOwner,
+    )]
+    pub parent_domain_record: Account<'info, DomainRecord>,
+
+    /// Wildcard record to delete
+    #[account(
+        mut,
+        close = owner,
+        seeds = [WILDCARD_RECORD_SEED, wildcard_record.parent.as_ref()],
+        bump = wildcard_record.bump,
+    )]
+    pub wildcard_record: Account<'info, WildcardRecord>,
+}
+
+/// Delete the wildcard record of a domain
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// 
+/// # Errors
+/// * `NotDomainOwner` - Caller is not the parent domain owner
+/// * `FuseBurned` - CANNOT_CREATE_SUBDOMAIN fuse is burned on the parent and the record is current
+pub fn delete_wildcard_record_handler(
+    context: Context<DeleteWildcardRecordAccountConstraints>,
+) -> Result<()> {
+    let current_timestamp = get_current_timestamp()?;
+    let wildcard_record = &context.accounts.wildcard_record;
+    let parent = &context.accounts.parent_domain_record;
+
+    // The parent cannot take back the wildcard addresses it gave up control of, stale records can always be removed
+    require!(
+        !wildcard_record.is_current(parent) || !parent.is_fuse_burned(FUSE_CANNOT_CREATE_SUBDOMAIN, current_timestamp),
+        CaRegistrarError::FuseBurned
+    );
+
+    emit_event!(context, WildcardRecordDeleted {
+        domain_name: parent.domain_name.clone(),
+        timestamp: current_timestamp,
+    });
+
+    msg!("Wildcard record of {} deleted", parent.domain_name);
+
+    Ok(())
+}
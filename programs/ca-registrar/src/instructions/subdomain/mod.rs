@@ -0,0 +1,31 @@
+pub(crate) mod utils;
+
+pub use create_subdomain::*;
+mod create_subdomain;
+
+pub use update_subdomain_addresses::*;
+mod update_subdomain_addresses;
+
+pub use transfer_subdomain::*;
+mod transfer_subdomain;
+
+pub use delete_subdomain::*;
+mod delete_subdomain;
+
+pub use set_subdomain_policy::*;
+mod set_subdomain_policy;
+
+pub use register_subdomain::*;
+mod register_subdomain;
+
+pub use burn_subdomain_fuses::*;
+mod burn_subdomain_fuses;
+
+pub use set_wildcard_record::*;
+mod set_wildcard_record;
+
+pub use delete_wildcard_record::*;
+mod delete_wildcard_record;
+
+pub use resolve_subdomain::*;
+mod resolve_subdomain;
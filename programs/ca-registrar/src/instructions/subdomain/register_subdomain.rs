@@ -0,0 +1,211 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::register::utils::*;
+use crate::instructions::subdomain::utils::*;
+
+/// Account constraints for registering subdomain instruction
+/// 
+/// This instruction allows anyone to buy a subdomain under a domain whose owner opened public registration.
+/// The price set in the subdomain policy is split between the parent owner and the protocol.
+/// Token accounts are only required when the policy is priced in an SPL token.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain_name: String, label: String)]
+pub struct RegisterSubdomainAccountConstraints<'info> {
+    /// User paying for the subdomain
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Parent domain record
+    #[account(
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump = parent_domain_record.bump,
+    )]
+    pub parent_domain_record: Account<'info, DomainRecord>,
+
+    /// Parent domain owner, receives SOL payments
+    #[account(
+        mut,
+        address = parent_domain_record.owner
+    )]
+    pub parent_owner: SystemAccount<'info>,
+
+    /// Subdomain policy of the parent
+    #[account(
+        seeds = [SUBDOMAIN_POLICY_SEED, parent_domain_record.key().as_ref()],
+        bump = subdomain_policy.bump,
+    )]
+    pub subdomain_policy: Account<'info, SubdomainPolicy>,
+
+    /// Subdomain record, created, or taken over if the previous registration lapsed
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = ANCHOR_DISCRIMINATOR + SubdomainRecord::INIT_SPACE,
+        seeds = [SUBDOMAIN_RECORD_SEED, parent_domain_record.key().as_ref(), &hash(label.as_bytes()).to_bytes()],
+        bump
+    )]
+    pub subdomain_record: Account<'info, SubdomainRecord>,
+
+    /// Program state account, receives the protocol share of SOL payments
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    /// Admin roles account, the treasurer receives the protocol share of token payments
+    #[account(
+        seeds = [ADMIN_ROLES_SEED],
+        bump = admin_roles.bump
+    )]
+    pub admin_roles: Account<'info, AdminRoles>,
+
+    /// Payment mint, must match the subdomain policy
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Buyer token account paying the price
+    #[account(mut)]
+    pub buyer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Parent owner token account receiving the price net of the protocol fee
+    #[account(mut)]
+    pub parent_owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasurer token account receiving the protocol fee
+    #[account(mut)]
+    pub treasurer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program of the payment mint
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Solana system program, used for account creation and SOL transfers
+    pub system_program: Program<'info, System>,
+}
+
+/// Register a subdomain according to the parent's subdomain policy
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `label` - Subdomain label without the parent name (e.g., "pay" for "pay.alice.ca")
+/// * `period` - Registration period, ending at most at the parent's expiry
+/// * `subdomain_owner` - Owner of the subdomain, can be any public key
+/// * `addresses` - List of blockchain addresses to set for the subdomain
+/// * `proof` - Merkle proof of the (label, buyer) leaf, only used by allowlist policies
+/// 
+/// # Errors
+/// * `InvalidSubdomainLabel` - Label is empty, longer than 63 characters or contains a dot or "*"
+/// * `InvalidRegisterYears` - Registration period is empty
+/// * `RegistrationPeriodTooShort` - Registration period is shorter than the minimum registration duration
+/// * `TooManyAddresses` - Number of addresses exceeds the limit
+/// * `ParentDomainInactive` - Parent domain is expired or suspended
+/// * `FuseBurned` - CANNOT_CREATE_SUBDOMAIN fuse is burned on the parent
+/// * `SubdomainIssuanceClosed` - Registration is closed, or the policy was set under a previous registration of the parent
+/// * `InvalidMerkleProof` - The buyer is not allowlisted for this label
+/// * `SubdomainNotAvailable` - Subdomain is registered and still resolvable
+/// * `SubdomainExceedsParentExpiry` - Subdomain would expire after its parent
+/// * `SubdomainPriceTooLow` - Price of a paid policy rounds down to zero for the registration period
+/// * `InvalidPaymentAccounts` - Payment accounts do not match the subdomain policy
+pub fn register_subdomain_handler(
+    context: Context<RegisterSubdomainAccountConstraints>,
+    label: String,
+    period: RegistrationPeriod,
+    subdomain_owner: Pubkey,
+    addresses: Vec<ChainAddress>,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    // Validate label, period and address count
+    validate_subdomain_label(&label)?;
+    require!(!period.is_empty(), CaRegistrarError::InvalidRegisterYears);
+    require!(addresses.len() <= 10, CaRegistrarError::TooManyAddresses);
+
+    // Only an active parent can issue subdomains
+    let current_timestamp = get_current_timestamp()?;
+    let parent = &context.accounts.parent_domain_record;
+    ensure_parent_can_issue(parent, current_timestamp)?;
+
+    // Verify the policy allows this registration
+    let subdomain_policy = &context.accounts.subdomain_policy;
+    require!(
+        subdomain_policy.parent_registration_generation == parent.registration_generation,
+        CaRegistrarError::SubdomainIssuanceClosed
+    );
+    match subdomain_policy.mode {
+        SubdomainIssuanceMode::Closed => return err!(CaRegistrarError::SubdomainIssuanceClosed),
+        SubdomainIssuanceMode::Open => {}
+        SubdomainIssuanceMode::Allowlist { merkle_root } => {
+            let leaf = hash_merkle_leaf(&[label.as_bytes(), context.accounts.buyer.key().as_ref()]);
+            require!(
+                verify_merkle_proof(&proof, &merkle_root, leaf),
+                CaRegistrarError::InvalidMerkleProof
+            );
+        }
+    }
+
+    // An existing subdomain can only be taken over once it no longer resolves
+    let subdomain_record = &context.accounts.subdomain_record;
+    require!(
+        subdomain_record.parent == Pubkey::default() || !subdomain_record.is_resolvable(parent, current_timestamp),
+        CaRegistrarError::SubdomainNotAvailable
+    );
+
+    // Subdomains are bought for at least the minimum registration duration, and cannot outlive the current
+    // registration of their parent
+    let expiry_timestamp = calculate_period_end(current_timestamp, &period)?;
+    require!(
+        expiry_timestamp - current_timestamp >= context.accounts.program_state.min_registration_seconds,
+        CaRegistrarError::RegistrationPeriodTooShort
+    );
+    require!(
+        expiry_timestamp <= parent.expiry_timestamp,
+        CaRegistrarError::SubdomainExceedsParentExpiry
+    );
+
+    // Charge the prorated price, split between the parent owner and the protocol
+    let price = calculate_price_usd(subdomain_policy.price_per_year, &period, 0, None)?;
+    require!(
+        price > 0 || subdomain_policy.price_per_year == 0,
+        CaRegistrarError::SubdomainPriceTooLow
+    );
+    let protocol_fee = collect_subdomain_payment(context.accounts, price)?;
+
+    // Write subdomain record
+    let parent_key = parent.key();
+    let parent_registration_generation = parent.registration_generation;
+    let subdomain_record = &mut context.accounts.subdomain_record;
+    subdomain_record.parent = parent_key;
+    subdomain_record.label = label;
+    subdomain_record.owner = subdomain_owner;
+    subdomain_record.parent_registration_generation = parent_registration_generation;
+    subdomain_record.addresses = addresses;
+    subdomain_record.bump = context.bumps.subdomain_record;
+    subdomain_record.expiry_timestamp = expiry_timestamp;
+    subdomain_record.fuses = 0;
+
+    emit_event!(context, SubdomainRegistered {
+        domain_name: context.accounts.parent_domain_record.domain_name.clone(),
+        label: subdomain_record.label.clone(),
+        owner: subdomain_owner,
+        buyer: context.accounts.buyer.key(),
+        addresses: subdomain_record.addresses.clone(),
+        payment_mint: context.accounts.subdomain_policy.payment_mint,
+        price,
+        protocol_fee,
+        expiry_timestamp,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Subdomain {}.{} registered until {} with owner {}",
+        subdomain_record.label, context.accounts.parent_domain_record.domain_name, expiry_timestamp, subdomain_owner);
+
+    Ok(())
+}
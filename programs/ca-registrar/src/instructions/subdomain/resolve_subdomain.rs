@@ -0,0 +1,70 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::*;
+use crate::state::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for resolving subdomain instruction
+/// 
+/// This read-only instruction returns the addresses of a subdomain, falling back to the wildcard record of the
+/// parent. The subdomain and wildcard accounts are derived from their seeds and may not exist.
+#[derive(Accounts)]
+#[instruction(domain_name: String, label: String)]
+pub struct ResolveSubdomainAccountConstraints<'info> {
+    /// Parent domain record
+    #[account(
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump = parent_domain_record.bump,
+    )]
+    pub parent_domain_record: Account<'info, DomainRecord>,
+
+    /// CHECK: Subdomain record PDA, read only if it exists
+    #[account(
+        seeds = [SUBDOMAIN_RECORD_SEED, parent_domain_record.key().as_ref(), &hash(label.as_bytes()).to_bytes()],
+        bump
+    )]
+    pub subdomain_record: UncheckedAccount<'info>,
+
+    /// CHECK: Wildcard record PDA, read only if it exists
+    #[account(
+        seeds = [WILDCARD_RECORD_SEED, parent_domain_record.key().as_ref()],
+        bump
+    )]
+    pub wildcard_record: UncheckedAccount<'info>,
+}
+
+/// Resolve the addresses of a subdomain, returned as the instruction return data
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// 
+/// # Errors
+/// * `NameNotResolvable` - Neither the subdomain nor the wildcard record resolves
+pub fn resolve_subdomain_handler(
+    context: Context<ResolveSubdomainAccountConstraints>,
+) -> Result<Vec<ChainAddress>> {
+    let subdomain_record = load_if_initialized::<SubdomainRecord>(&context.accounts.subdomain_record)?;
+    let wildcard_record = load_if_initialized::<WildcardRecord>(&context.accounts.wildcard_record)?;
+
+    let addresses = resolve_subdomain_addresses(
+        &context.accounts.parent_domain_record,
+        subdomain_record.as_ref(),
+        wildcard_record.as_ref(),
+        get_current_timestamp()?,
+    ).ok_or(CaRegistrarError::NameNotResolvable)?;
+
+    Ok(addresses.to_vec())
+}
+
+/// Deserialize a program account, or None if it was never created or was closed
+fn load_if_initialized<T: AccountDeserialize + Owner>(account: &AccountInfo) -> Result<Option<T>> {
+    if account.owner != &T::owner() || account.data_is_empty() {
+        return Ok(None);
+    }
+
+    let data = account.try_borrow_data()?;
+    Ok(Some(T::try_deserialize(&mut &data[..])?))
+}
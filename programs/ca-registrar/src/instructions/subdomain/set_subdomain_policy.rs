@@ -0,0 +1,88 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::subdomain::utils::*;
+
+/// Account constraints for setting subdomain policy instruction
+/// 
+/// This instruction allows the owner of an unexpired domain to open subdomain registration to the public,
+/// restrict it to an allowlist or close it, and to set its price.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain_name: String)]
+pub struct SetSubdomainPolicyAccountConstraints<'info> {
+    /// Parent domain owner, pays for the policy account
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Parent domain record
+    #[account(
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump = parent_domain_record.bump,
+        constraint = parent_domain_record.owner == owner.key() @ CaRegistrarError::NotDomainOwner,
+    )]
+    pub parent_domain_record: Account<'info, DomainRecord>,
+
+    /// Subdomain policy account, created on first use
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ANCHOR_DISCRIMINATOR + SubdomainPolicy::INIT_SPACE,
+        seeds = [SUBDOMAIN_POLICY_SEED, parent_domain_record.key().as_ref()],
+        bump
+    )]
+    pub subdomain_policy: Account<'info, SubdomainPolicy>,
+
+    /// Solana system program, used for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the subdomain issuance policy of a domain
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `mode` - Who can register subdomains
+/// * `payment_mint` - SPL token mint used for payment, None for SOL
+/// * `price_per_year` - Price of a one-year subdomain in lamports or token base units
+/// 
+/// # Errors
+/// * `NotDomainOwner` - Caller is not the parent domain owner
+/// * `ParentDomainInactive` - Parent domain is expired or suspended
+/// * `FuseBurned` - CANNOT_CREATE_SUBDOMAIN fuse is burned on the parent
+pub fn set_subdomain_policy_handler(
+    context: Context<SetSubdomainPolicyAccountConstraints>,
+    mode: SubdomainIssuanceMode,
+    payment_mint: Option<Pubkey>,
+    price_per_year: u64,
+) -> Result<()> {
+    // Only an active parent can issue subdomains
+    let current_timestamp = get_current_timestamp()?;
+    let parent = &context.accounts.parent_domain_record;
+    ensure_parent_can_issue(parent, current_timestamp)?;
+
+    // Write policy
+    let subdomain_policy = &mut context.accounts.subdomain_policy;
+    subdomain_policy.parent = parent.key();
+    subdomain_policy.parent_registration_generation = parent.registration_generation;
+    subdomain_policy.mode = mode;
+    subdomain_policy.payment_mint = payment_mint;
+    subdomain_policy.price_per_year = price_per_year;
+    subdomain_policy.bump = context.bumps.subdomain_policy;
+
+    emit_event!(context, SubdomainPolicyUpdated {
+        domain_name: parent.domain_name.clone(),
+        mode,
+        payment_mint,
+        price_per_year,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Subdomain policy of {} set to {:?}, {} per year", parent.domain_name, mode, price_per_year);
+
+    Ok(())
+}
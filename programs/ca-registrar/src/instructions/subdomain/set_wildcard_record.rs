@@ -0,0 +1,83 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::subdomain::utils::*;
+
+/// Account constraints for setting wildcard record instruction
+/// 
+/// This instruction allows the owner of an unexpired domain to set the default addresses of its subdomains,
+/// returned for any label without a subdomain record of its own.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain_name: String)]
+pub struct SetWildcardRecordAccountConstraints<'info> {
+    /// Parent domain owner, pays for the wildcard account
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Parent domain record
+    #[account(
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump = parent_domain_record.bump,
+        constraint = parent_domain_record.owner == owner.key() @ CaRegistrarError::NotDomainOwner,
+    )]
+    pub parent_domain_record: Account<'info, DomainRecord>,
+
+    /// Wildcard record, created on first use
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ANCHOR_DISCRIMINATOR + WildcardRecord::INIT_SPACE,
+        seeds = [WILDCARD_RECORD_SEED, parent_domain_record.key().as_ref()],
+        bump
+    )]
+    pub wildcard_record: Account<'info, WildcardRecord>,
+
+    /// Solana system program, used for account creation
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the wildcard record of a domain
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `addresses` - List of blockchain addresses returned for subdomains without their own record
+/// 
+/// # Errors
+/// * `NotDomainOwner` - Caller is not the parent domain owner
+/// * `ParentDomainInactive` - Parent domain is expired or suspended
+/// * `FuseBurned` - CANNOT_CREATE_SUBDOMAIN fuse is burned on the parent
+/// * `TooManyAddresses` - Number of addresses exceeds the limit
+pub fn set_wildcard_record_handler(
+    context: Context<SetWildcardRecordAccountConstraints>,
+    addresses: Vec<ChainAddress>,
+) -> Result<()> {
+    require!(addresses.len() <= 10, CaRegistrarError::TooManyAddresses);
+
+    // A wildcard answers for every subdomain, so it requires the right to issue them
+    let current_timestamp = get_current_timestamp()?;
+    let parent = &context.accounts.parent_domain_record;
+    ensure_parent_can_issue(parent, current_timestamp)?;
+
+    // Write wildcard record
+    let wildcard_record = &mut context.accounts.wildcard_record;
+    wildcard_record.parent = parent.key();
+    wildcard_record.parent_registration_generation = parent.registration_generation;
+    wildcard_record.addresses = addresses;
+    wildcard_record.bump = context.bumps.wildcard_record;
+
+    emit_event!(context, WildcardRecordUpdated {
+        domain_name: parent.domain_name.clone(),
+        addresses: wildcard_record.addresses.clone(),
+        timestamp: current_timestamp,
+    });
+
+    msg!("Wildcard record of {} set", parent.domain_name);
+
+    Ok(())
+}
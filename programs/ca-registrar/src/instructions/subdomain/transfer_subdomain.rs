@@ -0,0 +1,76 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::subdomain::utils::*;
+
+/// Account constraints for transferring subdomain ownership instruction
+/// 
+/// This instruction allows subdomain owners to transfer their subdomain to another user.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferSubdomainAccountConstraints<'info> {
+    /// Current subdomain owner
+    pub owner: Signer<'info>,
+
+    /// Parent domain record
+    #[account(address = subdomain_record.parent)]
+    pub parent_domain_record: Account<'info, DomainRecord>,
+
+    /// Subdomain record to transfer
+    #[account(
+        mut,
+        seeds = [SUBDOMAIN_RECORD_SEED, subdomain_record.parent.as_ref(), &hash(subdomain_record.label.as_bytes()).to_bytes()],
+        bump = subdomain_record.bump,
+        has_one = owner @ CaRegistrarError::NotSubdomainOwner,
+    )]
+    pub subdomain_record: Account<'info, SubdomainRecord>,
+}
+
+/// Transfer subdomain ownership to a new user
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `new_owner` - Public key of the new owner
+/// 
+/// # Errors
+/// * `NotSubdomainOwner` - Caller is not the subdomain owner
+/// * `ParentDomainInactive` - Parent domain is expired, suspended or was registered again
+/// * `FuseBurned` - CANNOT_TRANSFER fuse is burned
+pub fn transfer_subdomain_handler(
+    context: Context<TransferSubdomainAccountConstraints>,
+    new_owner: Pubkey,
+) -> Result<()> {
+    // Subdomains can only be modified while their parent is active
+    let current_timestamp = get_current_timestamp()?;
+    let subdomain_record = &mut context.accounts.subdomain_record;
+    ensure_subdomain_active(subdomain_record, &context.accounts.parent_domain_record, current_timestamp)?;
+
+    // Verify the fuse is not burned
+    require!(
+        !subdomain_record.is_fuse_burned(FUSE_CANNOT_TRANSFER, &context.accounts.parent_domain_record, current_timestamp),
+        CaRegistrarError::FuseBurned
+    );
+
+    // Update subdomain owner
+    let previous_owner = subdomain_record.owner;
+    subdomain_record.owner = new_owner;
+
+    emit_event!(context, SubdomainTransferred {
+        domain_name: context.accounts.parent_domain_record.domain_name.clone(),
+        label: subdomain_record.label.clone(),
+        previous_owner,
+        new_owner,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Transferred subdomain {}.{} to new owner {}",
+        subdomain_record.label, context.accounts.parent_domain_record.domain_name, new_owner);
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::subdomain::utils::*;
+
+/// Account constraints for updating subdomain addresses instruction
+/// 
+/// This instruction allows subdomain owners to update the list of blockchain addresses associated with the subdomain.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateSubdomainAddressesAccountConstraints<'info> {
+    /// Subdomain owner
+    pub owner: Signer<'info>,
+
+    /// Parent domain record
+    #[account(address = subdomain_record.parent)]
+    pub parent_domain_record: Account<'info, DomainRecord>,
+
+    /// Subdomain record to update
+    #[account(
+        mut,
+        seeds = [SUBDOMAIN_RECORD_SEED, subdomain_record.parent.as_ref(), &hash(subdomain_record.label.as_bytes()).to_bytes()],
+        bump = subdomain_record.bump,
+        has_one = owner @ CaRegistrarError::NotSubdomainOwner,
+    )]
+    pub subdomain_record: Account<'info, SubdomainRecord>,
+}
+
+/// Update blockchain addresses associated with a subdomain
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `addresses` - New list of blockchain addresses, completely replacing the existing list
+/// 
+/// # Errors
+/// * `NotSubdomainOwner` - Caller is not the subdomain owner
+/// * `ParentDomainInactive` - Parent domain is expired, suspended or was registered again
+/// * `FuseBurned` - CANNOT_SET_ADDRESSES fuse is burned
+/// * `TooManyAddresses` - Number of addresses exceeds the limit
+pub fn update_subdomain_addresses_handler(
+    context: Context<UpdateSubdomainAddressesAccountConstraints>,
+    addresses: Vec<ChainAddress>,
+) -> Result<()> {
+    // Validate address count
+    require!(addresses.len() <= 10, CaRegistrarError::TooManyAddresses);
+
+    // Subdomains can only be modified while their parent is active
+    let current_timestamp = get_current_timestamp()?;
+    let subdomain_record = &mut context.accounts.subdomain_record;
+    ensure_subdomain_active(subdomain_record, &context.accounts.parent_domain_record, current_timestamp)?;
+
+    // Verify the fuse is not burned
+    require!(
+        !subdomain_record.is_fuse_burned(FUSE_CANNOT_SET_ADDRESSES, &context.accounts.parent_domain_record, current_timestamp),
+        CaRegistrarError::FuseBurned
+    );
+
+    // Update address list in subdomain record
+    let previous_addresses = std::mem::replace(&mut subdomain_record.addresses, addresses);
+
+    emit_event!(context, SubdomainAddressesUpdated {
+        domain_name: context.accounts.parent_domain_record.domain_name.clone(),
+        label: subdomain_record.label.clone(),
+        previous_addresses,
+        new_addresses: subdomain_record.addresses.clone(),
+        timestamp: current_timestamp,
+    });
+
+    msg!("Updated addresses for subdomain {}.{}",
+        subdomain_record.label, context.accounts.parent_domain_record.domain_name);
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface;
+use crate::constants::*;
+use crate::state::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+use crate::instructions::subdomain::RegisterSubdomainAccountConstraints;
+
+/// Validate a subdomain label: 1 to 63 characters, without dots or the wildcard character
+pub fn validate_subdomain_label(label: &str) -> Result<()> {
+    require!(
+        !label.is_empty() && label.len() <= MAX_SUBDOMAIN_LABEL_LENGTH && !label.contains(['.', '*']),
+        CaRegistrarError::InvalidSubdomainLabel
+    );
+
+    Ok(())
+}
+
+/// Verify the parent domain is neither expired nor suspended
+pub fn ensure_parent_active(parent: &DomainRecord, current_timestamp: i64) -> Result<()> {
+    require!(
+        parent.is_resolvable(current_timestamp),
+        CaRegistrarError::ParentDomainInactive
+    );
+
+    Ok(())
+}
+
+/// Verify the parent domain can issue subdomains, i.e. it is active and its CANNOT_CREATE_SUBDOMAIN fuse is not burned
+pub fn ensure_parent_can_issue(parent: &DomainRecord, current_timestamp: i64) -> Result<()> {
+    ensure_parent_active(parent, current_timestamp)?;
+    require!(
+        !parent.is_fuse_burned(FUSE_CANNOT_CREATE_SUBDOMAIN, current_timestamp),
+        CaRegistrarError::FuseBurned
+    );
+
+    Ok(())
+}
+
+/// Verify a subdomain can be modified, i.e. it belongs to the current registration of an active parent
+pub fn ensure_subdomain_active(
+    subdomain: &SubdomainRecord,
+    parent: &DomainRecord,
+    current_timestamp: i64,
+) -> Result<()> {
+    require!(
+        subdomain.is_resolvable(parent, current_timestamp),
+        CaRegistrarError::ParentDomainInactive
+    );
+
+    Ok(())
+}
+
+/// Charge a subdomain price, paying the protocol fee to the treasury and the rest to the parent owner
+/// 
+/// SOL protocol fees accumulate in the program state like registration fees, token protocol fees are paid
+/// directly to a token account of the treasurer. Returns the protocol fee.
+pub fn collect_subdomain_payment<'info>(accounts: &RegisterSubdomainAccountConstraints<'info>, price: u64) -> Result<u64> {
+    if price == 0 {
+        return Ok(0);
+    }
+
+    let protocol_fee = calculate_share(price, accounts.program_state.subdomain_protocol_fee_bps)?;
+    let parent_owner_amount = price - protocol_fee;
+
+    match accounts.subdomain_policy.payment_mint {
+        None => {
+            let pay = |to: AccountInfo<'info>, amount: u64| -> Result<()> {
+                if amount == 0 {
+                    return Ok(());
+                }
+                system_program::transfer(
+                    CpiContext::new(
+                        accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: accounts.buyer.to_account_info(),
+                            to,
+                        },
+                    ),
+                    amount,
+                )
+            };
+            pay(accounts.program_state.to_account_info(), protocol_fee)?;
+            pay(accounts.parent_owner.to_account_info(), parent_owner_amount)?;
+        }
+        Some(payment_mint) => {
+            let (Some(mint), Some(buyer_token_account), Some(parent_owner_token_account), Some(treasurer_token_account), Some(token_program)) = (
+                accounts.payment_mint.as_ref(),
+                accounts.buyer_token_account.as_ref(),
+                accounts.parent_owner_token_account.as_ref(),
+                accounts.treasurer_token_account.as_ref(),
+                accounts.token_program.as_ref(),
+            ) else {
+                return err!(CaRegistrarError::InvalidPaymentAccounts);
+            };
+            require!(
+                mint.key() == payment_mint
+                    && parent_owner_token_account.mint == payment_mint
+                    && parent_owner_token_account.owner == accounts.parent_owner.key()
+                    && treasurer_token_account.mint == payment_mint
+                    && treasurer_token_account.owner == accounts.admin_roles.treasurer,
+                CaRegistrarError::InvalidPaymentAccounts
+            );
+
+            let pay = |to: AccountInfo<'info>, amount: u64| -> Result<()> {
+                if amount == 0 {
+                    return Ok(());
+                }
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        token_program.to_account_info(),
+                        token_interface::TransferChecked {
+                            from: buyer_token_account.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to,
+                            authority: accounts.buyer.to_account_info(),
+                        },
+                    ),
+                    amount,
+                    mint.decimals,
+                )
+            };
+            pay(treasurer_token_account.to_account_info(), protocol_fee)?;
+            pay(parent_owner_token_account.to_account_info(), parent_owner_amount)?;
+        }
+    }
+
+    msg!("Paid {} to the parent owner and {} protocol fee", parent_owner_amount, protocol_fee);
+
+    Ok(protocol_fee)
+}
@@ -3,12 +3,14 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::state::*;
+use crate::events::*;
 use crate::instructions::utils::*;
 use crate::error::CaRegistrarError;
 
 /// Account constraints for transferring domain ownership instruction
 /// 
 /// This instruction allows domain owners to transfer their domain to another user.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(domain_name: String)]
 pub struct TransferDomainAccountConstraints<'info> {
@@ -19,6 +21,7 @@ pub struct TransferDomainAccountConstraints<'info> {
     #[account(
         mut,
         has_one = owner,
+        constraint = !domain_record.suspended @ CaRegistrarError::DomainSuspended,
         seeds = [DOMAIN_RECORD_SEED, domain_record.domain_name.as_bytes()],
         bump = domain_record.bump
     )]
@@ -33,6 +36,8 @@ pub struct TransferDomainAccountConstraints<'info> {
 /// 
 /// # Errors
 /// * `DomainExpired` - Domain has expired
+/// * `DomainSuspended` - Domain is suspended
+/// * `FuseBurned` - CANNOT_TRANSFER fuse is burned
 pub fn transfer_domain_handler(
     context: Context<TransferDomainAccountConstraints>,
     new_owner: Pubkey,
@@ -47,9 +52,23 @@ pub fn transfer_domain_handler(
         CaRegistrarError::DomainExpired
     );
     
+    // Verify the transfer fuse is not burned
+    require!(
+        !domain_record.is_fuse_burned(FUSE_CANNOT_TRANSFER, current_timestamp),
+        CaRegistrarError::FuseBurned
+    );
+    
     // Update domain owner
+    let previous_owner = domain_record.owner;
     domain_record.owner = new_owner;
     
+    emit_event!(context, DomainTransferred {
+        domain_name: domain_record.domain_name.clone(),
+        previous_owner,
+        new_owner,
+        timestamp: current_timestamp,
+    });
+    
     msg!("Transferred domain {} to new owner {}", domain_record.domain_name, new_owner);
     
     Ok(())
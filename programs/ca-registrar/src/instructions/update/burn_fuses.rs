@@ -0,0 +1,72 @@
+#![allow(unexpected_cfgs)]
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::events::*;
+use crate::error::CaRegistrarError;
+use crate::instructions::utils::*;
+
+/// Account constraints for burning domain fuses instruction
+/// 
+/// This instruction allows domain owners to irrevocably give up rights on their domain until it expires.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(domain_name: String)]
+pub struct BurnFusesAccountConstraints<'info> {
+    /// Domain owner
+    pub owner: Signer<'info>,
+
+    /// Domain record to burn fuses on
+    #[account(
+        mut,
+        seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
+        bump = domain_record.bump,
+        has_one = owner @ CaRegistrarError::NotDomainOwner,
+        constraint = !domain_record.suspended @ CaRegistrarError::DomainSuspended,
+    )]
+    pub domain_record: Account<'info, DomainRecord>,
+}
+
+/// Burn permission fuses on a domain, burned fuses can never be cleared
+/// 
+/// # Parameters
+/// * `context` - Instruction context, containing all relevant accounts
+/// * `fuses` - Fuses to burn, any of CANNOT_TRANSFER, CANNOT_SET_ADDRESSES, CANNOT_CREATE_SUBDOMAIN and CANNOT_RELEASE
+/// 
+/// # Errors
+/// * `NotDomainOwner` - Caller is not the domain owner
+/// * `DomainSuspended` - Domain is suspended
+/// * `DomainExpired` - Domain has expired
+/// * `InvalidFuses` - No fuses given or a fuse cannot be burned on domains
+pub fn burn_fuses_handler(
+    context: Context<BurnFusesAccountConstraints>,
+    fuses: u32,
+) -> Result<()> {
+    require!(
+        fuses != 0 && fuses & !DOMAIN_FUSES == 0,
+        CaRegistrarError::InvalidFuses
+    );
+    
+    // Verify domain is not expired
+    let current_timestamp = get_current_timestamp()?;
+    let domain_record = &mut context.accounts.domain_record;
+    require!(
+        !domain_record.is_expired(current_timestamp),
+        CaRegistrarError::DomainExpired
+    );
+    
+    // Fuses are only ever set, never cleared
+    domain_record.fuses |= fuses;
+    
+    emit_event!(context, FusesBurned {
+        domain_name: domain_record.domain_name.clone(),
+        burned_fuses: fuses,
+        fuses: domain_record.fuses,
+        timestamp: current_timestamp,
+    });
+    
+    msg!("Burned fuses {:#x} on domain {}", fuses, domain_record.domain_name);
+    
+    Ok(())
+}
@@ -1,2 +1,5 @@
 pub use update_addresses::*;
-mod update_addresses; 
\ No newline at end of file
+mod update_addresses;
+
+pub use burn_fuses::*;
+mod burn_fuses;
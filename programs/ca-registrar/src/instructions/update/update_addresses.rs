@@ -3,12 +3,14 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::state::*;
+use crate::events::*;
 use crate::error::CaRegistrarError;
 use crate::instructions::utils::*;
 
 /// Account constraints for updating domain addresses instruction
 /// 
 /// This instruction allows domain owners to update the list of blockchain addresses associated with the domain.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(domain_name: String)]
 pub struct UpdateAddressesAccountConstraints<'info> {
@@ -21,6 +23,7 @@ pub struct UpdateAddressesAccountConstraints<'info> {
         seeds = [DOMAIN_RECORD_SEED, domain_name.as_bytes()],
         bump = domain_record.bump,
         has_one = owner @ CaRegistrarError::NotDomainOwner,
+        constraint = !domain_record.suspended @ CaRegistrarError::DomainSuspended,
     )]
     pub domain_record: Account<'info, DomainRecord>,
 }
@@ -33,6 +36,8 @@ pub struct UpdateAddressesAccountConstraints<'info> {
 /// 
 /// # Errors
 /// * `NotDomainOwner` - Caller is not the domain owner
+/// * `DomainSuspended` - Domain is suspended
+/// * `FuseBurned` - CANNOT_SET_ADDRESSES fuse is burned
 /// * `DomainExpired` - Domain has expired
 /// * `TooManyAddresses` - Number of addresses exceeds the limit
 pub fn update_addresses_handler(
@@ -55,8 +60,21 @@ pub fn update_addresses_handler(
         CaRegistrarError::DomainExpired
     );
     
+    // Verify the addresses fuse is not burned
+    require!(
+        !domain_record.is_fuse_burned(FUSE_CANNOT_SET_ADDRESSES, current_timestamp),
+        CaRegistrarError::FuseBurned
+    );
+    
     // Update address list in domain record
-    domain_record.addresses = addresses;
+    let previous_addresses = std::mem::replace(&mut domain_record.addresses, addresses);
+    
+    emit_event!(context, AddressesUpdated {
+        domain_name: domain_record.domain_name.clone(),
+        previous_addresses,
+        new_addresses: domain_record.addresses.clone(),
+        timestamp: current_timestamp,
+    });
     
     msg!("Updated addresses for domain {}", domain_record.domain_name);
     
@@ -1,5 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use crate::constants::*;
+use crate::state::*;
+use crate::error::CaRegistrarError;
 
 // get current timestamp
 pub fn get_current_timestamp() -> Result<i64> {
@@ -7,3 +14,222 @@ pub fn get_current_timestamp() -> Result<i64> {
     Ok(clock.unix_timestamp)
 }
 
+/// Event authority of an `#[event_cpi]` instruction, in the shape `emit_cpi!` reads from `ctx`
+/// 
+/// Lets helpers shared by several instructions emit events, and handlers emit them while other accounts are
+/// mutably borrowed.
+pub struct EventCpiContext<'a, 'info> {
+    pub accounts: EventCpiAccounts<'a, 'info>,
+    pub bumps: EventCpiBumps,
+}
+
+pub struct EventCpiAccounts<'a, 'info> {
+    pub event_authority: &'a AccountInfo<'info>,
+}
+
+pub struct EventCpiBumps {
+    pub event_authority: u8,
+}
+
+impl<'a, 'info> EventCpiContext<'a, 'info> {
+    pub fn new(event_authority: &'a AccountInfo<'info>, event_authority_bump: u8) -> Self {
+        Self {
+            accounts: EventCpiAccounts { event_authority },
+            bumps: EventCpiBumps { event_authority: event_authority_bump },
+        }
+    }
+}
+
+/// Event authority of the context of an `#[event_cpi]` instruction
+macro_rules! event_cpi_context {
+    ($context:expr) => {
+        $crate::instructions::utils::EventCpiContext::new(
+            &$context.accounts.event_authority,
+            $context.bumps.event_authority,
+        )
+    };
+}
+pub(crate) use event_cpi_context;
+
+/// Emit an event through a self-CPI to the event authority, which unlike program logs cannot be truncated
+macro_rules! emit_event {
+    ($context:expr, $event:expr) => {{
+        let ctx = $crate::instructions::utils::event_cpi_context!($context);
+        emit_cpi!($event);
+    }};
+}
+pub(crate) use emit_event;
+
+/// Convert a day count since the Unix epoch to a (year, month, day) civil date
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Convert a (year, month, day) civil date to a day count since the Unix epoch
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Number of days in a month of the proleptic Gregorian calendar
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Add calendar months to a Unix timestamp, keeping the time of day
+/// 
+/// The day of the month is kept, or clamped to the last day of shorter months (January 31 plus one month is
+/// February 28 or 29), so 12 months always land on the same calendar date
+pub fn add_calendar_months(timestamp: i64, months: u64) -> Result<i64> {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(SECONDS_PER_DAY));
+    let seconds_of_day = timestamp.rem_euclid(SECONDS_PER_DAY);
+
+    // Whole years must fit in a u32, which keeps the calendar arithmetic below far from overflow
+    let added_years = u32::try_from(months / 12).map_err(|_| error!(CaRegistrarError::MathOverflow))?;
+    let month_index = month - 1 + (months % 12) as i64;
+    let year = year + i64::from(added_years) + month_index / 12;
+    let month = month_index % 12 + 1;
+    let day = day.min(days_in_month(year, month));
+
+    days_from_civil(year, month, day)
+        .checked_mul(SECONDS_PER_DAY)
+        .and_then(|seconds| seconds.checked_add(seconds_of_day))
+        .ok_or(error!(CaRegistrarError::MathOverflow))
+}
+
+/// Calculate a basis-point share of an amount, rounded down
+pub fn calculate_share(amount: u64, share_bps: u16) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(share_bps as u128)
+        .ok_or(error!(CaRegistrarError::MathOverflow))?
+        / BASIS_POINTS_DENOMINATOR as u128;
+
+    u64::try_from(share).map_err(|_| error!(CaRegistrarError::MathOverflow))
+}
+
+/// Calculate registration price in USD cents
+/// 
+/// Prorates the yearly base price to the registration period, then applies the multi-year discount
+/// and the promo code discount, if any
+pub fn calculate_price_usd(
+    base_price_usd: u64,
+    period: &RegistrationPeriod,
+    multi_year_discount_bps: u16,
+    promo_discount: Option<&PromoDiscount>,
+) -> Result<u64> {
+    let (numerator, denominator) = match *period {
+        RegistrationPeriod::Years { count } => (count as u128, 1),
+        RegistrationPeriod::Months { count } => (count as u128, 12),
+        RegistrationPeriod::Seconds { seconds } => (seconds.max(0) as u128, SECONDS_PER_YEAR as u128),
+    };
+    let price_usd = (base_price_usd as u128)
+        .checked_mul(numerator)
+        .ok_or(error!(CaRegistrarError::MathOverflow))?
+        / denominator;
+    let price_usd = u64::try_from(price_usd).map_err(|_| error!(CaRegistrarError::MathOverflow))?;
+
+    // apply multi-year discount
+    let price_usd = price_usd - calculate_share(price_usd, multi_year_discount_bps)?;
+
+    // apply promo code discount
+    let price_usd = match promo_discount {
+        Some(PromoDiscount::Percentage { discount_bps }) => {
+            price_usd - calculate_share(price_usd, *discount_bps)?
+        }
+        Some(PromoDiscount::FixedUsd { amount_usd }) => price_usd.saturating_sub(*amount_usd),
+        None => price_usd,
+    };
+
+    Ok(price_usd)
+}
+
+/// Convert a price in USD cents to lamports at a SOL/USD rate given as a Pyth price and exponent
+pub fn convert_usd_to_lamports(price_usd: u64, sol_usd_price: i64, exponent: i32) -> Result<u64> {
+    // verify price is valid
+    require!(sol_usd_price > 0, CaRegistrarError::InvalidPriceFeed);
+    
+    // calculate lamports
+    let lamports = (price_usd as u128)
+        .checked_mul(LAMPORTS_PER_SOL as u128)
+        .and_then(|value| value.checked_mul(10_u128.checked_pow((-exponent) as u32)?))
+        .ok_or(error!(CaRegistrarError::MathOverflow))?
+        / (sol_usd_price as u128 * 100);
+    
+    u64::try_from(lamports).map_err(|_| error!(CaRegistrarError::MathOverflow))
+}
+
+/// Read the single signature verified by the Ed25519 program instruction preceding the current one
+/// 
+/// The Ed25519 program fails the whole transaction if the signature is invalid, so a successful read
+/// proves the returned public key signed the returned message.
+pub fn load_preceding_ed25519_signature(instructions_sysvar: &AccountInfo) -> Result<(Pubkey, Vec<u8>)> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, CaRegistrarError::InvalidVoucherSignature);
+
+    let instruction = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    require_keys_eq!(instruction.program_id, ed25519_program::ID, CaRegistrarError::InvalidVoucherSignature);
+
+    // Header: signature count (u8), padding (u8), then one set of u16 offsets per signature
+    let data = &instruction.data;
+    require!(data.len() >= 16 && data[0] == 1, CaRegistrarError::InvalidVoucherSignature);
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let public_key_offset = read_u16(6) as usize;
+    let message_data_offset = read_u16(10) as usize;
+    let message_data_size = read_u16(12) as usize;
+
+    // Signature, public key and message must all be stored in the Ed25519 instruction itself
+    require!(
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX,
+        CaRegistrarError::InvalidVoucherSignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(error!(CaRegistrarError::InvalidVoucherSignature))?;
+    let message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(error!(CaRegistrarError::InvalidVoucherSignature))?;
+
+    let public_key = Pubkey::try_from(public_key)
+        .map_err(|_| error!(CaRegistrarError::InvalidVoucherSignature))?;
+
+    Ok((public_key, message.to_vec()))
+}
+
+/// Hash the fields of a Merkle leaf
+pub fn hash_merkle_leaf(fields: &[&[u8]]) -> [u8; 32] {
+    hashv(&[&[MERKLE_LEAF_PREFIX], fields].concat()).to_bytes()
+}
+
+/// Verify a Merkle proof of inclusion
+/// 
+/// Internal nodes are the SHA-256 hash of the node prefix and the two children in ascending order,
+/// so proofs do not need to encode left/right positions
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed_root = proof.iter().fold(leaf, |node, sibling| {
+        let (first, second) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        hashv(&[MERKLE_NODE_PREFIX, &first, &second]).to_bytes()
+    });
+
+    computed_root == *root
+}
@@ -16,6 +16,7 @@ use state::*;
 
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -29,8 +30,10 @@ pub mod ca_registrar {
         context: Context<InitializeAccountConstraints>, 
         base_price_usd: u64, 
         grace_period_seconds: i64,
+        timelock_delay_seconds: i64,
+        initial_authority: Pubkey,
     ) -> Result<()> {
-        initialize_handler(context, base_price_usd, grace_period_seconds)
+        initialize_handler(context, base_price_usd, grace_period_seconds, timelock_delay_seconds, initial_authority)
     }
 
     pub fn update_price(
@@ -51,6 +54,191 @@ pub mod ca_registrar {
         update_grace_period_handler(context, grace_period_seconds)
     }
 
+    pub fn update_timelock_delay(
+        context: Context<UpdateTimelockDelayAccountConstraints>,
+        timelock_delay_seconds: i64,
+    ) -> Result<()> {
+        update_timelock_delay_handler(context, timelock_delay_seconds)
+    }
+
+    pub fn update_config_bounds(
+        context: Context<UpdateConfigBoundsAccountConstraints>,
+        config_bounds: ConfigBounds,
+    ) -> Result<()> {
+        update_config_bounds_handler(context, config_bounds)
+    }
+
+    pub fn cancel_queued_change(
+        context: Context<CancelQueuedChangeAccountConstraints>,
+        change_id: u64,
+    ) -> Result<()> {
+        cancel_queued_change_handler(context, change_id)
+    }
+
+    pub fn execute_queued_change(
+        context: Context<ExecuteQueuedChangeAccountConstraints>,
+        change_id: u64,
+    ) -> Result<()> {
+        execute_queued_change_handler(context, change_id)
+    }
+
+    pub fn update_role(
+        context: Context<UpdateRoleAccountConstraints>,
+        role: AdminRole,
+    ) -> Result<()> {
+        update_role_handler(context, role)
+    }
+
+    pub fn set_paused(
+        context: Context<SetPausedAccountConstraints>,
+        paused: bool,
+    ) -> Result<()> {
+        set_paused_handler(context, paused)
+    }
+
+    pub fn update_fee_split(
+        context: Context<UpdateFeeSplitAccountConstraints>,
+        fee_destinations: Vec<FeeDestination>,
+    ) -> Result<()> {
+        update_fee_split_handler(context, fee_destinations)
+    }
+
+    pub fn update_multi_year_discounts(
+        context: Context<UpdateMultiYearDiscountsAccountConstraints>,
+        multi_year_discounts: Vec<MultiYearDiscount>,
+    ) -> Result<()> {
+        update_multi_year_discounts_handler(context, multi_year_discounts)
+    }
+
+    pub fn create_promo_code(
+        context: Context<CreatePromoCodeAccountConstraints>,
+        code_hash: [u8; 32],
+        discount: PromoDiscount,
+        max_uses: u32,
+        expiry_timestamp: i64,
+    ) -> Result<()> {
+        create_promo_code_handler(context, code_hash, discount, max_uses, expiry_timestamp)
+    }
+
+    pub fn close_promo_code(
+        context: Context<ClosePromoCodeAccountConstraints>,
+    ) -> Result<()> {
+        close_promo_code_handler(context)
+    }
+
+    pub fn add_reserved_name(
+        context: Context<AddReservedNameAccountConstraints>,
+        name_hash: [u8; 32],
+    ) -> Result<()> {
+        add_reserved_name_handler(context, name_hash)
+    }
+
+    pub fn remove_reserved_name(
+        context: Context<RemoveReservedNameAccountConstraints>,
+    ) -> Result<()> {
+        remove_reserved_name_handler(context)
+    }
+
+    pub fn admin_register_reserved(
+        context: Context<AdminRegisterReservedAccountConstraints>,
+        domain_name: String,
+        period: RegistrationPeriod,
+        addresses: Vec<ChainAddress>,
+        owner: Pubkey,
+    ) -> Result<()> {
+        admin_register_reserved_handler(context, domain_name, period, addresses, owner)
+    }
+
+    pub fn configure_sunrise(
+        context: Context<ConfigureSunriseAccountConstraints>,
+        sunrise_merkle_root: [u8; 32],
+        general_availability_timestamp: i64,
+    ) -> Result<()> {
+        configure_sunrise_handler(context, sunrise_merkle_root, general_availability_timestamp)
+    }
+
+    pub fn update_airdrop_root(
+        context: Context<UpdateAirdropRootAccountConstraints>,
+        airdrop_merkle_root: [u8; 32],
+    ) -> Result<()> {
+        update_airdrop_root_handler(context, airdrop_merkle_root)
+    }
+
+    pub fn update_registration_horizon(
+        context: Context<UpdateRegistrationHorizonAccountConstraints>,
+        max_registration_horizon_seconds: i64,
+    ) -> Result<()> {
+        update_registration_horizon_handler(context, max_registration_horizon_seconds)
+    }
+
+    pub fn update_min_registration_duration(
+        context: Context<UpdateMinRegistrationDurationAccountConstraints>,
+        min_registration_seconds: i64,
+    ) -> Result<()> {
+        update_min_registration_duration_handler(context, min_registration_seconds)
+    }
+
+    pub fn update_dispute_timelock(
+        context: Context<UpdateDisputeTimelockAccountConstraints>,
+        enabled: bool,
+    ) -> Result<()> {
+        update_dispute_timelock_handler(context, enabled)
+    }
+
+    pub fn admin_suspend_domain(
+        context: Context<AdminSuspendDomainAccountConstraints>,
+        domain_name: String,
+        suspended: bool,
+        reason_code: u16,
+    ) -> Result<()> {
+        admin_suspend_domain_handler(context, domain_name, suspended, reason_code)
+    }
+
+    pub fn admin_force_transfer(
+        context: Context<AdminForceTransferAccountConstraints>,
+        domain_name: String,
+        new_owner: Pubkey,
+        reason_code: u16,
+    ) -> Result<()> {
+        admin_force_transfer_handler(context, domain_name, new_owner, reason_code)
+    }
+
+    pub fn update_release_refund_rate(
+        context: Context<UpdateReleaseRefundRateAccountConstraints>,
+        refund_rate_bps: u16,
+    ) -> Result<()> {
+        update_release_refund_rate_handler(context, refund_rate_bps)
+    }
+
+    pub fn update_subdomain_protocol_fee(
+        context: Context<UpdateSubdomainProtocolFeeAccountConstraints>,
+        fee_bps: u16,
+    ) -> Result<()> {
+        update_subdomain_protocol_fee_handler(context, fee_bps)
+    }
+
+    pub fn create_referrer(
+        context: Context<CreateReferrerAccountConstraints>,
+        share_bps_override: Option<u16>,
+    ) -> Result<()> {
+        create_referrer_handler(context, share_bps_override)
+    }
+
+    pub fn update_referrer(
+        context: Context<UpdateReferrerAccountConstraints>,
+        share_bps_override: Option<u16>,
+        active: bool,
+    ) -> Result<()> {
+        update_referrer_handler(context, share_bps_override, active)
+    }
+
+    pub fn update_default_referral_share(
+        context: Context<UpdateDefaultReferralShareAccountConstraints>,
+        share_bps: u16,
+    ) -> Result<()> {
+        update_default_referral_share_handler(context, share_bps)
+    }
+
     pub fn withdraw_fees(
         context: Context<WithdrawFeesAccountConstraints>, 
     ) -> Result<()> {
@@ -60,29 +248,61 @@ pub mod ca_registrar {
     pub fn register_domain(
         context: Context<RegisterDomainAccountConstraints>, 
         domain_name: String, 
-        years: u64, 
+        period: RegistrationPeriod,
         addresses: Vec<ChainAddress>,
         owner: Pubkey,
+        promo_code: Option<String>,
+    ) -> Result<()> {
+        register_domain_handler(context, domain_name, period, addresses, owner, promo_code)
+    }
+
+    pub fn register_domain_with_voucher(
+        context: Context<RegisterDomainWithVoucherAccountConstraints>,
+        domain_name: String,
+        period: RegistrationPeriod,
+        addresses: Vec<ChainAddress>,
+        owner: Pubkey,
+        voucher: Voucher,
+    ) -> Result<()> {
+        register_domain_with_voucher_handler(context, domain_name, period, addresses, owner, voucher)
+    }
+
+    pub fn claim_sunrise_domain(
+        context: Context<ClaimSunriseDomainAccountConstraints>,
+        domain_name: String,
+        period: RegistrationPeriod,
+        addresses: Vec<ChainAddress>,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        register_domain_handler(context, domain_name, years, addresses, owner)
+        claim_sunrise_domain_handler(context, domain_name, period, addresses, proof)
+    }
+
+    pub fn claim_domain(
+        context: Context<ClaimDomainAccountConstraints>,
+        domain_name: String,
+        owner: Pubkey,
+        expiry_timestamp: i64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        claim_domain_handler(context, domain_name, owner, expiry_timestamp, proof)
     }
 
     pub fn renew_domain(
         context: Context<RenewDomainAccountConstraints>, 
         _domain_name: String, 
-        years: u64,
+        period: RegistrationPeriod,
     ) -> Result<()> {
-        renew_domain_handler(context, years)
+        renew_domain_handler(context, period)
     }
 
     pub fn buy_domain(
         context: Context<BuyDomainAccountConstraints>, 
         _domain_name: String, 
-        years: u64, 
+        period: RegistrationPeriod,
         addresses: Vec<ChainAddress>,
         owner: Pubkey,
     ) -> Result<()> {
-        buy_domain_handler(context, years, addresses, owner)
+        buy_domain_handler(context, period, addresses, owner)
     }
 
     pub fn update_addresses(
@@ -100,4 +320,100 @@ pub mod ca_registrar {
     ) -> Result<()> {
         transfer_domain_handler(context, new_owner)
     }
+
+    pub fn burn_fuses(
+        context: Context<BurnFusesAccountConstraints>,
+        _domain_name: String,
+        fuses: u32,
+    ) -> Result<()> {
+        burn_fuses_handler(context, fuses)
+    }
+
+    pub fn create_subdomain(
+        context: Context<CreateSubdomainAccountConstraints>,
+        _domain_name: String,
+        label: String,
+        subdomain_owner: Pubkey,
+        addresses: Vec<ChainAddress>,
+    ) -> Result<()> {
+        create_subdomain_handler(context, label, subdomain_owner, addresses)
+    }
+
+    pub fn update_subdomain_addresses(
+        context: Context<UpdateSubdomainAddressesAccountConstraints>,
+        addresses: Vec<ChainAddress>,
+    ) -> Result<()> {
+        update_subdomain_addresses_handler(context, addresses)
+    }
+
+    pub fn transfer_subdomain(
+        context: Context<TransferSubdomainAccountConstraints>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        transfer_subdomain_handler(context, new_owner)
+    }
+
+    pub fn delete_subdomain(
+        context: Context<DeleteSubdomainAccountConstraints>,
+    ) -> Result<()> {
+        delete_subdomain_handler(context)
+    }
+
+    pub fn set_subdomain_policy(
+        context: Context<SetSubdomainPolicyAccountConstraints>,
+        _domain_name: String,
+        mode: SubdomainIssuanceMode,
+        payment_mint: Option<Pubkey>,
+        price_per_year: u64,
+    ) -> Result<()> {
+        set_subdomain_policy_handler(context, mode, payment_mint, price_per_year)
+    }
+
+    pub fn register_subdomain(
+        context: Context<RegisterSubdomainAccountConstraints>,
+        _domain_name: String,
+        label: String,
+        period: RegistrationPeriod,
+        subdomain_owner: Pubkey,
+        addresses: Vec<ChainAddress>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        register_subdomain_handler(context, label, period, subdomain_owner, addresses, proof)
+    }
+
+    pub fn burn_subdomain_fuses(
+        context: Context<BurnSubdomainFusesAccountConstraints>,
+        fuses: u32,
+    ) -> Result<()> {
+        burn_subdomain_fuses_handler(context, fuses)
+    }
+
+    pub fn set_wildcard_record(
+        context: Context<SetWildcardRecordAccountConstraints>,
+        _domain_name: String,
+        addresses: Vec<ChainAddress>,
+    ) -> Result<()> {
+        set_wildcard_record_handler(context, addresses)
+    }
+
+    pub fn delete_wildcard_record(
+        context: Context<DeleteWildcardRecordAccountConstraints>,
+    ) -> Result<()> {
+        delete_wildcard_record_handler(context)
+    }
+
+    pub fn resolve_subdomain(
+        context: Context<ResolveSubdomainAccountConstraints>,
+        _domain_name: String,
+        _label: String,
+    ) -> Result<Vec<ChainAddress>> {
+        resolve_subdomain_handler(context)
+    }
+
+    pub fn release_domain(
+        context: Context<ReleaseDomainAccountConstraints>,
+        _domain_name: String,
+    ) -> Result<()> {
+        release_domain_handler(context)
+    }
 }
@@ -5,22 +5,58 @@
 //! Includes functionality for domain lifecycle management like expiration checking.
 
 use anchor_lang::prelude::*;
+use crate::constants::*;
 
 /// Represents an address on a specific blockchain
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
 pub struct ChainAddress {
     pub chain_id: u8,  // Chain type ID (e.g. 0=Solana, 1=Ethereum, 2=Sui...) TODO: needs a link to the chain id reference
     #[max_len(64)]
     pub address: String,
 }
 
+/// Allowed ranges for admin configuration values, enforced by every admin setter
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct ConfigBounds {
+    /// Minimum base price in USD cents
+    pub min_base_price_usd: u64,
+    
+    /// Maximum base price in USD cents
+    pub max_base_price_usd: u64,
+    
+    /// Minimum grace period in seconds
+    pub min_grace_period_seconds: i64,
+    
+    /// Maximum grace period in seconds
+    pub max_grace_period_seconds: i64,
+}
+
+impl ConfigBounds {
+    /// Check if a base price is within bounds
+    pub fn contains_base_price(&self, base_price_usd: u64) -> bool {
+        (self.min_base_price_usd..=self.max_base_price_usd).contains(&base_price_usd)
+    }
+    
+    /// Check if a grace period is within bounds
+    pub fn contains_grace_period(&self, grace_period_seconds: i64) -> bool {
+        (self.min_grace_period_seconds..=self.max_grace_period_seconds).contains(&grace_period_seconds)
+    }
+}
+
+/// Share of withdrawn fees sent to a destination wallet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct FeeDestination {
+    /// Wallet receiving the share (e.g. DAO treasury, ops wallet, partner, incinerator)
+    pub wallet: Pubkey,
+    
+    /// Share of each withdrawal in basis points (100 = 1%)
+    pub share_bps: u16,
+}
+
 /// Global program state - singleton PDA
 #[account]
 #[derive(InitSpace)]
 pub struct ProgramState {
-    /// Admin authority who can update settings and withdraw fees
-    pub authority: Pubkey,
-    
     /// Base price in USD cents (e.g. 500 = $5.00)
     pub base_price_usd: u64,
     
@@ -31,10 +67,408 @@ pub struct ProgramState {
     /// Determines how long a domain can be renewed after expiration
     pub grace_period_seconds: i64,
     
+    /// Whether registrations, renewals and purchases are currently paused
+    pub paused: bool,
+    
+    /// Delay in seconds before a queued admin parameter change can be executed
+    pub timelock_delay_seconds: i64,
+    
+    /// Number of admin parameter changes ever queued, used as the id of the next one
+    pub queued_changes_count: u64,
+    
+    /// Allowed ranges for base price and grace period
+    pub config_bounds: ConfigBounds,
+    
+    /// Revenue split applied by withdraw_fees, the treasurer receives whatever is not allocated here
+    #[max_len(5)]
+    pub fee_destinations: Vec<FeeDestination>,
+    
+    /// Default share of registration fees paid to referrers, in basis points
+    pub default_referral_share_bps: u16,
+    
+    /// Merkle root of the (domain name, claimant) allowlist claimable during sunrise
+    pub sunrise_merkle_root: [u8; 32],
+    
+    /// Timestamp when general availability starts, before it only sunrise claims are accepted
+    pub general_availability_timestamp: i64,
+    
+    /// Merkle root of the pre-allocated (domain name, owner, expiry) leaves claimable through claim_domain
+    pub airdrop_merkle_root: [u8; 32],
+    
+    /// Whether domain suspensions and force-transfers must be queued behind the timelock
+    pub dispute_timelock_enabled: bool,
+    
+    /// Share of the unused paid amount refunded when a domain is released, in basis points
+    pub release_refund_rate_bps: u16,
+    
+    /// Maximum time ahead of now a domain can be registered or renewed to, in seconds
+    pub max_registration_horizon_seconds: i64,
+    
+    /// Minimum duration of a registration or renewal, in seconds
+    pub min_registration_seconds: i64,
+    
+    /// Protocol share of paid subdomain registrations, in basis points
+    pub subdomain_protocol_fee_bps: u16,
+    
+    /// Number of registrations ever started, including purchases of expired domains, the generation of the next one
+    pub registration_generations: u64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+/// Administrative roles that gate the admin instructions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AdminRole {
+    /// Can assign roles, transfer super-admin and change protocol parameters
+    SuperAdmin,
+    /// Can update the base registration price
+    PricingManager,
+    /// Receives withdrawn fees
+    Treasurer,
+    /// Can manage the reserved names list
+    ReservedNameManager,
+    /// Can pause and unpause registrations
+    Pauser,
+    /// Signs vouchers for free or discounted registrations
+    VoucherSigner,
+    /// Can suspend and force-transfer domains in disputes
+    DisputeManager,
+}
+
+/// Admin role assignments - singleton PDA
+#[account]
+#[derive(InitSpace)]
+pub struct AdminRoles {
+    /// Super-admin, implicitly holds every other role
+    pub super_admin: Pubkey,
+    
+    /// Pricing manager
+    pub pricing_manager: Pubkey,
+    
+    /// Treasurer, the destination of withdrawn fees
+    pub treasurer: Pubkey,
+    
+    /// Reserved-name manager
+    pub reserved_name_manager: Pubkey,
+    
+    /// Pauser
+    pub pauser: Pubkey,
+    
+    /// Voucher signer, whose ed25519 signatures are accepted on vouchers
+    pub voucher_signer: Pubkey,
+    
+    /// Dispute manager
+    pub dispute_manager: Pubkey,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl AdminRoles {
+    /// Get the key currently holding a role
+    pub fn holder(&self, role: AdminRole) -> Pubkey {
+        match role {
+            AdminRole::SuperAdmin => self.super_admin,
+            AdminRole::PricingManager => self.pricing_manager,
+            AdminRole::Treasurer => self.treasurer,
+            AdminRole::ReservedNameManager => self.reserved_name_manager,
+            AdminRole::Pauser => self.pauser,
+            AdminRole::VoucherSigner => self.voucher_signer,
+            AdminRole::DisputeManager => self.dispute_manager,
+        }
+    }
+    
+    /// Assign a role to a new key
+    pub fn set_holder(&mut self, role: AdminRole, holder: Pubkey) {
+        match role {
+            AdminRole::SuperAdmin => self.super_admin = holder,
+            AdminRole::PricingManager => self.pricing_manager = holder,
+            AdminRole::Treasurer => self.treasurer = holder,
+            AdminRole::ReservedNameManager => self.reserved_name_manager = holder,
+            AdminRole::Pauser => self.pauser = holder,
+            AdminRole::VoucherSigner => self.voucher_signer = holder,
+            AdminRole::DisputeManager => self.dispute_manager = holder,
+        }
+    }
+    
+    /// Check if a key holds a role, the super-admin holds every role
+    pub fn has_role(&self, role: AdminRole, key: &Pubkey) -> bool {
+        *key == self.super_admin || *key == self.holder(role)
+    }
+}
+
+/// Admin parameter change that only takes effect after the timelock delay
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ConfigChange {
+    /// New base price in USD cents
+    BasePrice { base_price_usd: u64 },
+    /// New grace period in seconds
+    GracePeriod { grace_period_seconds: i64 },
+    /// New timelock delay in seconds
+    TimelockDelay { timelock_delay_seconds: i64 },
+    /// Whether dispute actions must go through the timelock
+    DisputeTimelock { enabled: bool },
+    /// New maximum registration horizon in seconds
+    RegistrationHorizon { max_registration_horizon_seconds: i64 },
+    /// New minimum registration duration in seconds
+    MinRegistrationDuration { min_registration_seconds: i64 },
+    /// New protocol share of paid subdomain registrations in basis points
+    SubdomainProtocolFee { subdomain_protocol_fee_bps: u16 },
+    /// Suspend or reinstate a domain
+    SuspendDomain { domain_record: Pubkey, suspended: bool, reason_code: u16 },
+    /// Reassign a domain to a new owner, only while it is still the registration held by the owner it was decided against
+    ForceTransferDomain {
+        domain_record: Pubkey,
+        registration_generation: u64,
+        current_owner: Pubkey,
+        new_owner: Pubkey,
+        reason_code: u16,
+    },
+}
+
+impl ConfigChange {
+    /// Role allowed to queue and cancel this change
+    pub fn required_role(&self) -> AdminRole {
+        match self {
+            ConfigChange::BasePrice { .. } => AdminRole::PricingManager,
+            ConfigChange::GracePeriod { .. } => AdminRole::SuperAdmin,
+            ConfigChange::TimelockDelay { .. } => AdminRole::SuperAdmin,
+            ConfigChange::DisputeTimelock { .. } => AdminRole::SuperAdmin,
+            ConfigChange::RegistrationHorizon { .. } => AdminRole::SuperAdmin,
+            ConfigChange::MinRegistrationDuration { .. } => AdminRole::PricingManager,
+            ConfigChange::SubdomainProtocolFee { .. } => AdminRole::Treasurer,
+            ConfigChange::SuspendDomain { .. } => AdminRole::DisputeManager,
+            ConfigChange::ForceTransferDomain { .. } => AdminRole::DisputeManager,
+        }
+    }
+    
+    /// Domain record targeted by the change, if it applies to a domain rather than the program state
+    pub fn target_domain_record(&self) -> Option<Pubkey> {
+        match *self {
+            ConfigChange::SuspendDomain { domain_record, .. } => Some(domain_record),
+            ConfigChange::ForceTransferDomain { domain_record, .. } => Some(domain_record),
+            _ => None,
+        }
+    }
+}
+
+/// Queued admin parameter change, publicly visible until it is executed or cancelled
+#[account]
+#[derive(InitSpace)]
+pub struct QueuedChange {
+    /// Sequential id of the change, also used as PDA seed
+    pub change_id: u64,
+    
+    /// Parameter change to apply
+    pub change: ConfigChange,
+    
+    /// Admin who queued the change, receives the account rent back
+    pub proposer: Pubkey,
+    
+    /// Timestamp when the change was queued
+    pub queued_timestamp: i64,
+    
+    /// Timestamp from which the change can be executed
+    pub effective_timestamp: i64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+/// Discount applied to registrations of at least `min_years`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct MultiYearDiscount {
+    /// Minimum registration period in years for the discount to apply
+    pub min_years: u64,
+    
+    /// Discount in basis points (500 = 5% off)
+    pub discount_bps: u16,
+}
+
+/// Pricing configuration - singleton PDA
+#[account]
+#[derive(InitSpace)]
+pub struct PriceConfig {
+    /// Multi-year discount schedule, the largest applicable discount is used
+    #[max_len(5)]
+    pub multi_year_discounts: Vec<MultiYearDiscount>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PriceConfig {
+    /// Get the multi-year discount for a registration period, in basis points
+    pub fn multi_year_discount_bps(&self, years: u64) -> u16 {
+        self.multi_year_discounts
+            .iter()
+            .filter(|discount| years >= discount.min_years)
+            .map(|discount| discount.discount_bps)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Duration of a registration or renewal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RegistrationPeriod {
+    /// Calendar years, ending on the same calendar date
+    Years { count: u32 },
+    /// Calendar months, ending on the same day of the month, or the last day of shorter months
+    Months { count: u32 },
+    /// Exact duration in seconds
+    Seconds { seconds: i64 },
+}
+
+impl RegistrationPeriod {
+    /// Check if the period has no duration
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            RegistrationPeriod::Years { count } | RegistrationPeriod::Months { count } => count == 0,
+            RegistrationPeriod::Seconds { seconds } => seconds <= 0,
+        }
+    }
+    
+    /// Number of whole years in the period, used to select the multi-year discount
+    pub fn whole_years(&self) -> u64 {
+        match *self {
+            RegistrationPeriod::Years { count } => count as u64,
+            RegistrationPeriod::Months { count } => count as u64 / 12,
+            RegistrationPeriod::Seconds { seconds } => (seconds / SECONDS_PER_YEAR).max(0) as u64,
+        }
+    }
+}
+
+/// Discount granted by a promo code
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PromoDiscount {
+    /// Percentage off in basis points (10000 = free)
+    Percentage { discount_bps: u16 },
+    /// Fixed amount off in USD cents
+    FixedUsd { amount_usd: u64 },
+}
+
+/// Promotional discount code, derived from the hash of the code
+#[account]
+#[derive(InitSpace)]
+pub struct PromoCode {
+    /// SHA-256 hash of the code
+    pub code_hash: [u8; 32],
+    
+    /// Discount granted by the code
+    pub discount: PromoDiscount,
+    
+    /// Number of registrations that can still use the code
+    pub remaining_uses: u32,
+    
+    /// Timestamp after which the code can no longer be used
+    pub expiry_timestamp: i64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+/// Voucher signed off-chain by the voucher signer, granting a discounted registration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Voucher {
+    /// Domain name the voucher can be used for, or "*" for any name
+    pub domain_name: String,
+    
+    /// Discount in basis points (10000 = free)
+    pub discount_bps: u16,
+    
+    /// Maximum registration period in years covered by the voucher
+    pub max_years: u64,
+    
+    /// Timestamp after which the voucher can no longer be redeemed
+    pub expiry_timestamp: i64,
+    
+    /// Unique nonce, each nonce can be redeemed only once
+    pub nonce: u64,
+    
+    /// Wallet the voucher was issued to, the only one allowed to redeem it
+    pub buyer: Pubkey,
+}
+
+impl Voucher {
+    /// Check if the voucher can be used for a domain name
+    pub fn covers(&self, domain_name: &str) -> bool {
+        self.domain_name == "*" || self.domain_name == domain_name
+    }
+}
+
+/// Marker of a redeemed voucher nonce, prevents replaying a voucher
+#[account]
+#[derive(InitSpace)]
+pub struct VoucherNonce {
+    /// Redeemed nonce
+    pub nonce: u64,
+    
+    /// Domain registered with the voucher
+    pub domain_record: Pubkey,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+/// Marker of a claimed airdrop leaf, prevents claiming the same allocation twice
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimedLeaf {
+    /// Claimed leaf hash
+    pub leaf: [u8; 32],
+    
+    /// Domain created by the claim
+    pub domain_record: Pubkey,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+/// Reserved or blocked name, derived from the hash of the name
+/// 
+/// Reserved names cannot be registered or bought by the public, only assigned by the reserved-name manager
+#[account]
+#[derive(InitSpace)]
+pub struct ReservedName {
+    /// SHA-256 hash of the domain name (without .ca suffix)
+    pub name_hash: [u8; 32],
+    
     /// Bump seed for PDA derivation
     pub bump: u8,
 }
 
+/// Referral configuration for a partner routing registrations to the registrar
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerConfig {
+    /// Wallet receiving referral payouts
+    pub referrer: Pubkey,
+    
+    /// Share of registration fees in basis points, overriding the program default when set
+    pub share_bps_override: Option<u16>,
+    
+    /// Whether referrals through this config are currently accepted
+    pub active: bool,
+    
+    /// Number of paid registrations, renewals and purchases referred
+    pub referral_count: u64,
+    
+    /// Total lamports paid out to the referrer
+    pub total_paid_lamports: u64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl ReferrerConfig {
+    /// Share of registration fees paid to this referrer, in basis points
+    pub fn share_bps(&self, default_referral_share_bps: u16) -> u16 {
+        self.share_bps_override.unwrap_or(default_referral_share_bps)
+    }
+}
+
 /// Represents a domain record in the CA system
 #[account]
 #[derive(InitSpace)]
@@ -52,23 +486,341 @@ pub struct DomainRecord {
     /// Timestamp when domain was initially registered
     pub registration_timestamp: i64,
     
+    /// Unique generation of the registration, increasing with every new registration or purchase of a name
+    pub registration_generation: u64,
+    
     /// Addresses for different blockchains associated with this domain, max 10
     #[max_len(10)]
     pub addresses: Vec<ChainAddress>,
     
     /// Bump seed for PDA derivation
     pub bump: u8,
+    
+    /// Suspended by the dispute manager, a suspended domain does not resolve and cannot be modified by its owner
+    pub suspended: bool,
+    
+    /// Burned permission fuses (FUSE_* constants), effective until the domain expires
+    pub fuses: u32,
+    
+    /// Lamports kept by the treasury for the time from `paid_timestamp` until expiry, the basis of release refunds
+    pub paid_lamports: u64,
+    
+    /// Timestamp from which `paid_lamports` covers the registration, i.e. the last payment
+    pub paid_timestamp: i64,
 }
 
 impl DomainRecord {
+    /// Share of the paid lamports covering the time left until expiry, nothing once expired
+    pub fn unused_paid_lamports(&self, current_time: i64) -> u64 {
+        if self.paid_lamports == 0 || current_time >= self.expiry_timestamp {
+            return 0;
+        }
+        if current_time <= self.paid_timestamp {
+            return self.paid_lamports;
+        }
+        
+        let remaining = (self.expiry_timestamp - current_time) as u128;
+        let paid_duration = (self.expiry_timestamp - self.paid_timestamp) as u128;
+        (self.paid_lamports as u128 * remaining / paid_duration) as u64
+    }
+    
+    /// Share of the paid lamports covering the whole years left until expiry, nothing once less than a year is left
+    pub fn unused_whole_year_lamports(&self, current_time: i64) -> u64 {
+        if self.paid_lamports == 0 || current_time >= self.expiry_timestamp {
+            return 0;
+        }
+        
+        let whole_years = ((self.expiry_timestamp - current_time) / SECONDS_PER_YEAR) as u128;
+        let paid_duration = (self.expiry_timestamp - self.paid_timestamp.min(current_time)) as u128;
+        let lamports = self.paid_lamports as u128 * whole_years * SECONDS_PER_YEAR as u128 / paid_duration;
+        lamports.min(self.paid_lamports as u128) as u64
+    }
+    
     /// Calculate if a domain is expired
     pub fn is_expired(&self, current_time: i64) -> bool {
         current_time > self.expiry_timestamp
     }
     
+    /// Check if a domain resolves to its addresses, i.e. it is neither expired nor suspended
+    pub fn is_resolvable(&self, current_time: i64) -> bool {
+        !self.suspended && !self.is_expired(current_time)
+    }
+    
+    /// Check if a fuse is burned and still effective, fuses stop applying once the domain expires
+    pub fn is_fuse_burned(&self, fuse: u32, current_time: i64) -> bool {
+        self.fuses & fuse != 0 && !self.is_expired(current_time)
+    }
+    
     /// Check if a domain is in grace period
     pub fn is_in_grace_period(&self, current_time: i64, grace_period_seconds: i64) -> bool {
         current_time > self.expiry_timestamp && 
         current_time <= (self.expiry_timestamp + grace_period_seconds)
     }
 }
+
+/// Subdomain record, a child name issued by the owner of a parent domain (e.g. "pay" under "alice" for "pay.alice.ca")
+/// 
+/// PDA derived from the parent domain record and the hash of the label
+#[account]
+#[derive(InitSpace)]
+pub struct SubdomainRecord {
+    /// Parent domain record
+    pub parent: Pubkey,
+    
+    /// Subdomain label without the parent name (e.g., "pay" for "pay.alice.ca")
+    #[max_len(63)]
+    pub label: String,
+    
+    /// Owner of the subdomain
+    pub owner: Pubkey,
+    
+    /// Registration generation of the parent when the subdomain was created, the subdomain is stale once the
+    /// parent is released or bought by someone else
+    pub parent_registration_generation: u64,
+    
+    /// Addresses for different blockchains associated with this subdomain, max 10
+    #[max_len(10)]
+    pub addresses: Vec<ChainAddress>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+    
+    /// Timestamp when the subdomain expires, i64::MAX for subdomains following their parent
+    pub expiry_timestamp: i64,
+    
+    /// Burned permission fuses (FUSE_* constants), effective until the subdomain or its parent expires
+    pub fuses: u32,
+}
+
+impl SubdomainRecord {
+    /// Check if the subdomain still belongs to the current registration of its parent
+    pub fn is_current(&self, parent: &DomainRecord) -> bool {
+        self.parent_registration_generation == parent.registration_generation
+    }
+    
+    /// Check if a fuse is burned and still effective, fuses stop applying once the subdomain is stale or expired,
+    /// or its parent expired
+    pub fn is_fuse_burned(&self, fuse: u32, parent: &DomainRecord, current_time: i64) -> bool {
+        self.fuses & fuse != 0
+            && self.is_current(parent)
+            && current_time <= self.expiry_timestamp
+            && !parent.is_expired(current_time)
+    }
+    
+    /// Check if the subdomain was bought through the subdomain policy of the current registration of its parent and
+    /// has not expired, owner-created subdomains follow their parent and never expire
+    pub fn is_policy_registration_active(&self, parent: &DomainRecord, current_time: i64) -> bool {
+        self.expiry_timestamp != i64::MAX && self.is_current(parent) && current_time <= self.expiry_timestamp
+    }
+    
+    /// Check if a subdomain resolves to its addresses, i.e. it is current, unexpired and its parent resolves
+    pub fn is_resolvable(&self, parent: &DomainRecord, current_time: i64) -> bool {
+        self.is_current(parent) && current_time <= self.expiry_timestamp && parent.is_resolvable(current_time)
+    }
+}
+
+/// Default addresses of the subdomains of a domain (`*.alice.ca`), PDA derived from the parent domain record
+#[account]
+#[derive(InitSpace)]
+pub struct WildcardRecord {
+    /// Parent domain record
+    pub parent: Pubkey,
+    
+    /// Registration generation of the parent when the record was set, the record lapses with the registration
+    pub parent_registration_generation: u64,
+    
+    /// Addresses for different blockchains returned for subdomains without their own record, max 10
+    #[max_len(10)]
+    pub addresses: Vec<ChainAddress>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl WildcardRecord {
+    /// Check if the wildcard still belongs to the current registration of its parent
+    pub fn is_current(&self, parent: &DomainRecord) -> bool {
+        self.parent_registration_generation == parent.registration_generation
+    }
+}
+
+/// Resolve the addresses of a subdomain
+/// 
+/// Nothing resolves while the parent does not resolve. Otherwise an explicit subdomain record that resolves takes
+/// precedence, and a missing, stale or expired subdomain falls back to the wildcard record of the current
+/// registration of the parent.
+pub fn resolve_subdomain_addresses<'a>(
+    parent: &DomainRecord,
+    subdomain: Option<&'a SubdomainRecord>,
+    wildcard: Option<&'a WildcardRecord>,
+    current_time: i64,
+) -> Option<&'a [ChainAddress]> {
+    if !parent.is_resolvable(current_time) {
+        return None;
+    }
+    
+    if let Some(subdomain) = subdomain.filter(|subdomain| subdomain.is_resolvable(parent, current_time)) {
+        return Some(&subdomain.addresses);
+    }
+    
+    wildcard
+        .filter(|wildcard| wildcard.is_current(parent))
+        .map(|wildcard| wildcard.addresses.as_slice())
+}
+
+/// Who can register subdomains through register_subdomain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum SubdomainIssuanceMode {
+    /// Public registration is disabled
+    Closed,
+    /// Anyone can register any free label
+    Open,
+    /// Only the (label, registrant) pairs of a Merkle tree can be registered
+    Allowlist { merkle_root: [u8; 32] },
+}
+
+/// Public subdomain issuance policy set by a parent domain owner, PDA derived from the parent domain record
+#[account]
+#[derive(InitSpace)]
+pub struct SubdomainPolicy {
+    /// Parent domain record
+    pub parent: Pubkey,
+    
+    /// Registration generation of the parent when the policy was set, the policy lapses with the registration
+    pub parent_registration_generation: u64,
+    
+    /// Who can register subdomains
+    pub mode: SubdomainIssuanceMode,
+    
+    /// SPL token mint used for payment, None for SOL
+    pub payment_mint: Option<Pubkey>,
+    
+    /// Price of a one-year subdomain in lamports or token base units, prorated to the registration period
+    pub price_per_year: u64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+    
+    fn parent(registration_generation: u64) -> DomainRecord {
+        DomainRecord {
+            domain_name: "alice".to_string(),
+            owner: Pubkey::new_unique(),
+            expiry_timestamp: NOW + 365 * 86_400,
+            registration_timestamp: NOW,
+            registration_generation,
+            addresses: Vec::new(),
+            bump: 255,
+            suspended: false,
+            fuses: 0,
+            paid_lamports: 0,
+            paid_timestamp: NOW,
+        }
+    }
+    
+    fn subdomain(parent_registration_generation: u64) -> SubdomainRecord {
+        SubdomainRecord {
+            parent: Pubkey::new_unique(),
+            label: "pay".to_string(),
+            owner: Pubkey::new_unique(),
+            parent_registration_generation,
+            addresses: vec![ChainAddress { chain_id: 0, address: "subdomain".to_string() }],
+            bump: 255,
+            expiry_timestamp: i64::MAX,
+            fuses: FUSE_CANNOT_TRANSFER,
+        }
+    }
+
+    #[test]
+    fn same_second_reregistration_makes_subdomains_stale() {
+        // Released and registered again within the same second, only the generation differs
+        let subdomain = subdomain(7);
+        let reregistered = parent(8);
+        assert_eq!(reregistered.registration_timestamp, parent(7).registration_timestamp);
+        
+        assert!(subdomain.is_current(&parent(7)));
+        assert!(!subdomain.is_current(&reregistered));
+        assert!(!subdomain.is_resolvable(&reregistered, NOW));
+        assert!(!subdomain.is_fuse_burned(FUSE_CANNOT_TRANSFER, &reregistered, NOW));
+    }
+
+    fn wildcard(parent_registration_generation: u64) -> WildcardRecord {
+        WildcardRecord {
+            parent: Pubkey::new_unique(),
+            parent_registration_generation,
+            addresses: vec![ChainAddress { chain_id: 0, address: "wildcard".to_string() }],
+            bump: 255,
+        }
+    }
+    
+    /// First resolved address, empty when the subdomain resolves to an empty list
+    fn resolve<'a>(
+        parent: &DomainRecord,
+        subdomain: Option<&'a SubdomainRecord>,
+        wildcard: Option<&'a WildcardRecord>,
+        current_time: i64,
+    ) -> Option<&'a str> {
+        resolve_subdomain_addresses(parent, subdomain, wildcard, current_time)
+            .map(|addresses| addresses.first().map_or("", |address| address.address.as_str()))
+    }
+
+    #[test]
+    fn explicit_subdomain_takes_precedence_over_wildcard() {
+        let parent = parent(7);
+        let empty = SubdomainRecord { addresses: Vec::new(), ..subdomain(7) };
+        
+        assert_eq!(resolve(&parent, Some(&subdomain(7)), Some(&wildcard(7)), NOW), Some("subdomain"));
+        assert_eq!(resolve(&parent, Some(&empty), Some(&wildcard(7)), NOW), Some(""));
+        assert_eq!(resolve(&parent, None, Some(&wildcard(7)), NOW), Some("wildcard"));
+        assert_eq!(resolve(&parent, None, None, NOW), None);
+    }
+
+    #[test]
+    fn stale_or_expired_subdomain_falls_back_to_wildcard() {
+        let parent = parent(8);
+        let expired = SubdomainRecord { expiry_timestamp: NOW - 1, ..subdomain(8) };
+        
+        assert_eq!(resolve(&parent, Some(&subdomain(7)), Some(&wildcard(8)), NOW), Some("wildcard"));
+        assert_eq!(resolve(&parent, Some(&expired), Some(&wildcard(8)), NOW), Some("wildcard"));
+        assert_eq!(resolve(&parent, Some(&subdomain(7)), None, NOW), None);
+    }
+
+    #[test]
+    fn stale_wildcard_does_not_resolve() {
+        let parent = parent(8);
+        
+        assert_eq!(resolve(&parent, None, Some(&wildcard(7)), NOW), None);
+        assert_eq!(resolve(&parent, Some(&subdomain(7)), Some(&wildcard(7)), NOW), None);
+        assert_eq!(resolve(&parent, Some(&subdomain(8)), Some(&wildcard(7)), NOW), Some("subdomain"));
+    }
+
+    #[test]
+    fn nothing_resolves_under_an_inactive_parent() {
+        let suspended = DomainRecord { suspended: true, ..parent(7) };
+        let expired = parent(7);
+        
+        assert_eq!(resolve(&suspended, Some(&subdomain(7)), Some(&wildcard(7)), NOW), None);
+        assert_eq!(resolve(&expired, None, Some(&wildcard(7)), expired.expiry_timestamp + 1), None);
+    }
+
+    #[test]
+    fn bought_subdomains_are_protected_until_they_expire() {
+        let current_parent = parent(7);
+        let issued = subdomain(7);
+        let bought = SubdomainRecord { expiry_timestamp: NOW + 30 * 86_400, ..subdomain(7) };
+        
+        assert!(!issued.is_policy_registration_active(&current_parent, NOW));
+        assert!(bought.is_policy_registration_active(&current_parent, NOW));
+        assert!(bought.is_policy_registration_active(&current_parent, bought.expiry_timestamp));
+        assert!(!bought.is_policy_registration_active(&current_parent, bought.expiry_timestamp + 1));
+        
+        // A subdomain bought under a previous registration of the parent is stale and can be deleted
+        assert!(!bought.is_policy_registration_active(&parent(8), NOW));
+    }
+}